@@ -22,6 +22,8 @@ type LocalFont<T extends CssVariable | undefined = undefined> = {
   preload?: boolean
   variable?: T
   declarations?: Array<{ prop: string; value: string }>
+  subsets?: string[]
+  unicodeRanges?: string[]
 }
 
 export default function localFont<
@@ -54,20 +54,20 @@ use turbopack_binding::swc::{
 
 pub mod amp_attributes;
 mod auto_cjs;
-pub mod cjs_optimizer;
 pub mod disallow_re_export_all_in_page;
-pub mod named_import_transform;
 pub mod next_dynamic;
 pub mod next_ssg;
-pub mod optimize_barrel;
-pub mod optimize_server_react;
 pub mod page_config;
-pub mod react_remove_properties;
 pub mod react_server_components;
-pub mod remove_console;
-pub mod server_actions;
 pub mod shake_exports;
-mod top_level_binding_collector;
+
+pub use next_transform_cjs_optimizer as cjs_optimizer;
+pub use next_transform_named_import as named_import_transform;
+pub use next_transform_optimize_barrel as optimize_barrel;
+pub use next_transform_optimize_server_react as optimize_server_react;
+pub use next_transform_react_remove_properties as react_remove_properties;
+pub use next_transform_remove_console as remove_console;
+pub use next_transform_server_actions as server_actions;
 
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -0,0 +1,5 @@
+const server = require('next/server')
+
+export async function GET() {
+  return server.Response.json({ ok: true })
+}
@@ -0,0 +1,7 @@
+const Response = require("next/server/response").Response;
+;
+export async function GET() {
+    return Response.json({
+        ok: true
+    });
+}
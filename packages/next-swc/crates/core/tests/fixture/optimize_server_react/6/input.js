@@ -0,0 +1,15 @@
+import { useEffect, useState } from 'react'
+
+export default function Page({ initial }) {
+  const [count, setCount] = useState(initial)
+
+  useEffect(() => {
+    setCount(1)
+  }, [])
+
+  return <div>{count}</div>
+}
+
+export function getServerSideProps() {
+  return { props: { initial: 0 } }
+}
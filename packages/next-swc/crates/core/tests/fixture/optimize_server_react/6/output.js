@@ -0,0 +1,13 @@
+import { useEffect, useState } from 'react';
+export default function Page({ initial }) {
+    const count = initial, setCount = ()=>null;
+    null;
+    return <div>{count}</div>;
+}
+export function getServerSideProps() {
+    return {
+        props: {
+            initial: 0
+        }
+    };
+}
@@ -1,4 +1,12 @@
-use std::{collections::HashMap, future::Future, ops::Deref, sync::Arc};
+use std::{
+    collections::HashMap,
+    future::Future,
+    ops::Deref,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
 use anyhow::{anyhow, Context, Result};
 use napi::{
@@ -53,30 +61,49 @@ pub fn serde_enum_to_string<T: Serialize>(value: &T) -> Result<String> {
         .to_string())
 }
 
+/// The number of root tasks that haven't been disposed yet.
+static LIVE_ROOT_TASKS: AtomicUsize = AtomicUsize::new(0);
+
 /// The root of our turbopack computation.
 pub struct RootTask {
-    #[allow(dead_code)]
     turbo_tasks: Arc<TurboTasks<MemoryBackend>>,
-    #[allow(dead_code)]
     task_id: Option<TaskId>,
 }
 
+impl RootTask {
+    /// Stops the root task, so it's no longer recomputed on changes and its
+    /// captured state can be released. Does nothing if it's already stopped.
+    fn dispose(&mut self) {
+        if let Some(task_id) = self.task_id.take() {
+            self.turbo_tasks.dispose_root_task(task_id);
+            LIVE_ROOT_TASKS.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
 impl Drop for RootTask {
     fn drop(&mut self) {
-        // TODO stop the root task
+        self.dispose();
     }
 }
 
 #[napi]
 pub fn root_task_dispose(
-    #[napi(ts_arg_type = "{ __napiType: \"RootTask\" }")] _root_task: External<RootTask>,
+    #[napi(ts_arg_type = "{ __napiType: \"RootTask\" }")] mut root_task: External<RootTask>,
 ) -> napi::Result<()> {
-    // TODO(alexkirsz) Implement. Not panicking here to avoid crashing the process
-    // when testing.
-    eprintln!("root_task_dispose not yet implemented");
+    root_task.dispose();
     Ok(())
 }
 
+/// Returns the number of root tasks that haven't been disposed yet. Used in
+/// tests to check that subscriptions release their root tasks, so it's only
+/// exported by debug builds.
+#[cfg(debug_assertions)]
+#[napi]
+pub fn root_task_count() -> u32 {
+    LIVE_ROOT_TASKS.load(Ordering::Relaxed) as u32
+}
+
 pub async fn get_issues<T: Send>(source: Vc<T>) -> Result<Vec<ReadRef<PlainIssue>>> {
     let issues = source
         .peek_issues_with_path()
@@ -269,6 +296,7 @@ pub fn subscribe<T: 'static + Send + Sync, F: Future<Output = Result<T>> + Send,
             Ok(Default::default())
         })
     });
+    LIVE_ROOT_TASKS.fetch_add(1, Ordering::Relaxed);
     Ok(External::new(RootTask {
         turbo_tasks,
         task_id: Some(task_id),
@@ -18,16 +18,24 @@ use next_core::{
     next_client_reference::{
         ClientReferenceGraph, ClientReferenceType, NextEcmascriptClientReferenceTransition,
     },
-    next_dynamic::{NextDynamicEntries, NextDynamicTransition},
-    next_edge::route_regex::get_named_middleware_regex,
+    next_dynamic::{add_dynamic_entries_to_manifest, NextDynamicEntries, NextDynamicTransition},
+    next_edge::{
+        entry::edge_entry_global_var_name,
+        paths::{get_edge_function_paths, EdgeFunctionPaths},
+        route_regex::get_named_middleware_regex,
+    },
     next_manifests::{
         AppBuildManifest, AppPathsManifest, BuildManifest, ClientReferenceManifest,
-        EdgeFunctionDefinition, MiddlewareMatcher, MiddlewaresManifestV2, PagesManifest, Regions,
+        EdgeFunctionDefinition, MiddlewareMatcher, MiddlewaresManifestV2, PagesManifest,
+        ReactLoadableManifest, Regions, ServerReferenceManifest, SubresourceIntegrityManifest,
     },
     next_server::{
         get_server_module_options_context, get_server_resolve_options_context,
         get_server_runtime_entries, ServerContextType,
     },
+    next_server_actions::{
+        add_server_actions_to_manifest, create_server_actions_loader, get_server_actions,
+    },
     util::{get_asset_prefix_from_pathname, NextRuntime},
 };
 use serde::{Deserialize, Serialize};
@@ -41,11 +49,12 @@ use turbopack_binding::{
         core::{
             asset::{Asset, AssetContent},
             changed::any_content_changed_of_output_assets,
-            chunk::{ChunkableModule, ChunkingContext, EvaluatableAssets},
+            chunk::{ChunkableModule, ChunkingContext, EvaluatableAsset, EvaluatableAssets},
             file_source::FileSource,
             output::{OutputAsset, OutputAssets},
             virtual_output::VirtualOutputAsset,
         },
+        ecmascript::utils::StringifyJs,
         turbopack::{
             module_options::ModuleOptionsContext, resolve_options_context::ResolveOptionsContext,
             transition::ContextTransition, ModuleAssetContext,
@@ -445,14 +454,18 @@ struct AppEndpoint {
 #[turbo_tasks::value_impl]
 impl AppEndpoint {
     #[turbo_tasks::function]
-    fn app_page_entry(&self, loader_tree: Vc<LoaderTree>) -> Vc<AppEntry> {
-        get_app_page_entry(
+    async fn app_page_entry(&self, loader_tree: Vc<LoaderTree>) -> Result<Vc<AppEntry>> {
+        Ok(get_app_page_entry(
             self.app_project.rsc_module_context(),
             self.app_project.edge_rsc_module_context(),
             loader_tree,
             self.page.clone(),
             self.app_project.project().project_path(),
-        )
+            // The dev server always uses the `development` build id.
+            Vc::cell("development".to_string()),
+            self.app_project.project().next_config_json(),
+            *self.app_project.mode().await?,
+        ))
     }
 
     #[turbo_tasks::function]
@@ -550,13 +563,44 @@ impl AppEndpoint {
             .flatten()
             .collect();
 
-        let app_node_entries: Vec<_> = app_ssr_entries.iter().copied().chain([rsc_entry]).collect();
+        let runtime = app_entry.config.await?.runtime.unwrap_or_default();
 
-        // TODO(alexkirsz) Handle dynamic entries and dynamic chunks.
-        let _dynamic_entries = NextDynamicEntries::from_entries(Vc::cell(
-            app_node_entries.iter().copied().map(Vc::upcast).collect(),
-        ))
-        .await?;
+        let (rsc_chunking_context, ssr_chunking_context) = match runtime {
+            NextRuntime::Edge => (
+                this.app_project.project().edge_rsc_chunking_context(),
+                this.app_project.project().edge_ssr_chunking_context(),
+            ),
+            NextRuntime::NodeJs => (
+                Vc::upcast(this.app_project.project().rsc_chunking_context()),
+                Vc::upcast(this.app_project.project().ssr_chunking_context()),
+            ),
+        };
+
+        // Server components and SSR modules are chunked in different layers, so
+        // their dynamic entries are collected separately.
+        let mut react_loadable_manifest = ReactLoadableManifest::default();
+        client_assets.extend(
+            add_dynamic_entries_to_manifest(
+                &mut react_loadable_manifest,
+                NextDynamicEntries::from_entries(Vc::cell(
+                    app_ssr_entries.iter().copied().map(Vc::upcast).collect(),
+                )),
+                ssr_chunking_context,
+                this.app_project.project().client_chunking_context(),
+                &client_relative_path_ref,
+            )
+            .await?,
+        );
+        client_assets.extend(
+            add_dynamic_entries_to_manifest(
+                &mut react_loadable_manifest,
+                NextDynamicEntries::from_entries(Vc::cell(vec![Vc::upcast(rsc_entry)])),
+                rsc_chunking_context,
+                this.app_project.project().client_chunking_context(),
+                &client_relative_path_ref,
+            )
+            .await?,
+        );
 
         let app_entry_client_references = client_reference_graph
             .entry(Vc::upcast(app_entry.rsc_entry))
@@ -628,6 +672,69 @@ impl AppEndpoint {
         ));
         server_assets.push(build_manifest_output);
 
+        let react_loadable_manifest = serde_json::to_string_pretty(&react_loadable_manifest)?;
+        let react_loadable_manifest_js: Vc<Box<dyn OutputAsset>> =
+            Vc::upcast(VirtualOutputAsset::new(
+                node_root.join(format!(
+                    "server/app{manifest_path_prefix}/{ty}/react-loadable-manifest.js",
+                )),
+                AssetContent::file(
+                    File::from(format!(
+                        "self.__REACT_LOADABLE_MANIFEST={}",
+                        StringifyJs(&react_loadable_manifest)
+                    ))
+                    .into(),
+                ),
+            ));
+        server_assets.push(Vc::upcast(VirtualOutputAsset::new(
+            node_root.join(format!(
+                "server/app{manifest_path_prefix}/{ty}/react-loadable-manifest.json",
+            )),
+            AssetContent::file(File::from(react_loadable_manifest).into()),
+        )));
+
+        // The edge runtime reads the integrity manifest from the global scope,
+        // so it's also emitted as a script for edge entries.
+        let mut sri_manifest_js: Option<Vc<Box<dyn OutputAsset>>> = None;
+        if let Some(algorithm) = this
+            .app_project
+            .project()
+            .next_config()
+            .await?
+            .experimental
+            .sri
+            .as_ref()
+            .and_then(|sri| sri.algorithm)
+        {
+            let mut sri_manifest = SubresourceIntegrityManifest::default();
+            sri_manifest
+                .add_assets(
+                    client_assets.iter().copied(),
+                    &client_relative_path_ref,
+                    algorithm,
+                )
+                .await?;
+            let sri_manifest = serde_json::to_string_pretty(&sri_manifest)?;
+            sri_manifest_js = Some(Vc::upcast(VirtualOutputAsset::new(
+                node_root.join(format!(
+                    "server/app{manifest_path_prefix}/{ty}/subresource-integrity-manifest.js",
+                )),
+                AssetContent::file(
+                    File::from(format!(
+                        "self.__SUBRESOURCE_INTEGRITY_MANIFEST={}",
+                        StringifyJs(&sri_manifest)
+                    ))
+                    .into(),
+                ),
+            )));
+            server_assets.push(Vc::upcast(VirtualOutputAsset::new(
+                node_root.join(format!(
+                    "server/app{manifest_path_prefix}/{ty}/subresource-integrity-manifest.json",
+                )),
+                AssetContent::file(File::from(sri_manifest).into()),
+            )));
+        }
+
         let entry_manifest = ClientReferenceManifest::build_output(
             node_root,
             client_relative_path,
@@ -639,6 +746,61 @@ impl AppEndpoint {
         );
         server_assets.push(entry_manifest);
 
+        // Server actions are exposed to the action handler through a loader
+        // module that is evaluated along with the RSC entry.
+        let actions_context = match runtime {
+            NextRuntime::Edge => this.app_project.edge_rsc_module_context(),
+            NextRuntime::NodeJs => this.app_project.rsc_module_context(),
+        };
+        let actions = get_server_actions(Vc::upcast(rsc_entry));
+        let mut server_reference_manifest = ServerReferenceManifest::default();
+        let has_actions = !actions.await?.is_empty();
+        let actions_loader = if !has_actions {
+            None
+        } else {
+            let loader = create_server_actions_loader(
+                this.app_project.project().project_path(),
+                app_entry.original_name.clone(),
+                actions,
+                Vc::upcast(actions_context),
+            );
+            add_server_actions_to_manifest(
+                &mut server_reference_manifest,
+                &app_entry.original_name,
+                runtime,
+                actions,
+                loader,
+                rsc_chunking_context,
+            )
+            .await?;
+            let Some(loader) =
+                Vc::try_resolve_sidecast::<Box<dyn EvaluatableAsset>>(loader).await?
+            else {
+                bail!("Server actions loader must be evaluatable");
+            };
+            Some(loader)
+        };
+        let server_reference_manifest = serde_json::to_string_pretty(&server_reference_manifest)?;
+        let server_reference_manifest_js: Vc<Box<dyn OutputAsset>> =
+            Vc::upcast(VirtualOutputAsset::new(
+                node_root.join(format!(
+                    "server/app{manifest_path_prefix}/{ty}/server-reference-manifest.js",
+                )),
+                AssetContent::file(
+                    File::from(format!(
+                        "self.__RSC_SERVER_MANIFEST={}",
+                        StringifyJs(&server_reference_manifest)
+                    ))
+                    .into(),
+                ),
+            ));
+        server_assets.push(Vc::upcast(VirtualOutputAsset::new(
+            node_root.join(format!(
+                "server/app{manifest_path_prefix}/{ty}/server-reference-manifest.json",
+            )),
+            AssetContent::file(File::from(server_reference_manifest).into()),
+        )));
+
         fn create_app_paths_manifest(
             node_root: Vc<FileSystemPath>,
             ty: &'static str,
@@ -666,7 +828,7 @@ impl AppEndpoint {
             )))
         }
 
-        let endpoint_output = match app_entry.config.await?.runtime.unwrap_or_default() {
+        let endpoint_output = match runtime {
             NextRuntime::Edge => {
                 // create edge chunks
                 let chunking_context = this.app_project.project().edge_rsc_chunking_context();
@@ -675,6 +837,7 @@ impl AppEndpoint {
                     .edge_rsc_runtime_entries()
                     .await?
                     .clone_value();
+                evaluatable_assets.extend(actions_loader);
                 let Some(evaluatable) = Vc::try_resolve_sidecast(app_entry.rsc_entry).await? else {
                     bail!("Entry module must be evaluatable");
                 };
@@ -687,21 +850,40 @@ impl AppEndpoint {
                 );
                 server_assets.extend(files.await?.iter().copied());
 
+                // The manifests emitted for this entry that the edge runtime
+                // loads from the global scope before the chunks.
+                let mut manifests = vec![entry_manifest, react_loadable_manifest_js];
+                server_assets.push(react_loadable_manifest_js);
+                if has_actions {
+                    server_assets.push(server_reference_manifest_js);
+                    manifests.push(server_reference_manifest_js);
+                }
+                if let Some(sri_manifest_js) = sri_manifest_js {
+                    server_assets.push(sri_manifest_js);
+                    manifests.push(sri_manifest_js);
+                }
                 let node_root_value = node_root.await?;
-                let files_paths_from_root = files
-                    .await?
+                let mut files_paths_from_root = manifests
                     .iter()
-                    .map(move |&file| {
-                        let node_root_value = node_root_value.clone();
+                    .map(|manifest| {
+                        let node_root_value = &node_root_value;
                         async move {
                             Ok(node_root_value
-                                .get_path_to(&*file.ident().path().await?)
-                                .map(|path| path.to_string()))
+                                .get_path_to(&*manifest.ident().path().await?)
+                                .context("edge manifest path must be inside the node root")?
+                                .to_string())
                         }
                     })
-                    .try_flat_join()
+                    .try_join()
                     .await?;
 
+                let EdgeFunctionPaths {
+                    files: chunks_paths_from_root,
+                    wasm,
+                    assets,
+                } = get_edge_function_paths(files, &node_root_value).await?;
+                files_paths_from_root.extend(chunks_paths_from_root);
+
                 let server_path_value = server_path.await?;
                 let files_paths_from_server = files
                     .await?
@@ -737,7 +919,8 @@ impl AppEndpoint {
                         .clone()
                         .map(Regions::Single),
                     matchers: vec![matchers],
-                    ..Default::default()
+                    wasm,
+                    assets,
                 };
                 let middleware_manifest_v2 = MiddlewaresManifestV2 {
                     sorted_middleware: vec![app_entry.original_name.clone()],
@@ -772,11 +955,15 @@ impl AppEndpoint {
 
                 AppEndpointOutput::Edge {
                     files,
+                    global_var_name: edge_entry_global_var_name(&app_entry.pathname),
                     server_assets: Vc::cell(server_assets),
                     client_assets: Vc::cell(client_assets),
                 }
             }
             NextRuntime::NodeJs => {
+                let mut evaluatable_assets =
+                    this.app_project.rsc_runtime_entries().await?.clone_value();
+                evaluatable_assets.extend(actions_loader);
                 let rsc_chunk = this
                     .app_project
                     .project()
@@ -787,7 +974,7 @@ impl AppEndpoint {
                             original_name = app_entry.original_name
                         )),
                         app_entry.rsc_entry,
-                        this.app_project.rsc_runtime_entries(),
+                        Vc::cell(evaluatable_assets),
                     );
                 server_assets.push(rsc_chunk);
 
@@ -877,7 +1064,11 @@ impl Endpoint for AppEndpoint {
                     .to_string(),
                 server_paths,
             },
-            AppEndpointOutput::Edge { files, .. } => WrittenEndpoint::Edge {
+            AppEndpointOutput::Edge {
+                files,
+                ref global_var_name,
+                ..
+            } => WrittenEndpoint::Edge {
                 files: files
                     .await?
                     .iter()
@@ -889,7 +1080,7 @@ impl Endpoint for AppEndpoint {
                     })
                     .try_join()
                     .await?,
-                global_var_name: "TODO".to_string(),
+                global_var_name: global_var_name.clone(),
                 server_paths,
             },
         };
@@ -916,6 +1107,7 @@ enum AppEndpointOutput {
     },
     Edge {
         files: Vc<OutputAssets>,
+        global_var_name: String,
         server_assets: Vc<OutputAssets>,
         client_assets: Vc<OutputAssets>,
     },
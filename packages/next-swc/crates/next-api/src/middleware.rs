@@ -3,7 +3,10 @@ use next_core::{
     all_server_paths,
     middleware::get_middleware_module,
     mode::NextMode,
-    next_edge::entry::wrap_edge_entry,
+    next_edge::{
+        entry::{edge_entry_global_var_name, wrap_edge_entry},
+        paths::{get_edge_function_paths, EdgeFunctionPaths},
+    },
     next_manifests::{EdgeFunctionDefinition, MiddlewareMatcher, MiddlewaresManifestV2},
     next_server::{get_server_runtime_entries, ServerContextType},
     util::parse_config_from_source,
@@ -30,6 +33,10 @@ use crate::{
     route::{Endpoint, WrittenEndpoint},
 };
 
+/// The name of the middleware edge function. The edge runtime looks up its
+/// entry under the global var name derived from it.
+const MIDDLEWARE_NAME: &str = "middleware";
+
 #[turbo_tasks::value]
 pub struct MiddlewareEndpoint {
     project: Vc<Project>,
@@ -65,7 +72,7 @@ impl MiddlewareEndpoint {
             self.context,
             self.project.project_path(),
             module,
-            "middleware".to_string(),
+            MIDDLEWARE_NAME.to_string(),
         );
 
         let mut evaluatable_assets = get_server_runtime_entries(
@@ -106,29 +113,29 @@ impl MiddlewareEndpoint {
 
         let config = parse_config_from_source(this.userland_module);
 
-        let mut output_assets = self.edge_files().await?.clone_value();
+        let edge_files = self.edge_files();
+        let mut output_assets = edge_files.await?.clone_value();
 
         let node_root = this.project.node_root();
 
-        let files_paths_from_root = {
-            let node_root = &node_root.await?;
-            output_assets
-                .iter()
-                .map(|&file| async move {
-                    Ok(node_root
-                        .get_path_to(&*file.ident().path().await?)
-                        .context("middleware file path must be inside the node root")?
-                        .to_string())
-                })
-                .try_join()
-                .await?
-        };
+        let EdgeFunctionPaths {
+            files: files_paths_from_root,
+            wasm,
+            assets,
+        } = get_edge_function_paths(edge_files, &*node_root.await?).await?;
 
         let matchers = if let Some(matchers) = config.await?.matcher.as_ref() {
+            let base_path = this.project.next_config().base_path().await?;
             matchers
                 .iter()
                 .map(|matcher| MiddlewareMatcher {
-                    original_source: matcher.to_string(),
+                    // Middleware runs before the basePath is stripped, so its matchers need to
+                    // include it.
+                    original_source: if matcher == "/" && !base_path.is_empty() {
+                        base_path.clone_value()
+                    } else {
+                        format!("{}{matcher}", &*base_path)
+                    },
                     ..Default::default()
                 })
                 .collect()
@@ -142,11 +149,12 @@ impl MiddlewareEndpoint {
 
         let edge_function_definition = EdgeFunctionDefinition {
             files: files_paths_from_root,
-            name: "middleware".to_string(),
+            name: MIDDLEWARE_NAME.to_string(),
             page: "/".to_string(),
             regions: None,
             matchers,
-            ..Default::default()
+            wasm,
+            assets,
         };
         let middleware_manifest_v2 = MiddlewaresManifestV2 {
             sorted_middleware: Default::default(),
@@ -202,7 +210,7 @@ impl Endpoint for MiddlewareEndpoint {
 
         Ok(WrittenEndpoint::Edge {
             files,
-            global_var_name: "TODO".to_string(),
+            global_var_name: edge_entry_global_var_name(MIDDLEWARE_NAME),
             server_paths,
         }
         .cell())
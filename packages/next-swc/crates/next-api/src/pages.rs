@@ -9,10 +9,14 @@ use next_core::{
         get_client_runtime_entries, ClientContextType, RuntimeEntries,
     },
     next_dynamic::NextDynamicTransition,
-    next_edge::route_regex::get_named_middleware_regex,
+    next_edge::{
+        entry::edge_entry_global_var_name,
+        paths::{get_edge_function_paths, EdgeFunctionPaths},
+        route_regex::get_named_middleware_regex,
+    },
     next_manifests::{
         BuildManifest, EdgeFunctionDefinition, MiddlewareMatcher, MiddlewaresManifestV2,
-        PagesManifest,
+        PagesManifest, SubresourceIntegrityManifest,
     },
     next_pages::create_page_ssr_entry_module,
     next_server::{
@@ -26,9 +30,7 @@ use next_core::{
     PageLoaderAsset,
 };
 use serde::{Deserialize, Serialize};
-use turbo_tasks::{
-    trace::TraceRawVcs, Completion, TaskInput, TryFlatJoinIterExt, TryJoinIterExt, Value, Vc,
-};
+use turbo_tasks::{trace::TraceRawVcs, Completion, TaskInput, TryJoinIterExt, Value, Vc};
 use turbopack_binding::{
     turbo::tasks_fs::{
         File, FileContent, FileSystem, FileSystemPath, FileSystemPathOption, VirtualFileSystem,
@@ -779,6 +781,42 @@ impl PageEndpoint {
         )))
     }
 
+    /// The integrity manifest of the client chunks, if `experimental.sri` is
+    /// enabled.
+    #[turbo_tasks::function]
+    async fn subresource_integrity_manifest(
+        self: Vc<Self>,
+        client_chunks: Vc<OutputAssets>,
+    ) -> Result<Vc<OutputAssets>> {
+        let this = self.await?;
+        let project = this.pages_project.project();
+        let Some(algorithm) = project
+            .next_config()
+            .await?
+            .experimental
+            .sri
+            .as_ref()
+            .and_then(|sri| sri.algorithm)
+        else {
+            return Ok(OutputAssets::empty());
+        };
+        let mut sri_manifest = SubresourceIntegrityManifest::default();
+        sri_manifest
+            .add_assets(
+                client_chunks.await?.iter().copied(),
+                &*project.client_relative_path().await?,
+                algorithm,
+            )
+            .await?;
+        let manifest_path_prefix = get_asset_prefix_from_pathname(&this.pathname.await?);
+        Ok(Vc::cell(vec![Vc::upcast(VirtualOutputAsset::new(
+            project.node_root().join(format!(
+                "server/pages{manifest_path_prefix}/subresource-integrity-manifest.json",
+            )),
+            AssetContent::file(File::from(serde_json::to_string_pretty(&sri_manifest)?).into()),
+        ))]))
+    }
+
     #[turbo_tasks::function]
     fn output_assets(self: Vc<Self>) -> Vc<OutputAssets> {
         self.output().output_assets()
@@ -797,6 +835,12 @@ impl PageEndpoint {
                 client_assets.extend(client_chunks.await?.iter().copied());
                 let build_manifest = self.build_manifest(client_chunks);
                 server_assets.push(build_manifest);
+                server_assets.extend(
+                    self.subresource_integrity_manifest(client_chunks)
+                        .await?
+                        .iter()
+                        .copied(),
+                );
                 self.ssr_chunk()
             }
             PageEndpointType::Data => self.ssr_data_chunk(),
@@ -825,19 +869,11 @@ impl PageEndpoint {
                 }
                 server_assets.extend(files_value.iter().copied());
 
-                let node_root_value = node_root.await?;
-                let files_paths_from_root: Vec<String> = files_value
-                    .iter()
-                    .map(move |&file| {
-                        let node_root_value = node_root_value.clone();
-                        async move {
-                            Ok(node_root_value
-                                .get_path_to(&*file.ident().path().await?)
-                                .map(|path| path.to_string()))
-                        }
-                    })
-                    .try_flat_join()
-                    .await?;
+                let EdgeFunctionPaths {
+                    files: files_paths_from_root,
+                    wasm,
+                    assets,
+                } = get_edge_function_paths(files, &*node_root.await?).await?;
 
                 let pathname = this.pathname.await?;
                 let named_regex = get_named_middleware_regex(&pathname);
@@ -853,7 +889,8 @@ impl PageEndpoint {
                     page: original_name.to_string(),
                     regions: None,
                     matchers: vec![matchers],
-                    ..Default::default()
+                    wasm,
+                    assets,
                 };
                 let middleware_manifest_v2 = MiddlewaresManifestV2 {
                     sorted_middleware: vec![pathname.to_string()],
@@ -878,6 +915,7 @@ impl PageEndpoint {
 
                 PageEndpointOutput::Edge {
                     files,
+                    global_var_name: edge_entry_global_var_name(&pathname),
                     server_assets: Vc::cell(server_assets),
                     client_assets: Vc::cell(client_assets),
                 }
@@ -923,7 +961,11 @@ impl Endpoint for PageEndpoint {
                     .to_string(),
                 server_paths,
             },
-            PageEndpointOutput::Edge { files, .. } => WrittenEndpoint::Edge {
+            PageEndpointOutput::Edge {
+                files,
+                ref global_var_name,
+                ..
+            } => WrittenEndpoint::Edge {
                 files: files
                     .await?
                     .iter()
@@ -935,7 +977,7 @@ impl Endpoint for PageEndpoint {
                     })
                     .try_join()
                     .await?,
-                global_var_name: "TODO".to_string(),
+                global_var_name: global_var_name.clone(),
                 server_paths,
             },
         };
@@ -963,6 +1005,7 @@ enum PageEndpointOutput {
     },
     Edge {
         files: Vc<OutputAssets>,
+        global_var_name: String,
         server_assets: Vc<OutputAssets>,
         client_assets: Vc<OutputAssets>,
     },
@@ -127,6 +127,7 @@ impl ProjectContainer {
             watch: options.watch,
             server_addr: options.server_addr.parse()?,
             next_config,
+            next_config_json: Vc::cell(options.next_config.clone()),
             js_config,
             env: Vc::upcast(env),
             browserslist_query: "last 1 Chrome versions, last 1 Firefox versions, last 1 Safari \
@@ -170,6 +171,9 @@ pub struct Project {
     /// Next config.
     next_config: Vc<NextConfig>,
 
+    /// The contents of next.config.js, serialized to JSON.
+    next_config_json: Vc<String>,
+
     /// Js/Tsconfig read by load-jsconfig
     js_config: Vc<JsConfig>,
 
@@ -277,6 +281,11 @@ impl Project {
         Ok(self.await?.next_config)
     }
 
+    #[turbo_tasks::function]
+    pub(super) async fn next_config_json(self: Vc<Self>) -> Result<Vc<String>> {
+        Ok(self.await?.next_config_json)
+    }
+
     #[turbo_tasks::function]
     pub(super) async fn js_config(self: Vc<Self>) -> Result<Vc<JsConfig>> {
         Ok(self.await?.js_config)
@@ -306,7 +315,7 @@ impl Project {
 
     #[turbo_tasks::function]
     pub(super) fn client_compile_time_info(&self) -> Vc<CompileTimeInfo> {
-        get_client_compile_time_info(self.mode, self.browserslist_query.clone())
+        get_client_compile_time_info(self.mode, self.browserslist_query.clone(), self.next_config)
     }
 
     #[turbo_tasks::function]
@@ -332,6 +341,8 @@ impl Project {
         Ok(get_client_chunking_context(
             self.project_path(),
             self.client_root(),
+            self.next_config().computed_asset_prefix(),
+            self.next_config().production_browser_source_maps(),
             self.client_compile_time_info().environment(),
             this.mode,
         ))
@@ -417,11 +428,11 @@ impl Project {
             .map(|c| c.styled_components.is_some())
             .unwrap_or_default();
         let react_remove_properties_enabled = compiler_options
-            .and_then(|c| c.react_remove_properties)
-            .unwrap_or_default();
+            .and_then(|c| c.react_remove_properties.as_ref())
+            .is_some_and(|c| c.is_enabled());
         let remove_console_enabled = compiler_options
-            .map(|c| c.remove_console.is_some())
-            .unwrap_or_default();
+            .and_then(|c| c.remove_console.as_ref())
+            .is_some_and(|c| c.is_enabled());
         let emotion_enabled = compiler_options
             .map(|c| c.emotion.is_some())
             .unwrap_or_default();
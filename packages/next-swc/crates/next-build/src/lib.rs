@@ -10,9 +10,14 @@ use turbopack_binding::turbo::{
 pub mod build_options;
 pub(crate) mod next_app;
 pub(crate) mod next_build;
+pub(crate) mod next_export;
 pub(crate) mod next_pages;
+pub(crate) mod next_standalone;
 
-use anyhow::Result;
+use std::env::current_dir;
+
+use anyhow::{Context, Result};
+use dunce::canonicalize;
 use turbo_tasks::{StatsType, TurboTasksBackendApi};
 
 pub use self::build_options::BuildOptions;
@@ -34,13 +39,26 @@ pub async fn build(options: BuildOptions) -> Result<()> {
     };
     tt.set_stats_type(stats_type);
 
-    run_once(tt, async move {
+    let app_dir = options
+        .dir
+        .as_ref()
+        .map(canonicalize)
+        .unwrap_or_else(current_dir)
+        .context("project directory can't be found")?;
+    let workspace_root = match options.root.as_ref() {
+        Some(root) => canonicalize(root).context("root directory can't be found")?,
+        None => app_dir.clone(),
+    };
+
+    run_once(tt.clone(), async move {
         next_build::next_build(TransientInstance::new(options)).await?;
 
         Ok(())
     })
     .await?;
 
+    next_standalone::emit_standalone(&tt, &app_dir, &workspace_root).await?;
+
     Ok(())
 }
 
@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use next_core::{
     app_structure::{find_app_dir_if_enabled, get_entrypoints, Entrypoint},
+    get_edge_resolve_options_context,
     mode::NextMode,
     next_app::{
         get_app_client_shared_chunks, get_app_page_entry, get_app_route_entry,
@@ -15,11 +16,23 @@ use next_core::{
     next_client_reference::{ClientReferenceGraph, NextEcmascriptClientReferenceTransition},
     next_config::NextConfig,
     next_dynamic::NextDynamicTransition,
-    next_manifests::{AppBuildManifest, AppPathsManifest, BuildManifest, ClientReferenceManifest},
+    next_edge::{
+        paths::{get_edge_function_paths, EdgeFunctionPaths},
+        route_regex::get_named_middleware_regex,
+    },
+    next_manifests::{
+        AppBuildManifest, AppPathsManifest, BuildManifest, ClientReferenceManifest,
+        EdgeFunctionDefinition, MiddlewareMatcher, MiddlewaresManifestV2, Regions,
+        ServerReferenceManifest,
+    },
     next_server::{
         get_server_module_options_context, get_server_resolve_options_context,
         get_server_runtime_entries, ServerContextType,
     },
+    next_server_actions::{
+        add_server_actions_to_manifest, create_server_actions_loader, get_server_actions,
+    },
+    util::NextRuntime,
 };
 use turbo_tasks::{TryJoinIterExt, Value, Vc};
 use turbopack_binding::{
@@ -30,7 +43,9 @@ use turbopack_binding::{
     turbopack::{
         build::BuildChunkingContext,
         core::{
-            chunk::EvaluatableAssets, compile_time_info::CompileTimeInfo, file_source::FileSource,
+            chunk::{ChunkableModule, ChunkingContext, EvaluatableAsset, EvaluatableAssets},
+            compile_time_info::CompileTimeInfo,
+            file_source::FileSource,
             output::OutputAsset,
         },
         ecmascript::chunk::EcmascriptChunkingContext,
@@ -39,6 +54,10 @@ use turbopack_binding::{
     },
 };
 
+use crate::next_build::{
+    MIDDLEWARE_BUILD_MANIFEST_JS, SERVER_REFERENCE_MANIFEST_JS, SUBRESOURCE_INTEGRITY_MANIFEST_JS,
+};
+
 #[turbo_tasks::value]
 pub struct AppEntries {
     /// All app entries.
@@ -46,9 +65,17 @@ pub struct AppEntries {
     /// The RSC runtime entries that should be evaluated before any app entry
     /// module when server rendering.
     pub rsc_runtime_entries: Vc<EvaluatableAssets>,
+    /// The RSC runtime entries that should be evaluated before any app entry
+    /// module when server rendering in the edge runtime.
+    pub edge_rsc_runtime_entries: Vc<EvaluatableAssets>,
     /// The client runtime entries that should be evaluated before any app entry
     /// module when client rendering.
     pub client_runtime_entries: Vc<EvaluatableAssets>,
+    /// The context app entries using the Node.js runtime are processed in.
+    /// Server actions of these entries are loaded in the same context.
+    pub rsc_context: Option<Vc<ModuleAssetContext>>,
+    /// The context app entries using the edge runtime are processed in.
+    pub edge_rsc_context: Option<Vc<ModuleAssetContext>>,
 }
 
 /// Computes all app entries found under the given project root.
@@ -59,7 +86,10 @@ pub async fn get_app_entries(
     env: Vc<Box<dyn ProcessEnv>>,
     client_compile_time_info: Vc<CompileTimeInfo>,
     server_compile_time_info: Vc<CompileTimeInfo>,
+    edge_compile_time_info: Vc<CompileTimeInfo>,
     next_config: Vc<NextConfig>,
+    next_config_json: Vc<String>,
+    build_id: Vc<String>,
 ) -> Result<Vc<AppEntries>> {
     let app_dir = find_app_dir_if_enabled(project_root);
 
@@ -67,7 +97,10 @@ pub async fn get_app_entries(
         return Ok(AppEntries::cell(AppEntries {
             entries: vec![],
             rsc_runtime_entries: EvaluatableAssets::empty(),
+            edge_rsc_runtime_entries: EvaluatableAssets::empty(),
             client_runtime_entries: EvaluatableAssets::empty(),
+            rsc_context: None,
+            edge_rsc_context: None,
         }));
     };
 
@@ -177,6 +210,21 @@ pub async fn get_app_entries(
         execution_context,
     );
 
+    let edge_rsc_resolve_options_context = get_edge_resolve_options_context(
+        project_root,
+        rsc_ty,
+        mode,
+        next_config,
+        execution_context,
+    );
+
+    let edge_rsc_context = ModuleAssetContext::new(
+        Vc::cell(transitions.clone()),
+        edge_compile_time_info,
+        rsc_module_options_context,
+        edge_rsc_resolve_options_context,
+    );
+
     let rsc_context = ModuleAssetContext::new(
         Vc::cell(transitions),
         server_compile_time_info,
@@ -191,24 +239,24 @@ pub async fn get_app_entries(
             Ok(match entrypoint {
                 Entrypoint::AppPage { page, loader_tree } => get_app_page_entry(
                     rsc_context,
-                    // TODO add edge support
-                    rsc_context,
+                    edge_rsc_context,
                     *loader_tree,
                     page.clone(),
                     project_root,
+                    build_id,
+                    next_config_json,
+                    mode,
                 ),
                 Entrypoint::AppRoute { page, path } => get_app_route_entry(
                     rsc_context,
-                    // TODO add edge support
-                    rsc_context,
+                    edge_rsc_context,
                     Vc::upcast(FileSource::new(*path)),
                     page.clone(),
                     project_root,
                 ),
                 Entrypoint::AppMetadata { page, metadata } => get_app_metadata_route_entry(
                     rsc_context,
-                    // TODO add edge support
-                    rsc_context,
+                    edge_rsc_context,
                     project_root,
                     page.clone(),
                     mode,
@@ -238,29 +286,39 @@ pub async fn get_app_entries(
     Ok(AppEntries::cell(AppEntries {
         entries,
         rsc_runtime_entries: runtime_entries.resolve_entries(Vc::upcast(rsc_context)),
+        edge_rsc_runtime_entries: runtime_entries.resolve_entries(Vc::upcast(edge_rsc_context)),
         client_runtime_entries: client_runtime_entries.resolve_entries(Vc::upcast(client_context)),
+        rsc_context: Some(rsc_context),
+        edge_rsc_context: Some(edge_rsc_context),
     }))
 }
 
 /// Computes and returns all chunks for app entries. The chunks will be appended
 /// to `all_chunks`, and the chunking information will be added to the provided
-/// manifests.
+/// manifests. Entries using the edge runtime are chunked for the edge and
+/// registered as functions in the middleware manifest.
 pub async fn compute_app_entries_chunks(
     app_entries: &AppEntries,
     app_client_reference_graph: Vc<ClientReferenceGraph>,
     app_client_references_chunks: Vc<ClientReferencesChunks>,
     rsc_chunking_context: Vc<BuildChunkingContext>,
+    edge_rsc_chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
     client_chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
     ssr_chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+    project_root: Vc<FileSystemPath>,
     node_root: Vc<FileSystemPath>,
     client_relative_path: Vc<FileSystemPath>,
     app_paths_manifest_dir_path: &FileSystemPath,
     app_build_manifest: &mut AppBuildManifest,
     build_manifest: &mut BuildManifest,
     app_paths_manifest: &mut AppPathsManifest,
+    middleware_manifest: &mut MiddlewaresManifestV2,
+    server_reference_manifest: &mut ServerReferenceManifest,
     all_chunks: &mut Vec<Vc<Box<dyn OutputAsset>>>,
+    sri_enabled: bool,
 ) -> Result<()> {
     let client_relative_path_ref = client_relative_path.await?;
+    let node_root_ref = node_root.await?;
 
     let app_client_shared_chunks =
         get_app_client_shared_chunks(app_entries.client_runtime_entries, client_chunking_context);
@@ -287,15 +345,145 @@ pub async fn compute_app_entries_chunks(
             .entry(Vc::upcast(app_entry.rsc_entry))
             .await?;
 
-        let rsc_chunk = rsc_chunking_context.entry_chunk(
-            node_root.join(format!(
-                "server/app/{original_name}.js",
-                original_name = app_entry.original_name
-            )),
-            app_entry.rsc_entry,
-            app_entries.rsc_runtime_entries,
+        let entry_manifest = ClientReferenceManifest::build_output(
+            node_root,
+            client_relative_path,
+            app_entry.original_name.clone(),
+            app_client_reference_graph.entry(Vc::upcast(app_entry.rsc_entry)),
+            app_client_references_chunks,
+            client_chunking_context,
+            ssr_chunking_context,
         );
-        all_chunks.push(rsc_chunk);
+
+        all_chunks.push(entry_manifest);
+
+        let config = app_entry.config.await?;
+        let runtime = config.runtime.unwrap_or_default();
+
+        // Server actions are exposed to the action handler through a loader
+        // module that is evaluated along with the RSC entry.
+        let (actions_context, actions_chunking_context) = match runtime {
+            NextRuntime::Edge => (app_entries.edge_rsc_context, edge_rsc_chunking_context),
+            NextRuntime::NodeJs => (app_entries.rsc_context, Vc::upcast(rsc_chunking_context)),
+        };
+        let actions = get_server_actions(Vc::upcast(app_entry.rsc_entry));
+        let actions_loader = match actions_context {
+            Some(actions_context) if !actions.await?.is_empty() => {
+                let loader = create_server_actions_loader(
+                    project_root,
+                    app_entry.original_name.clone(),
+                    actions,
+                    Vc::upcast(actions_context),
+                );
+                add_server_actions_to_manifest(
+                    server_reference_manifest,
+                    &app_entry.original_name,
+                    runtime,
+                    actions,
+                    loader,
+                    actions_chunking_context,
+                )
+                .await?;
+                let Some(loader) =
+                    Vc::try_resolve_sidecast::<Box<dyn EvaluatableAsset>>(loader).await?
+                else {
+                    bail!("Server actions loader must be evaluatable");
+                };
+                Some(loader)
+            }
+            _ => None,
+        };
+
+        match runtime {
+            NextRuntime::Edge => {
+                let mut evaluatable_assets =
+                    app_entries.edge_rsc_runtime_entries.await?.clone_value();
+                evaluatable_assets.extend(actions_loader);
+                let Some(evaluatable) = Vc::try_resolve_sidecast(app_entry.rsc_entry).await? else {
+                    bail!("Entry module must be evaluatable");
+                };
+                evaluatable_assets.push(evaluatable);
+                let edge_chunks = edge_rsc_chunking_context.evaluated_chunk_group(
+                    app_entry
+                        .rsc_entry
+                        .as_root_chunk(Vc::upcast(edge_rsc_chunking_context)),
+                    Vc::cell(evaluatable_assets),
+                );
+                all_chunks.extend(edge_chunks.await?.iter().copied());
+
+                let EdgeFunctionPaths {
+                    files: edge_chunks_paths,
+                    wasm,
+                    assets,
+                } = get_edge_function_paths(edge_chunks, &node_root_ref).await?;
+
+                // The manifests emitted for this entry are loaded by the edge
+                // runtime before the chunks.
+                let mut files = vec![MIDDLEWARE_BUILD_MANIFEST_JS.to_string()];
+                if actions_loader.is_some() {
+                    files.push(SERVER_REFERENCE_MANIFEST_JS.to_string());
+                }
+                if sri_enabled {
+                    files.push(SUBRESOURCE_INTEGRITY_MANIFEST_JS.to_string());
+                }
+                files.push(
+                    node_root_ref
+                        .get_path_to(&*entry_manifest.ident().path().await?)
+                        .expect("client reference manifest path should be within node root")
+                        .to_string(),
+                );
+                files.extend(edge_chunks_paths.iter().cloned());
+
+                let edge_function_definition = EdgeFunctionDefinition {
+                    files,
+                    name: app_entry.pathname.clone(),
+                    page: app_entry.original_name.clone(),
+                    regions: config.preferred_region.clone().map(Regions::Single),
+                    matchers: vec![MiddlewareMatcher {
+                        regexp: Some(get_named_middleware_regex(&app_entry.pathname)),
+                        original_source: app_entry.pathname.clone(),
+                        ..Default::default()
+                    }],
+                    wasm,
+                    assets,
+                };
+                middleware_manifest
+                    .functions
+                    .insert(app_entry.original_name.clone(), edge_function_definition);
+
+                let Some(edge_entry_path) = edge_chunks_paths.first() else {
+                    bail!("expected edge chunks for {}", app_entry.original_name);
+                };
+                app_paths_manifest.edge_server_app_paths.pages.insert(
+                    app_entry.original_name.clone(),
+                    app_paths_manifest_dir_path
+                        .get_path_to(&*node_root.join(edge_entry_path.clone()).await?)
+                        .expect("edge chunk path should be within app paths manifest directory")
+                        .to_string(),
+                );
+            }
+            NextRuntime::NodeJs => {
+                let mut evaluatable_assets = app_entries.rsc_runtime_entries.await?.clone_value();
+                evaluatable_assets.extend(actions_loader);
+                let rsc_chunk = rsc_chunking_context.entry_chunk(
+                    node_root.join(format!(
+                        "server/app/{original_name}.js",
+                        original_name = app_entry.original_name
+                    )),
+                    app_entry.rsc_entry,
+                    Vc::cell(evaluatable_assets),
+                );
+                all_chunks.push(rsc_chunk);
+
+                app_paths_manifest.node_server_app_paths.pages.insert(
+                    app_entry.original_name.clone(),
+                    app_paths_manifest_dir_path
+                        .get_path_to(&*rsc_chunk.ident().path().await?)
+                        .expect("RSC chunk path should be within app paths manifest directory")
+                        .to_string(),
+                );
+            }
+        }
 
         let mut app_entry_client_chunks = vec![];
         // TODO(alexkirsz) In which manifest should this go?
@@ -330,26 +518,6 @@ pub async fn compute_app_entries_chunks(
             app_entry.original_name.clone(),
             app_entry_client_chunks_paths,
         );
-
-        app_paths_manifest.node_server_app_paths.pages.insert(
-            app_entry.original_name.clone(),
-            app_paths_manifest_dir_path
-                .get_path_to(&*rsc_chunk.ident().path().await?)
-                .expect("RSC chunk path should be within app paths manifest directory")
-                .to_string(),
-        );
-
-        let entry_manifest = ClientReferenceManifest::build_output(
-            node_root,
-            client_relative_path,
-            app_entry.original_name.clone(),
-            app_client_reference_graph.entry(Vc::upcast(app_entry.rsc_entry)),
-            app_client_references_chunks,
-            client_chunking_context,
-            ssr_chunking_context,
-        );
-
-        all_chunks.push(entry_manifest);
     }
 
     Ok(())
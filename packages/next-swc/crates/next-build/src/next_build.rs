@@ -1,22 +1,28 @@
 use std::{
     collections::{HashMap, HashSet},
     env::current_dir,
-    path::{PathBuf, MAIN_SEPARATOR},
+    path::{Path, PathBuf, MAIN_SEPARATOR},
 };
 
 use anyhow::{Context, Result};
 use dunce::canonicalize;
 use next_core::{
+    custom_routes::source_to_regex,
+    get_edge_chunking_context, get_edge_compile_time_info,
     mode::NextMode,
     next_app::get_app_client_references_chunks,
     next_client::{get_client_chunking_context, get_client_compile_time_info},
     next_client_reference::{ClientReferenceGraph, ClientReferenceType},
-    next_config::load_next_config,
-    next_dynamic::NextDynamicEntries,
+    next_config::{
+        load_headers, load_next_config, load_next_config_json, load_redirects, OutputType,
+    },
+    next_dynamic::{add_dynamic_entries_to_manifest, NextDynamicEntries},
     next_manifests::{
-        AppBuildManifest, AppPathsManifest, BuildManifest, ClientBuildManifest, FontManifest,
-        MiddlewaresManifest, NextFontManifest, PagesManifest, ReactLoadableManifest,
-        ServerReferenceManifest,
+        AppBuildManifest, AppPathRoutesManifest, AppPathsManifest, BuildManifest,
+        ClientBuildManifest, ExportMarker, FontManifest, MiddlewaresManifest,
+        MiddlewaresManifestV2, NextFontManifest, PagesManifest, ReactLoadableManifest,
+        RequiredServerFiles, RoutesManifest, RoutesManifestHeader, RoutesManifestRedirect,
+        ServerReferenceManifest, SubresourceIntegrityManifest,
     },
     next_server::{get_server_chunking_context, get_server_compile_time_info},
     url_node::get_sorted_routes,
@@ -36,6 +42,7 @@ use turbopack_binding::{
             chunk::ChunkingContext,
             environment::ServerAddr,
             issue::{handle_issues, IssueReporter, IssueSeverity},
+            module::Module,
             output::{OutputAsset, OutputAssets},
             virtual_fs::VirtualFileSystem,
         },
@@ -50,12 +57,24 @@ use turbopack_binding::{
 use crate::{
     build_options::{BuildContext, BuildOptions},
     next_app::app_entries::{compute_app_entries_chunks, get_app_entries},
+    next_export::{emit_static_export, validate_static_export, validate_static_export_images},
     next_pages::page_entries::{compute_page_entries_chunks, get_page_entries},
 };
 
 // TODO this should be Error, but we need to fix the errors happening first
 static MIN_FAILING_SEVERITY: IssueSeverity = IssueSeverity::Fatal;
 
+/// The build manifest edge functions load from the global scope.
+pub(crate) const MIDDLEWARE_BUILD_MANIFEST_JS: &str = "server/middleware-build-manifest.js";
+
+/// The server reference manifest edge functions load from the global scope.
+pub(crate) const SERVER_REFERENCE_MANIFEST_JS: &str = "server/server-reference-manifest.js";
+
+/// The integrity manifest edge functions load from the global scope when
+/// `experimental.sri` is enabled.
+pub(crate) const SUBRESOURCE_INTEGRITY_MANIFEST_JS: &str =
+    "server/subresource-integrity-manifest.js";
+
 #[turbo_tasks::function]
 pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Result<Vc<Completion>> {
     let project_root = options
@@ -96,15 +115,13 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
     let node_root = node_fs.root().join(".next".to_string());
     let client_fs = client_fs(project_root.clone(), issue_reporter);
     let client_root = client_fs.root().join(".next".to_string());
-    // TODO(alexkirsz) This should accept a URL for assetPrefix.
-    // let client_public_fs = VirtualFileSystem::new();
-    // let client_public_root = client_public_fs.root();
     let workspace_fs = workspace_fs(workspace_root.clone(), issue_reporter);
     let project_relative = project_root.strip_prefix(&workspace_root).unwrap();
     let project_relative = project_relative
         .strip_prefix(MAIN_SEPARATOR)
         .unwrap_or(project_relative)
         .replace(MAIN_SEPARATOR, "/");
+    let app_dir = project_root.clone();
     let project_root = workspace_fs.root().join(project_relative);
 
     let node_root_ref = node_root.await?;
@@ -124,11 +141,14 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
 
     let execution_context =
         ExecutionContext::new(project_root, node_execution_chunking_context, env);
-    let next_config = load_next_config(execution_context.with_layer("next_config".to_string()));
+    let next_config_execution_context = execution_context.with_layer("next_config".to_string());
+    let next_config = load_next_config(next_config_execution_context);
 
     let mode = NextMode::Build;
-    let client_compile_time_info = get_client_compile_time_info(mode, browserslist_query);
+    let client_compile_time_info =
+        get_client_compile_time_info(mode, browserslist_query, next_config);
     let server_compile_time_info = get_server_compile_time_info(mode, env, ServerAddr::empty());
+    let edge_compile_time_info = get_edge_compile_time_info(project_root, ServerAddr::empty());
 
     // TODO(alexkirsz) Pages should build their own routes, outside of a FS.
     let next_router_fs = Vc::upcast::<Box<dyn FileSystem>>(VirtualFileSystem::new());
@@ -143,13 +163,22 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         next_config,
     );
 
+    // `next build` always passes a build id, standalone builds use the one of
+    // the dev server.
+    let build_id = options.build_context.as_ref().map_or_else(
+        || "development".to_string(),
+        |context| context.build_id.clone(),
+    );
     let app_entries = get_app_entries(
         project_root,
         execution_context,
         env,
         client_compile_time_info,
         server_compile_time_info,
+        edge_compile_time_info,
         next_config,
+        load_next_config_json(next_config_execution_context),
+        Vc::cell(build_id),
     );
 
     handle_issues(
@@ -169,6 +198,18 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
     )
     .await?;
 
+    let is_static_export = next_config.await?.output == Some(OutputType::Export);
+    if is_static_export {
+        handle_issues(
+            validate_static_export(project_root, next_config, page_entries, app_entries),
+            issue_reporter,
+            IssueSeverity::Error.cell(),
+            None,
+            None,
+        )
+        .await?;
+    }
+
     let page_entries = page_entries.await?;
     let app_entries = app_entries.await?;
 
@@ -204,6 +245,48 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         .flatten()
         .collect();
 
+    // `next/image` is only known to be used when it's part of a client graph.
+    let is_next_image_imported = if is_static_export {
+        let client_modules: Vec<Vc<Box<dyn Module>>> = page_entries
+            .entries
+            .iter()
+            .map(|entry| async move { Ok(Vc::upcast(entry.await?.client_module)) })
+            .try_join()
+            .await?
+            .into_iter()
+            .chain(
+                app_client_reference_tys
+                    .await?
+                    .iter()
+                    .map(|client_reference_ty| async move {
+                        Ok(match client_reference_ty {
+                            ClientReferenceType::EcmascriptClientReference(entry) => {
+                                Some(Vc::upcast(entry.await?.client_module))
+                            }
+                            ClientReferenceType::CssClientReference(_) => None,
+                        })
+                    })
+                    .try_join()
+                    .await?
+                    .into_iter()
+                    .flatten(),
+            )
+            .collect();
+        let is_next_image_imported =
+            validate_static_export_images(project_root, next_config, Vc::cell(client_modules));
+        handle_issues(
+            is_next_image_imported,
+            issue_reporter,
+            IssueSeverity::Error.cell(),
+            None,
+            None,
+        )
+        .await?;
+        *is_next_image_imported.await?
+    } else {
+        false
+    };
+
     let page_ssr_entries = page_entries
         .entries
         .iter()
@@ -212,23 +295,19 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         .try_join()
         .await?;
 
-    let app_node_entries: Vec<_> = app_ssr_entries
-        .iter()
-        .copied()
-        .chain(app_rsc_entries.iter().copied())
-        .collect();
-
-    let all_node_entries: Vec<_> = page_ssr_entries
-        .iter()
-        .copied()
-        .chain(app_node_entries.iter().copied())
-        .collect();
-
-    // TODO(alexkirsz) Handle dynamic entries and dynamic chunks.
-    let _dynamic_entries = NextDynamicEntries::from_entries(Vc::cell(
-        all_node_entries.iter().copied().map(Vc::upcast).collect(),
-    ))
-    .await?;
+    // Server components and SSR modules are chunked in different layers, so their
+    // dynamic entries are collected separately.
+    let ssr_dynamic_entries = NextDynamicEntries::from_entries(Vc::cell(
+        page_ssr_entries
+            .iter()
+            .copied()
+            .chain(app_ssr_entries.iter().copied())
+            .map(Vc::upcast)
+            .collect(),
+    ));
+    let rsc_dynamic_entries = NextDynamicEntries::from_entries(Vc::cell(
+        app_rsc_entries.iter().copied().map(Vc::upcast).collect(),
+    ));
 
     // TODO(alexkirsz) At this point, we have access to the whole module graph via
     // the entries. This is where we should compute unique module ids and optimized
@@ -239,6 +318,8 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
     let client_chunking_context = get_client_chunking_context(
         project_root,
         client_root,
+        next_config.computed_asset_prefix(),
+        next_config.production_browser_source_maps(),
         client_compile_time_info.environment(),
         mode,
     );
@@ -254,13 +335,22 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
     let rsc_chunking_context = server_chunking_context.with_layer("rsc".to_string());
     let ssr_chunking_context = server_chunking_context.with_layer("ssr".to_string());
 
+    let edge_chunking_context = get_edge_chunking_context(
+        project_root,
+        node_root,
+        client_root,
+        edge_compile_time_info.environment(),
+    );
+    let edge_rsc_chunking_context = edge_chunking_context.with_layer("edge rsc".to_string());
+
     let mut all_chunks = vec![];
 
     let mut build_manifest: BuildManifest = Default::default();
     let build_manifest_path = client_root.join("build-manifest.json".to_string());
 
     // This ensures that the _next prefix is properly stripped from all client paths
-    // in manifests. It will be added back on the client through the chunk_base_path
+    // in manifests. It will be added back, together with the assetPrefix, on the
+    // server when rendering and on the client through the chunk_base_path
     // mechanism.
     let client_relative_path = client_root.join("_next".to_string());
     let client_relative_path_ref = client_relative_path.await?;
@@ -284,6 +374,31 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
     )
     .await?;
 
+    // DYNAMIC CHUNKING
+
+    let mut react_loadable_manifest = ReactLoadableManifest::default();
+
+    all_chunks.extend(
+        add_dynamic_entries_to_manifest(
+            &mut react_loadable_manifest,
+            ssr_dynamic_entries,
+            Vc::upcast(ssr_chunking_context),
+            client_chunking_context,
+            &client_relative_path_ref,
+        )
+        .await?,
+    );
+    all_chunks.extend(
+        add_dynamic_entries_to_manifest(
+            &mut react_loadable_manifest,
+            rsc_dynamic_entries,
+            Vc::upcast(rsc_chunking_context),
+            client_chunking_context,
+            &client_relative_path_ref,
+        )
+        .await?,
+    );
+
     // APP CHUNKING
 
     let mut app_build_manifest = AppBuildManifest::default();
@@ -293,6 +408,11 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
     let app_paths_manifest_path = node_root.join("server/app-paths-manifest.json".to_string());
     let app_paths_manifest_dir_path = app_paths_manifest_path.parent().await?;
 
+    let mut middleware_manifest = MiddlewaresManifestV2::default();
+    let middleware_manifest_path = node_root.join("server/middleware-manifest.json".to_string());
+
+    let mut server_reference_manifest = ServerReferenceManifest::default();
+
     // APP CLIENT REFERENCES CHUNKING
 
     let app_client_references_chunks = get_app_client_references_chunks(
@@ -312,20 +432,31 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
     // APP RSC CHUNKING
     // TODO(alexkirsz) Do some of that in parallel with the above.
 
+    let sri_enabled = next_config
+        .await?
+        .experimental
+        .sri
+        .as_ref()
+        .is_some_and(|sri| sri.algorithm.is_some());
     compute_app_entries_chunks(
         &app_entries,
         app_client_references,
         app_client_references_chunks,
         rsc_chunking_context,
+        edge_rsc_chunking_context,
         client_chunking_context,
         Vc::upcast(ssr_chunking_context),
+        project_root,
         node_root,
         client_relative_path,
         &app_paths_manifest_dir_path,
         &mut app_build_manifest,
         &mut build_manifest,
         &mut app_paths_manifest,
+        &mut middleware_manifest,
+        &mut server_reference_manifest,
         &mut all_chunks,
+        sri_enabled,
     )
     .await?;
 
@@ -334,6 +465,12 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
     if let Some(build_context) = &options.build_context {
         let BuildContext { build_id, rewrites } = build_context;
 
+        completions.push(
+            node_root
+                .join("BUILD_ID".to_string())
+                .write(FileContent::Content(build_id.clone().into()).cell()),
+        );
+
         let ssg_manifest_path = format!("static/{build_id}/_ssgManifest.js");
 
         let ssg_manifest_fs_path = node_root.join(ssg_manifest_path.clone());
@@ -407,16 +544,208 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
     completions.push(write_manifest(pages_manifest, pages_manifest_path)?);
     completions.push(write_manifest(app_build_manifest, app_build_manifest_path)?);
     completions.push(write_manifest(app_paths_manifest, app_paths_manifest_path)?);
+    completions.push(write_manifest(
+        react_loadable_manifest,
+        node_root.join("react-loadable-manifest.json".to_string()),
+    )?);
+    let app_path_routes_manifest = AppPathRoutesManifest {
+        routes: app_entries
+            .entries
+            .iter()
+            .map(|entry| async move {
+                let entry = entry.await?;
+                Ok((entry.original_name.clone(), entry.pathname.clone()))
+            })
+            .try_join()
+            .await?
+            .into_iter()
+            .collect(),
+    };
+    completions.push(write_manifest(
+        app_path_routes_manifest,
+        node_root.join("app-path-routes-manifest.json".to_string()),
+    )?);
+    completions.push(write_manifest(
+        MiddlewaresManifest::MiddlewaresManifestV2(middleware_manifest),
+        middleware_manifest_path,
+    )?);
+    // The build manifest is loaded by edge functions from the global scope.
+    completions.push(
+        node_root
+            .join(MIDDLEWARE_BUILD_MANIFEST_JS.to_string())
+            .write(
+                FileContent::Content(
+                    format!("self.__BUILD_MANIFEST={};", StringifyJs(&build_manifest)).into(),
+                )
+                .cell(),
+            ),
+    );
     completions.push(write_manifest(build_manifest, build_manifest_path)?);
+    // The server reference manifest is loaded by edge functions from the
+    // global scope as well.
+    completions.push(
+        node_root
+            .join(SERVER_REFERENCE_MANIFEST_JS.to_string())
+            .write(
+                FileContent::Content(
+                    format!(
+                        "self.__RSC_SERVER_MANIFEST={};",
+                        StringifyJs(&serde_json::to_string(&server_reference_manifest)?)
+                    )
+                    .into(),
+                )
+                .cell(),
+            ),
+    );
+    completions.push(write_manifest(
+        server_reference_manifest,
+        node_root.join("server/server-reference-manifest.json".to_string()),
+    )?);
 
-    // Placeholder manifests.
+    let headers = load_headers(next_config_execution_context).await?;
+    let redirects = load_redirects(next_config_execution_context).await?;
+    let next_config_ref = next_config.await?;
+    let routes_manifest = RoutesManifest {
+        base_path: &next_config_ref.base_path,
+        i18n: next_config_ref.i18n.as_ref(),
+        headers: headers
+            .iter()
+            .map(|header| {
+                Ok(RoutesManifestHeader {
+                    header,
+                    regex: source_to_regex(&header.source)
+                        .with_context(|| format!("invalid header source `{}`", header.source))?
+                        .0,
+                })
+            })
+            .collect::<Result<_>>()?,
+        redirects: redirects
+            .iter()
+            .map(|redirect| {
+                Ok(RoutesManifestRedirect::new(
+                    redirect,
+                    source_to_regex(&redirect.source)
+                        .with_context(|| format!("invalid redirect source `{}`", redirect.source))?
+                        .0,
+                ))
+            })
+            .collect::<Result<_>>()?,
+        ..Default::default()
+    };
+    completions.push(write_manifest(
+        routes_manifest,
+        node_root.join("routes-manifest.json".to_string()),
+    )?);
 
-    // TODO(alexkirsz) Proper middleware manifest with all (edge?) routes in it,
-    // experimental-edge pages?
+    let all_assets = all_assets_from_entries(Vc::cell(all_chunks)).await?;
+
+    let mut required_server_files: Vec<_> = [
+        "routes-manifest.json",
+        "server/pages-manifest.json",
+        "build-manifest.json",
+        "server/middleware-manifest.json",
+        "server/middleware-build-manifest.js",
+        "server/app-paths-manifest.json",
+        "app-path-routes-manifest.json",
+        "app-build-manifest.json",
+        "server/server-reference-manifest.js",
+        "server/server-reference-manifest.json",
+        "react-loadable-manifest.json",
+        "server/font-manifest.json",
+        "server/next-font-manifest.json",
+    ]
+    .into_iter()
+    .map(|file| format!(".next/{file}"))
+    .collect();
+
+    if let Some(algorithm) = next_config_ref
+        .experimental
+        .sri
+        .as_ref()
+        .and_then(|sri| sri.algorithm)
+    {
+        let mut sri_manifest = SubresourceIntegrityManifest::default();
+        sri_manifest
+            .add_assets(
+                all_assets.iter().copied(),
+                &*client_relative_path.await?,
+                algorithm,
+            )
+            .await?;
+        // Like the server reference manifest, this is loaded by edge functions
+        // from the global scope.
+        completions.push(
+            node_root
+                .join(SUBRESOURCE_INTEGRITY_MANIFEST_JS.to_string())
+                .write(
+                    FileContent::Content(
+                        format!(
+                            "self.__SUBRESOURCE_INTEGRITY_MANIFEST={};",
+                            StringifyJs(&serde_json::to_string(&sri_manifest)?)
+                        )
+                        .into(),
+                    )
+                    .cell(),
+                ),
+        );
+        completions.push(write_manifest(
+            sri_manifest,
+            node_root.join("server/subresource-integrity-manifest.json".to_string()),
+        )?);
+        required_server_files.extend([
+            format!(".next/{SUBRESOURCE_INTEGRITY_MANIFEST_JS}"),
+            ".next/server/subresource-integrity-manifest.json".to_string(),
+        ]);
+    }
+
+    // The standalone output mirrors the directory layout below the tracing
+    // root.
+    let relative_app_dir = if next_config_ref.output == Some(OutputType::Standalone) {
+        let tracing_root = next_config_ref
+            .experimental
+            .output_file_tracing_root
+            .as_deref()
+            .unwrap_or(&workspace_root);
+        let tracing_root =
+            canonicalize(tracing_root).context("outputFileTracingRoot can't be found")?;
+        canonicalize(&app_dir)
+            .context("project directory can't be found")?
+            .strip_prefix(&tracing_root)
+            .context("the project directory must be inside outputFileTracingRoot")?
+            .to_str()
+            .context("project directory contains invalid characters")?
+            .replace(MAIN_SEPARATOR, "/")
+    } else {
+        String::new()
+    };
+    let required_server_files = RequiredServerFiles {
+        version: 1,
+        config: &next_config_ref,
+        app_dir: &app_dir,
+        relative_app_dir: &relative_app_dir,
+        files: required_server_files,
+        ignore: vec![],
+    };
     completions.push(write_manifest(
-        MiddlewaresManifest::default(),
-        node_root.join("server/middleware-manifest.json".to_string()),
+        required_server_files,
+        node_root.join("required-server-files.json".to_string()),
     )?);
+
+    // The static export itself is rendered from the emitted build.
+    if is_static_export {
+        completions.push(write_manifest(
+            ExportMarker {
+                version: 1,
+                has_export_path_map: next_config_ref.export_path_map.is_some(),
+                export_trailing_slash: next_config_ref.trailing_slash,
+                is_next_image_imported,
+            },
+            node_root.join("export-marker.json".to_string()),
+        )?);
+    }
+
+    // Placeholder manifests.
+
     completions.push(write_manifest(
         NextFontManifest::default(),
         node_root.join("server/next-font-manifest.json".to_string()),
@@ -425,18 +754,10 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         FontManifest::default(),
         node_root.join("server/font-manifest.json".to_string()),
     )?);
-    completions.push(write_manifest(
-        ServerReferenceManifest::default(),
-        node_root.join("server/server-reference-manifest.json".to_string()),
-    )?);
-    completions.push(write_manifest(
-        ReactLoadableManifest::default(),
-        node_root.join("react-loadable-manifest.json".to_string()),
-    )?);
 
     completions.push(
         emit_all_assets(
-            all_chunks,
+            &all_assets,
             &node_root_ref,
             client_relative_path,
             client_root,
@@ -444,7 +765,15 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         .await?,
     );
 
-    Ok(Completions::all(completions))
+    let completion = Completions::all(completions);
+
+    // The static export is rendered from the build written to disk.
+    if is_static_export {
+        completion.await?;
+        emit_static_export(Path::new(&app_dir), execution_context).await?;
+    }
+
+    Ok(completion)
 }
 
 #[turbo_tasks::function]
@@ -498,15 +827,13 @@ async fn client_fs(
     Ok(Vc::upcast(disk_fs))
 }
 
-/// Emits all assets transitively reachable from the given chunks, that are
-/// inside the node root or the client root.
+/// Emits all given assets that are inside the node root or the client root.
 async fn emit_all_assets(
-    chunks: Vec<Vc<Box<dyn OutputAsset>>>,
+    all_assets: &[Vc<Box<dyn OutputAsset>>],
     node_root: &FileSystemPath,
     client_relative_path: Vc<FileSystemPath>,
     client_output_path: Vc<FileSystemPath>,
 ) -> Result<Vc<Completion>> {
-    let all_assets = all_assets_from_entries(Vc::cell(chunks)).await?;
     Ok(Completions::all(
         all_assets
             .iter()
@@ -0,0 +1,636 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::Path,
+};
+
+use anyhow::{bail, Context, Result};
+use base64::Engine;
+use indexmap::indexmap;
+use next_core::{
+    middleware::middleware_files,
+    next_config::{ImageLoader, NextConfig},
+    util::{get_asset_path_from_pathname, get_html_path_from_pathname},
+    NextRevalidate, NextSegmentDynamic,
+};
+use serde::Deserialize;
+use serde_json::json;
+use turbo_tasks::{
+    graph::{AdjacencyMap, GraphTraversal},
+    Completion, TryJoinIterExt, Value, Vc,
+};
+use turbopack_binding::{
+    turbo::{
+        tasks_bytes::stream::SingleValue,
+        tasks_env::CustomProcessEnv,
+        tasks_fs::{json::parse_json_with_source_context, File, FileSystemPath},
+    },
+    turbopack::{
+        core::{
+            asset::AssetContent,
+            context::AssetContext,
+            ident::AssetIdent,
+            issue::{Issue, IssueExt, IssueSeverity},
+            module::{Module, Modules},
+            reference_type::{InnerAssets, ReferenceType},
+            resolve::{
+                find_context_file,
+                options::{ImportMap, ImportMapping},
+                FindContextFileResult,
+            },
+            virtual_source::VirtualSource,
+        },
+        ecmascript::{chunk::EcmascriptExports, EcmascriptModuleAsset},
+        node::{debug::should_debug, evaluate::evaluate, execution_context::ExecutionContext},
+        turbopack::evaluate_context::node_evaluate_asset_context,
+    },
+};
+
+use crate::{
+    next_app::app_entries::AppEntries, next_pages::page_entries::PageEntries,
+    next_standalone::collect_files,
+};
+
+/// Modules which are only part of the client graph when `next/image` or
+/// `next/legacy/image` is used.
+const NEXT_IMAGE_MODULES: &[&str] = &[
+    "next/dist/shared/lib/image-external.js",
+    "next/dist/client/image-component.js",
+    "next/dist/client/legacy/image.js",
+];
+
+const STATIC_EXPORT_DOCS: &str =
+    "https://nextjs.org/docs/app/building-your-application/deploying/static-exports#unsupported-features";
+
+/// An issue with a route or a feature that can't be statically exported.
+#[turbo_tasks::value(shared)]
+struct StaticExportIssue {
+    path: Vc<FileSystemPath>,
+    title: Vc<String>,
+    description: Vc<String>,
+    severity: Vc<IssueSeverity>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for StaticExportIssue {
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("export".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        self.severity
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        self.title
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        self.description
+    }
+
+    #[turbo_tasks::function]
+    fn documentation_link(&self) -> Vc<String> {
+        Vc::cell(STATIC_EXPORT_DOCS.to_string())
+    }
+}
+
+fn emit_issue(path: Vc<FileSystemPath>, title: String, description: &str, severity: IssueSeverity) {
+    StaticExportIssue {
+        path,
+        title: Vc::cell(title),
+        description: Vc::cell(description.to_string()),
+        severity: severity.cell(),
+    }
+    .cell()
+    .emit();
+}
+
+/// Reports the routes and features of a project that can't be statically
+/// exported with `output: "export"`.
+#[turbo_tasks::function]
+pub(crate) async fn validate_static_export(
+    project_root: Vc<FileSystemPath>,
+    next_config: Vc<NextConfig>,
+    page_entries: Vc<PageEntries>,
+    app_entries: Vc<AppEntries>,
+) -> Result<Vc<Completion>> {
+    let has_export_path_map = next_config.await?.export_path_map.is_some();
+
+    let middleware = find_context_file(
+        project_root,
+        middleware_files(next_config.page_extensions()),
+    );
+    if let FindContextFileResult::Found(middleware_path, _) = *middleware.await? {
+        emit_issue(
+            middleware_path,
+            "Middleware cannot be used with \"output: export\"".to_string(),
+            "Middleware runs on every request, which requires a server.",
+            IssueSeverity::Error,
+        );
+    }
+
+    for page_entry in page_entries.await?.entries.iter() {
+        let page_entry = page_entry.await?;
+        let pathname = page_entry.pathname.await?;
+        if *pathname == "/api" || pathname.starts_with("/api/") {
+            continue;
+        }
+
+        let page_path = page_entry.page_module.ident().path();
+        if *has_export(page_entry.page_module, "getServerSideProps".to_string()).await? {
+            emit_issue(
+                page_path,
+                format!("Page {pathname} uses getServerSideProps"),
+                "Pages using getServerSideProps are rendered on every request, which is not \
+                 supported with \"output: export\".",
+                IssueSeverity::Error,
+            );
+        } else if is_dynamic_route(&pathname)
+            && !has_export_path_map
+            && !*has_export(page_entry.page_module, "getStaticPaths".to_string()).await?
+        {
+            emit_issue(
+                page_path,
+                format!("Dynamic page {pathname} is not exported"),
+                "Export getStaticPaths from it or add its paths to exportPathMap in \
+                 next.config.js to export them.",
+                IssueSeverity::Warning,
+            );
+        }
+    }
+
+    // Dynamic app routes without `generateStaticParams` are reported when
+    // their paths are collected for the export, as the params can be
+    // generated by any of their layouts.
+    for app_entry in app_entries.await?.entries.iter() {
+        let app_entry = app_entry.await?;
+        let config = app_entry.config.await?;
+        if matches!(config.dynamic, Some(NextSegmentDynamic::ForceDynamic))
+            || matches!(
+                config.revalidate,
+                Some(NextRevalidate::Frequency { seconds: 0 })
+            )
+        {
+            emit_issue(
+                app_entry.source_path,
+                format!("Route {} uses dynamic rendering", app_entry.original_name),
+                "Routes with `dynamic = \"force-dynamic\"` or `revalidate = 0` are rendered on \
+                 every request, which is not supported with \"output: export\".",
+                IssueSeverity::Error,
+            );
+        }
+    }
+
+    Ok(Completion::new())
+}
+
+/// Checks whether `next/image` is used by any of the client `modules`. Reports
+/// an issue if it is used with the default loader, as the Image Optimization
+/// API requires a server.
+#[turbo_tasks::function]
+pub(crate) async fn validate_static_export_images(
+    project_root: Vc<FileSystemPath>,
+    next_config: Vc<NextConfig>,
+    modules: Vc<Modules>,
+) -> Result<Vc<bool>> {
+    let modules = AdjacencyMap::new()
+        .skip_duplicates()
+        .visit(modules.await?.iter().copied(), get_referenced_modules)
+        .await
+        .completed()?
+        .into_inner()
+        .into_reverse_topological()
+        .map(|module| async move { Ok(module.ident().path().await?.path.clone()) })
+        .try_join()
+        .await?;
+    let is_next_image_imported = modules.iter().any(|path| {
+        NEXT_IMAGE_MODULES
+            .iter()
+            .any(|image_module| path.ends_with(image_module))
+    });
+
+    let next_config = next_config.await?;
+    if is_next_image_imported
+        && next_config.images.loader == ImageLoader::Default
+        && !next_config.images.unoptimized
+    {
+        emit_issue(
+            project_root.join(next_config.config_file_name.clone()),
+            "Image Optimization using the default loader is not compatible with \"output: export\""
+                .to_string(),
+            "Configure `images.unoptimized = true` or a custom `images.loader` in next.config.js.",
+            IssueSeverity::Error,
+        );
+    }
+
+    Ok(Vc::cell(is_next_image_imported))
+}
+
+async fn get_referenced_modules(
+    module: Vc<Box<dyn Module>>,
+) -> Result<impl Iterator<Item = Vc<Box<dyn Module>>> + Send> {
+    let modules = module
+        .references()
+        .await?
+        .iter()
+        .map(|reference| async move {
+            Ok(reference
+                .resolve_reference()
+                .primary_modules()
+                .await?
+                .clone_value())
+        })
+        .try_join()
+        .await?;
+    Ok(modules.into_iter().flatten())
+}
+
+#[turbo_tasks::function]
+async fn has_export(module: Vc<Box<dyn Module>>, name: String) -> Result<Vc<bool>> {
+    let Some(ecmascript_asset) =
+        Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(module).await?
+    else {
+        return Ok(Vc::cell(false));
+    };
+
+    if let EcmascriptExports::EsmExports(exports) = &*ecmascript_asset.get_exports().await? {
+        return Ok(Vc::cell(exports.await?.exports.contains_key(&name)));
+    }
+
+    Ok(Vc::cell(false))
+}
+
+fn is_dynamic_route(pathname: &str) -> bool {
+    pathname
+        .split('/')
+        .any(|segment| segment.starts_with('[') && segment.ends_with(']'))
+}
+
+/// The subset of `.next/export-marker.json` needed to export the build.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportMarker {
+    has_export_path_map: bool,
+    export_trailing_slash: bool,
+}
+
+/// The result of rendering a path, as returned by `render.js`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RenderedPath {
+    path: String,
+    html: Option<String>,
+    rsc: Option<String>,
+    /// The props returned by `getStaticProps`.
+    page_data: Option<serde_json::Value>,
+    /// The base64 encoded response body of a route handler.
+    body: Option<String>,
+    error: Option<String>,
+}
+
+/// The paths to export by default and the dynamic routes whose paths are
+/// collected from `getStaticPaths` or `generateStaticParams` by `render.js`.
+fn get_default_path_map(
+    pages: &HashMap<String, String>,
+    app_paths: &HashMap<String, String>,
+) -> (BTreeMap<String, serde_json::Value>, Vec<serde_json::Value>) {
+    let mut path_map = BTreeMap::new();
+    let mut dynamic_routes = vec![];
+    for page in pages.keys() {
+        if matches!(page.as_str(), "/_app" | "/_document" | "/_error" | "/api")
+            || page.starts_with("/api/")
+        {
+            continue;
+        }
+        if is_dynamic_route(page) {
+            dynamic_routes.push(json!({ "page": page, "pathname": page }));
+        } else {
+            path_map.insert(page.clone(), json!({ "page": page }));
+        }
+    }
+    for (page, pathname) in app_paths {
+        if page.starts_with("/_") {
+            continue;
+        }
+        if is_dynamic_route(pathname) {
+            dynamic_routes.push(json!({ "page": page, "pathname": pathname, "_isAppDir": true }));
+        } else {
+            path_map.insert(pathname.clone(), json!({ "page": page, "_isAppDir": true }));
+        }
+    }
+    if !path_map.contains_key("/404") {
+        if app_paths.contains_key("/_not-found") {
+            path_map.insert(
+                "/404".to_string(),
+                json!({ "page": "/_not-found", "_isAppDir": true }),
+            );
+        } else if pages.contains_key("/_error") {
+            path_map.insert("/404".to_string(), json!({ "page": "/_error" }));
+        }
+    }
+    // The manifests are hash maps, so the order of the dynamic routes is
+    // fixed to keep the output stable.
+    dynamic_routes.sort_by(|a, b| a["pathname"].as_str().cmp(&b["pathname"].as_str()));
+    (path_map, dynamic_routes)
+}
+
+/// Statically exports the build into `out/` for `output: "export"`, like
+/// `exportApp` in next.js/packages/next/src/export/index.ts.
+///
+/// This needs to run after the build has been written to disk, as the pages
+/// are rendered from the emitted server files.
+pub(crate) async fn emit_static_export(
+    app_dir: &Path,
+    execution_context: Vc<ExecutionContext>,
+) -> Result<()> {
+    let dist_dir = app_dir.join(".next");
+    let required_server_files: serde_json::Value =
+        read_json(&dist_dir.join("required-server-files.json"))?;
+    if required_server_files["config"]["output"] != "export" {
+        return Ok(());
+    }
+
+    let marker: ExportMarker = read_json(&dist_dir.join("export-marker.json"))?;
+    let build_id = fs::read_to_string(dist_dir.join("BUILD_ID"))
+        .context("static exports require a build id")?;
+    let build_id = build_id.trim();
+
+    let pages: HashMap<String, String> = read_json(&dist_dir.join("server/pages-manifest.json"))?;
+    let app_paths: HashMap<String, String> =
+        read_json(&dist_dir.join("app-path-routes-manifest.json"))?;
+    let (path_map, dynamic_routes) = get_default_path_map(&pages, &app_paths);
+
+    let out_dir = app_dir.join("out");
+    if out_dir.exists() {
+        fs::remove_dir_all(&out_dir)?;
+    }
+    copy_dir(&app_dir.join("public"), &out_dir)?;
+    copy_dir(&dist_dir.join("static"), &out_dir.join("_next/static"))?;
+
+    let input = json!({
+        "dir": app_dir,
+        "distDir": dist_dir,
+        "buildId": build_id,
+        "hasExportPathMap": marker.has_export_path_map,
+        "pathMap": path_map,
+        "dynamicRoutes": dynamic_routes,
+    });
+    let mut errors = vec![];
+    for app in [false, true] {
+        if (app && app_paths.is_empty()) || (!app && pages.is_empty()) {
+            continue;
+        }
+        for rendered in render(execution_context, &required_server_files, &input, app).await? {
+            if let Some(error) = rendered.error {
+                errors.push(format!("{}: {error}", rendered.path));
+                continue;
+            }
+            write_rendered_path(&out_dir, build_id, rendered, marker.export_trailing_slash)?;
+        }
+    }
+
+    if !errors.is_empty() {
+        errors.sort();
+        bail!(
+            "Export encountered errors on following paths:\n\t{}",
+            errors.join("\n\t")
+        );
+    }
+
+    Ok(())
+}
+
+/// Renders the pages or the app paths of `input` with next.js, in a Node.js
+/// process of the evaluate pool.
+async fn render(
+    execution_context: Vc<ExecutionContext>,
+    required_server_files: &serde_json::Value,
+    input: &serde_json::Value,
+    app: bool,
+) -> Result<Vec<RenderedPath>> {
+    let mut input = input.clone();
+    input["app"] = app.into();
+
+    let ExecutionContext {
+        project_path,
+        chunking_context,
+        env,
+    } = *execution_context.await?;
+
+    // Pages and app paths need different React builds, so they are rendered
+    // with different environments, and thereby by different pools.
+    let mut render_env = indexmap! {
+        "NODE_ENV".to_string() => "production".to_string(),
+    };
+    if app {
+        // Like the app export workers of next.js.
+        let server_actions = required_server_files["config"]["experimental"]["serverActions"]
+            .as_bool()
+            .unwrap_or_default();
+        render_env.insert(
+            "__NEXT_PRIVATE_PREBUNDLED_REACT".to_string(),
+            if server_actions {
+                "experimental"
+            } else {
+                "next"
+            }
+            .to_string(),
+        );
+    }
+    let env = Vc::upcast(CustomProcessEnv::new(env, Vc::cell(render_env)));
+
+    let mut import_map = ImportMap::default();
+    import_map.insert_exact_alias("next", ImportMapping::External(None).into());
+    import_map.insert_wildcard_alias("next/", ImportMapping::External(None).into());
+    let context = node_evaluate_asset_context(execution_context, Some(import_map.cell()), None);
+
+    let render_path = project_path.join("next-export-render.js".to_string());
+    let render_asset = context.process(
+        Vc::upcast(VirtualSource::new(
+            render_path,
+            AssetContent::file(File::from(include_str!("render.js")).into()),
+        )),
+        Value::new(ReferenceType::Internal(InnerAssets::empty())),
+    );
+
+    let rendered = evaluate(
+        render_asset,
+        project_path,
+        env,
+        AssetIdent::from_path(render_path),
+        context,
+        chunking_context.with_layer("next_export".to_string()),
+        None,
+        vec![Vc::cell(input)],
+        Completion::immutable(),
+        should_debug("next_export"),
+    )
+    .await?;
+
+    let SingleValue::Single(rendered) = rendered
+        .try_into_single()
+        .await
+        .context("rendering the static export failed")?
+    else {
+        bail!("rendering the static export failed");
+    };
+    parse_json_with_source_context(rendered.to_str()?)
+}
+
+fn write_rendered_path(
+    out_dir: &Path,
+    build_id: &str,
+    rendered: RenderedPath,
+    trailing_slash: bool,
+) -> Result<()> {
+    if let Some(page_data) = rendered.page_data {
+        // Written to the path the client fetches it from, which doesn't
+        // depend on `trailingSlash`.
+        let file = get_asset_path_from_pathname(&rendered.path, ".json");
+        write_file(
+            &out_dir
+                .join("_next/data")
+                .join(build_id)
+                .join(file.trim_start_matches('/')),
+            serde_json::to_vec(&page_data)?,
+        )?;
+    }
+
+    let (file, content) = match (rendered.html, rendered.body) {
+        (Some(html), _) => {
+            // Static hosts look for 404.html in the root.
+            let file = if rendered.path == "/404" {
+                "/404.html".to_string()
+            } else {
+                get_html_path_from_pathname(&rendered.path, trailing_slash)
+            };
+            (file, html.into_bytes())
+        }
+        (None, Some(body)) => (
+            rendered.path,
+            base64::engine::general_purpose::STANDARD.decode(body)?,
+        ),
+        (None, None) => return Ok(()),
+    };
+
+    let path = out_dir.join(file.trim_start_matches('/'));
+    write_file(&path, content)?;
+    if let Some(rsc) = rendered.rsc {
+        fs::write(path.with_extension("txt"), rsc)?;
+    }
+    Ok(())
+}
+
+fn write_file(path: &Path, content: impl AsRef<[u8]>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)?;
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    if !from.exists() {
+        return Ok(());
+    }
+    let mut files = vec![];
+    collect_files(from, &mut files)?;
+    for file in files {
+        let target = to.join(file.strip_prefix(from)?);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(&file, &target).with_context(|| format!("copying {}", file.display()))?;
+    }
+    Ok(())
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T> {
+    serde_json::from_str(
+        &fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?,
+    )
+    .with_context(|| format!("parsing {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, fs};
+
+    use serde_json::json;
+
+    use super::{get_default_path_map, write_rendered_path, RenderedPath};
+
+    #[test]
+    fn collects_dynamic_routes() {
+        let pages = HashMap::from(
+            ["/_app", "/_error", "/about", "/api/hello", "/posts/[slug]"]
+                .map(|page| (page.to_string(), format!("pages{page}.js"))),
+        );
+        let app_paths = HashMap::from(
+            [("/page", "/"), ("/blog/[slug]/page", "/blog/[slug]")]
+                .map(|(page, pathname)| (page.to_string(), pathname.to_string())),
+        );
+
+        let (path_map, dynamic_routes) = get_default_path_map(&pages, &app_paths);
+
+        assert_eq!(
+            serde_json::to_value(path_map).unwrap(),
+            json!({
+                "/": { "page": "/page", "_isAppDir": true },
+                "/404": { "page": "/_error" },
+                "/about": { "page": "/about" },
+            })
+        );
+        assert_eq!(
+            dynamic_routes,
+            [
+                json!({ "page": "/blog/[slug]/page", "pathname": "/blog/[slug]", "_isAppDir": true }),
+                json!({ "page": "/posts/[slug]", "pathname": "/posts/[slug]" }),
+            ]
+        );
+    }
+
+    #[test]
+    fn writes_static_props_data() -> anyhow::Result<()> {
+        let out_dir = tempfile::tempdir()?;
+        let out_dir = out_dir.path();
+        for path in ["/", "/posts/hello"] {
+            write_rendered_path(
+                out_dir,
+                "build-id",
+                RenderedPath {
+                    path: path.to_string(),
+                    html: Some("<html></html>".to_string()),
+                    rsc: None,
+                    page_data: Some(json!({ "pageProps": { "path": path } })),
+                    body: None,
+                    error: None,
+                },
+                true,
+            )?;
+        }
+
+        assert!(out_dir.join("index.html").is_file());
+        assert!(out_dir.join("posts/hello/index.html").is_file());
+        assert_eq!(
+            fs::read_to_string(out_dir.join("_next/data/build-id/index.json"))?,
+            r#"{"pageProps":{"path":"/"}}"#
+        );
+        assert_eq!(
+            fs::read_to_string(out_dir.join("_next/data/build-id/posts/hello.json"))?,
+            r#"{"pageProps":{"path":"/posts/hello"}}"#
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,322 @@
+// Renders the paths of a static export, like `exportPage` in
+// next.js/packages/next/src/export/worker.ts.
+//
+// Evaluated by next-build with `{ dir, distDir, buildId, app,
+// hasExportPathMap, pathMap, dynamicRoutes }` and returns the rendered paths.
+// The files are written by next-build.
+//
+// Pages and app paths need different React builds, so they are rendered by
+// separate processes, selected by `app`.
+
+require('next/dist/server/node-polyfill-fetch')
+require('next/dist/server/node-environment')
+
+const fs = require('fs')
+const path = require('path')
+const { default: loadConfig } = require('next/dist/server/config')
+const { PHASE_EXPORT } = require('next/dist/shared/lib/constants')
+const { loadComponents } = require('next/dist/server/load-components')
+const { isPageStatic } = require('next/dist/build/utils')
+const {
+  createRequestResponseMocks,
+} = require('next/dist/server/lib/mock-request')
+const { NodeNextRequest } = require('next/dist/server/base-http/node')
+const {
+  NextRequestAdapter,
+  signalFromNodeResponse,
+} = require('next/dist/server/web/spec-extension/adapters/next-request')
+const {
+  RouteModuleLoader,
+} = require('next/dist/server/future/helpers/module-loader/route-module-loader')
+const {
+  isDynamicRoute,
+} = require('next/dist/shared/lib/router/utils/is-dynamic')
+const {
+  getRouteMatcher,
+} = require('next/dist/shared/lib/router/utils/route-matcher')
+const {
+  getRouteRegex,
+} = require('next/dist/shared/lib/router/utils/route-regex')
+const {
+  normalizeAppPath,
+} = require('next/dist/shared/lib/router/utils/app-paths')
+const { isAppRouteRoute } = require('next/dist/lib/is-app-route-route')
+const {
+  DYNAMIC_ERROR_CODE,
+} = require('next/dist/client/components/hooks-server-context')
+const envConfig = require('next/dist/shared/lib/runtime-config.shared-runtime')
+
+const DYNAMIC_RENDERING_ERROR =
+  'uses dynamic rendering, which is not supported with "output: export"'
+
+export default async function renderExport(
+  _ipc,
+  { dir, distDir, buildId, app, hasExportPathMap, pathMap, dynamicRoutes }
+) {
+  const results = []
+  const nextConfig = await loadConfig(PHASE_EXPORT, dir)
+
+  // Dynamic routes are exported with the paths returned by their
+  // `getStaticPaths` or `generateStaticParams`, which next.js passes to
+  // `exportPathMap` as part of the default path map.
+  for (const route of dynamicRoutes) {
+    if (!!route._isAppDir !== app) {
+      continue
+    }
+
+    let result
+    try {
+      result = await getStaticPaths(route, distDir, nextConfig)
+    } catch (err) {
+      result = { error: `${err.stack || err}` }
+    }
+    if (result.error) {
+      results.push({ path: route.pathname, error: result.error })
+      continue
+    }
+    for (const staticPath of result.paths) {
+      pathMap[staticPath] = app
+        ? { page: route.page, _isAppDir: true }
+        : { page: route.page }
+    }
+  }
+
+  // Both processes call `exportPathMap`, as either of them might need to
+  // render some of the returned paths.
+  const exportPathMap = hasExportPathMap
+    ? await nextConfig.exportPathMap(pathMap, {
+        dev: false,
+        dir,
+        outDir: path.join(dir, 'out'),
+        distDir,
+        buildId,
+      })
+    : pathMap
+
+  const renderOpts = {
+    dir,
+    buildId,
+    nextExport: true,
+    assetPrefix: nextConfig.assetPrefix.replace(/\/$/, ''),
+    distDir,
+    dev: false,
+    basePath: nextConfig.basePath,
+    trailingSlash: nextConfig.trailingSlash,
+    supportsDynamicHTML: false,
+    crossOrigin: nextConfig.crossOrigin,
+    nextConfigOutput: nextConfig.output,
+    optimizeFonts: false,
+    images: nextConfig.images,
+    serverComponents: app,
+    hasServerComponents: app,
+    nextFontManifest: readJson(distDir, 'server/next-font-manifest.json'),
+    strictNextHead: !!nextConfig.experimental.strictNextHead,
+    ...(app
+      ? {
+          serverActionsManifest: readJson(
+            distDir,
+            'server/server-reference-manifest.json'
+          ),
+        }
+      : {}),
+  }
+  if (Object.keys(nextConfig.publicRuntimeConfig).length > 0) {
+    renderOpts.runtimeConfig = nextConfig.publicRuntimeConfig
+  }
+  envConfig.setConfig({
+    serverRuntimeConfig: nextConfig.serverRuntimeConfig,
+    publicRuntimeConfig: nextConfig.publicRuntimeConfig,
+  })
+
+  // We need this for server rendering the Link component.
+  globalThis.__NEXT_DATA__ = { nextExport: true }
+
+  for (const [pathname, entry] of Object.entries(exportPathMap)) {
+    if (!!entry._isAppDir !== app) {
+      continue
+    }
+
+    let result
+    try {
+      result = await renderPath(pathname, entry, distDir, renderOpts)
+    } catch (err) {
+      result = {
+        error:
+          err.digest === DYNAMIC_ERROR_CODE
+            ? DYNAMIC_RENDERING_ERROR
+            : `${err.stack || err}`,
+      }
+    }
+    results.push({ path: pathname, ...result })
+  }
+
+  return results
+}
+
+function readJson(distDir, file) {
+  return JSON.parse(fs.readFileSync(path.join(distDir, file), 'utf8'))
+}
+
+async function getStaticPaths(
+  { page, pathname, _isAppDir },
+  distDir,
+  nextConfig
+) {
+  const result = await isPageStatic({
+    page: pathname,
+    originalAppPath: _isAppDir ? page : undefined,
+    distDir,
+    configFileName: nextConfig.configFileName,
+    runtimeEnvConfig: {
+      serverRuntimeConfig: nextConfig.serverRuntimeConfig,
+      publicRuntimeConfig: nextConfig.publicRuntimeConfig,
+    },
+    httpAgentOptions: nextConfig.httpAgentOptions,
+    locales: nextConfig.i18n?.locales,
+    defaultLocale: nextConfig.i18n?.defaultLocale,
+    pageType: _isAppDir ? 'app' : 'pages',
+    incrementalCacheHandlerPath:
+      nextConfig.experimental.incrementalCacheHandlerPath,
+    isrFlushToDisk: nextConfig.experimental.isrFlushToDisk,
+    maxMemoryCacheSize: nextConfig.experimental.isrMemoryCacheSize,
+    nextConfigOutput: nextConfig.output,
+  })
+  const paths = result.prerenderRoutes || []
+
+  if (!_isAppDir) {
+    if (result.prerenderRoutes && result.prerenderFallback !== false) {
+      return {
+        error:
+          'Pages with `fallback` enabled in `getStaticPaths` can not be exported.\nRead more: https://nextjs.org/docs/messages/ssg-fallback-true-export',
+      }
+    }
+    return { paths }
+  }
+
+  // Dynamic rendering is reported by next-build.
+  const { dynamic, revalidate } = result.appConfig || {}
+  if (
+    paths.length === 0 &&
+    revalidate !== 0 &&
+    dynamic !== 'error' &&
+    dynamic !== 'force-static'
+  ) {
+    return {
+      error: `Page "${page}" is missing "generateStaticParams()" so it cannot be used with "output: export" config.`,
+    }
+  }
+  return { paths }
+}
+
+async function renderPath(pathname, { page, query = {} }, distDir, renderOpts) {
+  const isAppDir = !!renderOpts.serverComponents
+  const isRouteHandler = isAppDir && isAppRouteRoute(page)
+  const normalizedPage = isAppDir ? normalizeAppPath(page) : page
+
+  let params
+  if (isDynamicRoute(normalizedPage) && normalizedPage !== pathname) {
+    params = getRouteMatcher(getRouteRegex(normalizedPage))(pathname)
+    if (!params) {
+      throw new Error(
+        `The provided export path '${pathname}' doesn't match the '${page}' page.\nRead more: https://nextjs.org/docs/messages/export-path-mismatch`
+      )
+    }
+    query = { ...query, ...params }
+  }
+
+  const { req, res } = createRequestResponseMocks({ url: pathname })
+  if (pathname === '/404') {
+    res.statusCode = 404
+  }
+  if (renderOpts.trailingSlash && !req.url.endsWith('/')) {
+    req.url += '/'
+  }
+
+  if (isRouteHandler) {
+    req.url = `http://localhost:3000${req.url}`
+    const request = NextRequestAdapter.fromNodeNextRequest(
+      new NodeNextRequest(req),
+      signalFromNodeResponse(res)
+    )
+    const context = {
+      params,
+      prerenderManifest: {
+        version: 4,
+        routes: {},
+        dynamicRoutes: {},
+        preview: {
+          previewModeEncryptionKey: '',
+          previewModeId: '',
+          previewModeSigningKey: '',
+        },
+        notFoundRoutes: [],
+      },
+      staticGenerationContext: {
+        originalPathname: page,
+        nextExport: true,
+        supportsDynamicHTML: false,
+      },
+    }
+    const routeModule = await RouteModuleLoader.load(
+      path.posix.join(distDir, 'server', 'app', page)
+    )
+    const response = await routeModule.handle(request, context)
+    if (context.staticGenerationContext.store?.revalidate === 0) {
+      return { error: DYNAMIC_RENDERING_ERROR }
+    }
+    const body = Buffer.from(await response.arrayBuffer())
+    return { body: body.toString('base64') }
+  }
+
+  const components = await loadComponents({
+    distDir,
+    pathname: page,
+    isAppPath: isAppDir,
+  })
+  const curRenderOpts = {
+    ...components,
+    ...renderOpts,
+    params,
+    originalPathname: page,
+  }
+
+  if (isAppDir) {
+    const {
+      renderToHTMLOrFlight,
+    } = require('next/dist/server/future/route-modules/app-page/module.compiled')
+    curRenderOpts.params ||= {}
+    const result = await renderToHTMLOrFlight(
+      req,
+      res,
+      page === '/_not-found' ? '/404' : normalizedPage,
+      query,
+      curRenderOpts
+    )
+    const html = result.toUnchunkedString()
+    if (result.metadata.revalidate === 0) {
+      return { error: DYNAMIC_RENDERING_ERROR }
+    }
+    return { html, rsc: result.metadata.pageData }
+  }
+
+  if (components.getServerSideProps) {
+    return { error: DYNAMIC_RENDERING_ERROR }
+  }
+  if (typeof components.Component === 'string') {
+    return { html: components.Component }
+  }
+  const {
+    renderToHTML,
+  } = require('next/dist/server/future/route-modules/pages/module.compiled')
+  const result = await renderToHTML(req, res, page, query, curRenderOpts)
+  const metadata = result?.metadata || {}
+  return {
+    // Paths for which `getStaticProps` returns `notFound` aren't exported.
+    html: metadata.isNotFound
+      ? undefined
+      : result && !result.isNull
+      ? result.toUnchunkedString()
+      : '',
+    pageData: metadata.pageData,
+  }
+}
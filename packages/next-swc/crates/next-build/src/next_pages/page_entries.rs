@@ -31,8 +31,11 @@ use turbopack_binding::{
             compile_time_info::CompileTimeInfo,
             context::AssetContext,
             file_source::FileSource,
+            module::Module,
             output::OutputAsset,
-            reference_type::{EntryReferenceSubType, ReferenceType},
+            reference_type::{
+                EcmaScriptModulesReferenceSubType, EntryReferenceSubType, ReferenceType,
+            },
             source::Source,
         },
         ecmascript::{
@@ -316,6 +319,8 @@ pub struct PageEntry {
     pub ssr_module: Vc<Box<dyn EcmascriptChunkPlaceable>>,
     /// The client entry module asset.
     pub client_module: Vc<EcmascriptModuleAsset>,
+    /// The page's own module, whose exports are analyzed for static exports.
+    pub page_module: Vc<Box<dyn Module>>,
 }
 
 #[turbo_tasks::function]
@@ -356,10 +361,18 @@ async fn get_page_entry_for_file(
         bail!("expected an ECMAScript module asset");
     };
 
+    let page_module = ssr_module_context.process(
+        source,
+        Value::new(ReferenceType::EcmaScriptModules(
+            EcmaScriptModulesReferenceSubType::Undefined,
+        )),
+    );
+
     Ok(PageEntry {
         pathname,
         ssr_module,
         client_module,
+        page_module,
     }
     .cell())
 }
@@ -0,0 +1,391 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{Context, Result};
+use dunce::canonicalize;
+use serde::Deserialize;
+use turbopack_binding::{
+    features::node_file_trace::{start, Args},
+    turbo::{tasks::TurboTasks, tasks_fs::glob::Glob, tasks_memory::MemoryBackend},
+    turbopack::turbopack::{
+        module_options::ModuleOptionsContext, resolve_options_context::ResolveOptionsContext,
+    },
+};
+
+/// The subset of `.next/required-server-files.json` needed to create the
+/// standalone output.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RequiredServerFiles {
+    config: serde_json::Value,
+    relative_app_dir: String,
+    files: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StandaloneConfig {
+    output: Option<String>,
+    #[serde(default)]
+    output_file_tracing: bool,
+    #[serde(default)]
+    experimental: StandaloneExperimentalConfig,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StandaloneExperimentalConfig {
+    output_file_tracing_root: Option<String>,
+    output_file_tracing_ignores: Option<Vec<String>>,
+}
+
+/// Creates `.next/standalone` for `output: "standalone"`, like
+/// `copyTracedFiles` in next.js/packages/next/src/build/utils.ts.
+///
+/// This needs to run after the build has been written to disk, as the
+/// server's runtime dependencies are traced from the emitted files.
+pub(crate) async fn emit_standalone(
+    turbo_tasks: &Arc<TurboTasks<MemoryBackend>>,
+    app_dir: &Path,
+    workspace_root: &Path,
+) -> Result<()> {
+    let dist_dir = app_dir.join(".next");
+    let required_server_files = read_required_server_files(&dist_dir)?;
+    let config: StandaloneConfig = serde_json::from_value(required_server_files.config.clone())?;
+    if config.output.as_deref() != Some("standalone") || !config.output_file_tracing {
+        return Ok(());
+    }
+
+    // The traced paths and `relativeAppDir` are relative to the canonical
+    // tracing root.
+    let tracing_root = canonicalize(
+        config
+            .experimental
+            .output_file_tracing_root
+            .map(PathBuf::from)
+            .unwrap_or_else(|| workspace_root.to_path_buf()),
+    )
+    .context("outputFileTracingRoot can't be found")?;
+    let ignores = config
+        .experimental
+        .output_file_tracing_ignores
+        .unwrap_or_default()
+        .iter()
+        .map(|pattern| Glob::parse(pattern))
+        .collect::<Result<Vec<_>>>()?;
+
+    // All server files are copied, as some of them are only loaded
+    // dynamically. Their runtime dependencies are found by tracing them.
+    let mut server_files = vec![];
+    collect_files(&dist_dir.join("server"), &mut server_files)?;
+    let mut inputs: Vec<PathBuf> = server_files
+        .iter()
+        .filter(|path| path.extension().is_some_and(|ext| ext == "js"))
+        .cloned()
+        .collect();
+    let next_package = find_next_package(app_dir)
+        .context("the `next` package is required for standalone output")?;
+    inputs.push(next_package.join("dist/server/lib/start-server.js"));
+    inputs.push(next_package.join("dist/server/next-server.js"));
+
+    let args: Args = serde_json::from_value(serde_json::json!({
+        "action": "print",
+        "input": inputs,
+        "contextDirectory": tracing_root,
+        "processCwd": app_dir,
+    }))?;
+    let traced_files = start(
+        Arc::new(args),
+        Some(turbo_tasks),
+        Some(ModuleOptionsContext {
+            enable_types: true,
+            ..Default::default()
+        }),
+        Some(ResolveOptionsContext {
+            ..Default::default()
+        }),
+    )
+    .await?;
+
+    write_standalone(
+        app_dir,
+        &tracing_root,
+        required_server_files,
+        server_files,
+        traced_files,
+        &ignores,
+    )
+}
+
+fn read_required_server_files(dist_dir: &Path) -> Result<RequiredServerFiles> {
+    Ok(serde_json::from_str(
+        &fs::read_to_string(dist_dir.join("required-server-files.json"))
+            .context("reading required-server-files.json")?,
+    )?)
+}
+
+/// Copies the traced and required server files to `.next/standalone` and
+/// writes its `server.js`.
+fn write_standalone(
+    app_dir: &Path,
+    tracing_root: &Path,
+    required_server_files: RequiredServerFiles,
+    server_files: Vec<PathBuf>,
+    traced_files: impl IntoIterator<Item = String>,
+    ignores: &[Glob],
+) -> Result<()> {
+    let output_dir = app_dir.join(".next/standalone");
+    if output_dir.exists() {
+        fs::remove_dir_all(&output_dir)?;
+    }
+
+    let mut copied = HashSet::new();
+    for relative_path in traced_files {
+        if ignores.iter().any(|glob| glob.execute(&relative_path)) {
+            continue;
+        }
+        copy_file(
+            &tracing_root.join(&relative_path),
+            &output_dir.join(&relative_path),
+            &mut copied,
+        )?;
+    }
+
+    let standalone_app_dir = output_dir.join(&required_server_files.relative_app_dir);
+    for path in server_files {
+        let relative_path = path.strip_prefix(app_dir)?;
+        copy_file(&path, &standalone_app_dir.join(relative_path), &mut copied)?;
+    }
+    for file in &required_server_files.files {
+        let path = app_dir.join(file);
+        // Some of the files are only written by the next.js build.
+        if path.exists() {
+            copy_file(&path, &standalone_app_dir.join(file), &mut copied)?;
+        }
+    }
+
+    fs::write(
+        standalone_app_dir.join("server.js"),
+        server_js(app_dir, required_server_files.config)?,
+    )?;
+
+    Ok(())
+}
+
+pub(crate) fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Finds the `next` package like Node.js would when resolving it from the
+/// project.
+fn find_next_package(app_dir: &Path) -> Option<PathBuf> {
+    app_dir
+        .ancestors()
+        .map(|dir| dir.join("node_modules/next"))
+        .find(|dir| dir.join("package.json").exists())
+}
+
+/// Copies a file, keeping symlinks as they are, as package managers like
+/// pnpm link packages.
+fn copy_file(from: &Path, to: &Path, copied: &mut HashSet<PathBuf>) -> Result<()> {
+    if !copied.insert(to.to_path_buf()) {
+        return Ok(());
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    match fs::read_link(from) {
+        Ok(target) => symlink(&target, to, from.is_dir()),
+        Err(_) => {
+            fs::copy(from, to).with_context(|| format!("copying {}", from.display()))?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, path: &Path, _is_dir: bool) -> Result<()> {
+    Ok(std::os::unix::fs::symlink(target, path)?)
+}
+
+/// Windows distinguishes between file and directory symlinks, so the kind
+/// of the link's target (`is_dir`) has to be passed along.
+#[cfg(windows)]
+fn symlink(target: &Path, path: &Path, is_dir: bool) -> Result<()> {
+    if is_dir {
+        Ok(std::os::windows::fs::symlink_dir(target, path)?)
+    } else {
+        Ok(std::os::windows::fs::symlink_file(target, path)?)
+    }
+}
+
+/// The entry point of the standalone server.
+fn server_js(app_dir: &Path, mut config: serde_json::Value) -> Result<String> {
+    config["distDir"] = "./.next".into();
+    config["configFile"] = serde_json::Value::Null;
+
+    let is_module = fs::read_to_string(app_dir.join("package.json"))
+        .ok()
+        .and_then(|package_json| serde_json::from_str::<serde_json::Value>(&package_json).ok())
+        .is_some_and(|package_json| package_json["type"] == "module");
+    let imports = if is_module {
+        indoc::indoc! {r#"
+            import path from 'path'
+            import { fileURLToPath } from 'url'
+            const __dirname = fileURLToPath(new URL('.', import.meta.url))
+            import { startServer } from 'next/dist/server/lib/start-server.js'
+        "#}
+    } else {
+        indoc::indoc! {r#"
+            const path = require('path')
+            const { startServer } = require('next/dist/server/lib/start-server')
+        "#}
+    };
+
+    Ok(indoc::formatdoc! {r#"
+        {imports}
+        const dir = path.join(__dirname)
+
+        process.env.NODE_ENV = 'production'
+        process.chdir(__dirname)
+
+        // Make sure commands gracefully respect termination signals (e.g. from Docker)
+        // Allow the graceful termination to be manually configurable
+        if (!process.env.NEXT_MANUAL_SIG_HANDLE) {{
+          process.on('SIGTERM', () => process.exit(0))
+          process.on('SIGINT', () => process.exit(0))
+        }}
+
+        const currentPort = parseInt(process.env.PORT, 10) || 3000
+        const hostname = process.env.HOSTNAME || 'localhost'
+
+        let keepAliveTimeout = parseInt(process.env.KEEP_ALIVE_TIMEOUT, 10)
+        const nextConfig = {config}
+
+        process.env.__NEXT_PRIVATE_STANDALONE_CONFIG = JSON.stringify(nextConfig)
+        process.env.__NEXT_PRIVATE_PREBUNDLED_REACT = nextConfig.experimental && nextConfig.experimental.serverActions
+          ? 'experimental'
+          : 'next'
+
+        if (
+          Number.isNaN(keepAliveTimeout) ||
+          !Number.isFinite(keepAliveTimeout) ||
+          keepAliveTimeout < 0
+        ) {{
+          keepAliveTimeout = undefined
+        }}
+
+        startServer({{
+          dir,
+          isDev: false,
+          config: nextConfig,
+          hostname,
+          port: currentPort,
+          allowRetry: false,
+          keepAliveTimeout,
+          useWorkers: true,
+        }}).catch((err) => {{
+          console.error(err);
+          process.exit(1);
+        }});
+    "#,
+        config = serde_json::to_string(&config)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use turbopack_binding::turbo::tasks_fs::glob::Glob;
+
+    use super::{read_required_server_files, write_standalone};
+
+    #[test]
+    fn copies_the_standalone_tree() -> anyhow::Result<()> {
+        let root = tempfile::tempdir()?;
+        let root = dunce::canonicalize(root.path())?;
+        let app_dir = root.join("apps/web");
+        let dist_dir = app_dir.join(".next");
+        fs::create_dir_all(dist_dir.join("server/pages"))?;
+        fs::create_dir_all(root.join("node_modules/dep"))?;
+        fs::write(dist_dir.join("server/pages/index.js"), "")?;
+        fs::write(dist_dir.join("BUILD_ID"), "build-id")?;
+        fs::write(root.join("node_modules/dep/index.js"), "")?;
+        fs::write(root.join("node_modules/dep/README.md"), "")?;
+        fs::write(
+            dist_dir.join("required-server-files.json"),
+            serde_json::json!({
+                "version": 1,
+                "config": {
+                    "output": "standalone",
+                    "outputFileTracing": true,
+                },
+                "appDir": app_dir,
+                "relativeAppDir": "apps/web",
+                "files": [".next/BUILD_ID", ".next/prerender-manifest.json"],
+                "ignore": [],
+            })
+            .to_string(),
+        )?;
+        #[cfg(unix)]
+        std::os::unix::fs::symlink("dep", root.join("node_modules/linked"))?;
+
+        let required_server_files = read_required_server_files(&dist_dir)?;
+        assert_eq!(required_server_files.relative_app_dir, "apps/web");
+
+        let mut traced_files = vec![
+            "node_modules/dep/index.js".to_string(),
+            "node_modules/dep/README.md".to_string(),
+        ];
+        if cfg!(unix) {
+            traced_files.push("node_modules/linked".to_string());
+        }
+        write_standalone(
+            &app_dir,
+            &root,
+            required_server_files,
+            vec![dist_dir.join("server/pages/index.js")],
+            traced_files,
+            &[Glob::parse("**/*.md")?],
+        )?;
+
+        let standalone = dist_dir.join("standalone");
+        assert!(standalone.join("node_modules/dep/index.js").is_file());
+        assert!(!standalone.join("node_modules/dep/README.md").exists());
+        #[cfg(unix)]
+        assert_eq!(
+            fs::read_link(standalone.join("node_modules/linked"))?,
+            std::path::Path::new("dep")
+        );
+
+        let standalone_app_dir = standalone.join("apps/web");
+        assert!(standalone_app_dir
+            .join(".next/server/pages/index.js")
+            .is_file());
+        assert_eq!(
+            fs::read_to_string(standalone_app_dir.join(".next/BUILD_ID"))?,
+            "build-id"
+        );
+        assert!(!standalone_app_dir
+            .join(".next/prerender-manifest.json")
+            .exists());
+        let server_js = fs::read_to_string(standalone_app_dir.join("server.js"))?;
+        assert!(server_js.contains(r#""distDir":"./.next""#));
+        assert!(server_js.contains("require('next/dist/server/lib/start-server')"));
+
+        Ok(())
+    }
+}
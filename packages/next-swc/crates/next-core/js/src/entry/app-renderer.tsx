@@ -77,7 +77,7 @@ async function runOperation(renderData: RenderData) {
   } = {
     // TODO: give an actual buildId when next build is supported
     buildId: 'development',
-    basePath: '',
+    basePath: renderData.data?.basePath ?? '',
     params: renderData.params,
     supportsDynamicHTML: true,
     dev: true,
@@ -129,7 +129,7 @@ async function runOperation(renderData: RenderData) {
     clientReferenceManifest,
     runtime: 'nodejs',
     serverComponents: true,
-    assetPrefix: '',
+    assetPrefix: renderData.data?.assetPrefix ?? '',
     pageConfig: {},
     reactLoadableManifest: {},
     nextConfigOutput: renderData.data?.nextConfigOutput,
@@ -1,5 +1,5 @@
 import type { Ipc, StructuredError } from '@vercel/turbopack-node/ipc/index'
-import type { IncomingMessage } from 'node:http'
+import type { ClientRequest, IncomingMessage } from 'node:http'
 import { Buffer } from 'node:buffer'
 import { createServer, makeRequest, type ServerInfo } from '../internal/server'
 import { toPairs } from '../internal/headers'
@@ -21,9 +21,12 @@ type RouterRequest = {
   pathname: string
   rawHeaders: [string, string][]
   rawQuery: string
-  body: number[][]
 }
 
+type IpcIncomingMessage =
+  | { type: 'bodyChunk'; data: number[] }
+  | { type: 'bodyEnd' }
+
 type IpcOutgoingMessage = {
   type: 'value'
   data: string | Buffer
@@ -74,7 +77,7 @@ async function getResolveRoute(
 }
 
 export default async function route(
-  ipc: Ipc<RouterRequest, IpcOutgoingMessage>,
+  ipc: Ipc<IpcIncomingMessage, IpcOutgoingMessage>,
   routerRequest: RouterRequest,
   dir: string,
   serverInfo: ServerInfo
@@ -99,13 +102,10 @@ export default async function route(
       serverInfo
     )
 
-    const body = Buffer.concat(
-      routerRequest.body.map((arr) => Buffer.from(arr))
-    )
-
-    // Send the clientRequest, so the server parses everything. We can then pass
-    // the serverRequest to Next.js to handle.
-    clientRequest.end(body)
+    // Stream the body to the clientRequest, so the server parses everything
+    // while it's still being received. We can then pass the serverRequest to
+    // Next.js to handle.
+    const bodyPromise = streamBody(ipc, clientRequest)
 
     // The route promise must not block us from starting the middleware
     // response handling, so we cannot await it yet. By making the call, we
@@ -121,9 +121,13 @@ export default async function route(
       handleMiddlewareResponse(ipc, c)
     )
 
-    // Now that both promises are in progress, we await both so that a
-    // rejection in either will end the routing.
-    const [routeResult] = await Promise.all([routePromise, middlewarePromise])
+    // Now that all promises are in progress, we await them so that a
+    // rejection in any will end the routing.
+    const [routeResult] = await Promise.all([
+      routePromise,
+      middlewarePromise,
+      bodyPromise,
+    ])
 
     server.close()
 
@@ -154,8 +158,32 @@ export default async function route(
   }
 }
 
+async function streamBody(
+  ipc: Ipc<IpcIncomingMessage, IpcOutgoingMessage>,
+  clientRequest: ClientRequest
+): Promise<void> {
+  while (true) {
+    const msg = await ipc.recv()
+
+    switch (msg.type) {
+      case 'bodyChunk': {
+        clientRequest.write(Buffer.from(msg.data))
+        break
+      }
+      case 'bodyEnd': {
+        clientRequest.end()
+        return
+      }
+      default: {
+        // @ts-expect-error msg.type is never
+        throw new Error(`unexpected message type: ${msg.type}`)
+      }
+    }
+  }
+}
+
 async function handleMiddlewareResponse(
-  ipc: Ipc<RouterRequest, IpcOutgoingMessage>,
+  ipc: Ipc<IpcIncomingMessage, IpcOutgoingMessage>,
   clientResponse: IncomingMessage
 ): Promise<void> {
   // If this header is specified, we know that the response was not handled by
@@ -94,6 +94,20 @@ export default function startHandler({
     Component: NextComponentType,
     namespace: Record<string, any>
   ): Promise<IpcOutgoingMessage> {
+    const i18n = renderData.data?.i18n ?? undefined
+    const parsedQuery = parse(renderData.rawQuery)
+    // The Next.js router passes the detected locale through internal query
+    // params.
+    const defaultLocale = i18n
+      ? (parsedQuery.__nextDefaultLocale as string | undefined) ??
+        i18n.defaultLocale
+      : undefined
+    const locale = i18n
+      ? (parsedQuery.__nextLocale as string | undefined) ?? defaultLocale
+      : undefined
+    delete parsedQuery.__nextLocale
+    delete parsedQuery.__nextDefaultLocale
+
     if ('getStaticPaths' in namespace) {
       const {
         paths: prerenderRoutes,
@@ -102,6 +116,8 @@ export default function startHandler({
       } = await buildStaticPaths({
         page: renderData.path,
         getStaticPaths: namespace.getStaticPaths,
+        locales: i18n?.locales,
+        defaultLocale,
         // TODO(alexkirsz) Provide the correct next.config.js path.
         configFileName: 'next.config.js',
       })
@@ -174,7 +190,7 @@ export default function startHandler({
       /* RenderOptsPartial */
       isDataReq,
       runtimeConfig: {},
-      assetPrefix: '',
+      assetPrefix: renderData.data?.assetPrefix ?? '',
       canonicalBase: '',
       previewProps: {
         previewModeId: '',
@@ -182,7 +198,11 @@ export default function startHandler({
         previewModeSigningKey: '',
       },
       params: renderData.params,
-      basePath: '',
+      basePath: renderData.data?.basePath ?? '',
+      locale,
+      locales: i18n?.locales,
+      defaultLocale,
+      domainLocales: i18n?.domains,
       // TODO(WEB-583) this isn't correct, instead it should set `dev: true`
       nextExport: true,
       nextConfigOutput: renderData.data?.nextConfigOutput,
@@ -229,7 +249,6 @@ export default function startHandler({
     } as any
     const res: ServerResponse = createServerResponse(req, renderData.path)
 
-    const parsedQuery = parse(renderData.rawQuery)
     const query = { ...parsedQuery, ...renderData.params }
 
     const renderResult = await renderToHTML(
@@ -1,5 +1,6 @@
 import type { ServerInfo } from '@vercel/turbopack-next/internal/server'
 import type { RenderOptsPartial } from 'next/dist/server/render'
+import type { I18NConfig } from 'next/dist/server/config-shared'
 
 export type RenderData = {
   params: Record<string, string | string[]>
@@ -11,6 +12,9 @@ export type RenderData = {
   rawHeaders: Array<[string, string]>
   data?: {
     nextConfigOutput?: RenderOptsPartial['nextConfigOutput']
+    basePath?: string
+    assetPrefix?: string
+    i18n?: I18NConfig | null
     serverInfo?: ServerInfo
     allowedRevalidateHeaderKeys?: string[]
     fetchCacheKeyPrefix?: string
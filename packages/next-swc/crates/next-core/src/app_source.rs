@@ -58,7 +58,9 @@ use crate::{
     embed_js::{next_asset, next_js_file_path},
     env::env_for_js,
     fallback::get_fallback_page,
-    loader_tree::{LoaderTreeModule, ServerComponentTransition},
+    loader_tree::{
+        LoaderTreeModule, ServerComponentTransition, DEFAULT_GLOBAL_ERROR, GLOBAL_ERROR,
+    },
     mode::NextMode,
     next_app::{metadata::route::get_app_metadata_route_source, AppPage, AppPath, PathSegment},
     next_client::{
@@ -964,18 +966,35 @@ impl AppRenderer {
         )
         .await?;
 
-        let mut result = RopeBuilder::from(
+        let global_error_import = if loader_tree_module.global_error_request() == GLOBAL_ERROR {
+            // The custom global error component is already processed as an inner asset.
+            formatdoc!(
+                "
+                \"TURBOPACK {{ chunking-type: isolatedParallel }}\";
+                import GlobalError from \"{GLOBAL_ERROR}\"
+            "
+            )
+        } else {
             formatdoc!(
                 "
                 \"TURBOPACK {{ chunking-type: isolatedParallel; transition: {rsc_transition} }}\";
-                import GlobalErrorMod from \"next/dist/client/components/error-boundary\"
+                import GlobalErrorMod from \"{DEFAULT_GLOBAL_ERROR}\"
                 const {{ GlobalError }} = GlobalErrorMod;
+            "
+            )
+        };
+
+        let mut result = RopeBuilder::from(global_error_import.into_bytes());
+        write!(
+            result,
+            "{}",
+            formatdoc!(
+                "
                 \"TURBOPACK {{ chunking-type: isolatedParallel; transition: {rsc_transition} }}\";
                 import base from \"next/dist/server/app-render/entry-base\"\n
             "
             )
-            .into_bytes(),
-        );
+        )?;
 
         for import in loader_tree_module.imports {
             writeln!(result, "{import}")?;
@@ -12,7 +12,10 @@ use turbo_tasks::{
     Vc,
 };
 use turbopack_binding::{
-    turbo::tasks_fs::{DirectoryContent, DirectoryEntry, FileSystemEntryType, FileSystemPath},
+    turbo::tasks_fs::{
+        DirectoryContent, DirectoryEntry, FileSystemEntryType, FileSystemPath, LinkContent,
+        LinkType,
+    },
     turbopack::core::issue::{Issue, IssueExt, IssueSeverity},
 };
 
@@ -48,6 +51,9 @@ pub struct Components {
     pub default: Option<Vc<FileSystemPath>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub route: Option<Vc<FileSystemPath>>,
+    /// Only used in the root of the app directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub global_error: Option<Vc<FileSystemPath>>,
     #[serde(skip_serializing_if = "Metadata::is_empty")]
     pub metadata: Metadata,
 }
@@ -63,6 +69,7 @@ impl Components {
             not_found: self.not_found,
             default: None,
             route: None,
+            global_error: self.global_error,
             metadata: self.metadata.clone(),
         }
     }
@@ -77,6 +84,7 @@ impl Components {
             not_found: a.not_found.or(b.not_found),
             default: a.default.or(b.default),
             route: a.route.or(b.route),
+            global_error: a.global_error.or(b.global_error),
             metadata: Metadata::merge(&a.metadata, &b.metadata),
         }
     }
@@ -298,15 +306,30 @@ pub async fn find_app_dir_if_enabled(project_path: Vc<FileSystemPath>) -> Result
 }
 
 #[turbo_tasks::function]
-async fn get_directory_tree(
+fn get_directory_tree(
+    dir: Vc<FileSystemPath>,
+    page_extensions: Vc<Vec<String>>,
+) -> Vc<DirectoryTree> {
+    get_directory_tree_internal(dir, page_extensions, Vc::cell(Vec::new()))
+}
+
+/// `ancestors` are the paths of the directories that are being walked, which
+/// a symlink must not point to.
+#[turbo_tasks::function]
+async fn get_directory_tree_internal(
     dir: Vc<FileSystemPath>,
     page_extensions: Vc<Vec<String>>,
+    ancestors: Vc<Vec<String>>,
 ) -> Result<Vc<DirectoryTree>> {
     let DirectoryContent::Entries(entries) = &*dir.read_dir().await? else {
         bail!("{} must be a directory", dir.to_string().await?);
     };
     let page_extensions_value = page_extensions.await?;
 
+    let mut ancestors_value = ancestors.await?.clone_value();
+    ancestors_value.push(dir.await?.path.clone());
+    let ancestors = Vc::cell(ancestors_value);
+
     let mut subdirectories = BTreeMap::new();
     let mut components = Components::default();
 
@@ -316,7 +339,14 @@ async fn get_directory_tree(
     let mut metadata_twitter = Vec::new();
 
     for (basename, entry) in entries {
-        match *entry {
+        let entry = match *entry {
+            DirectoryEntry::Symlink(link) => match resolve_symlink(link).await? {
+                Some(entry) => entry,
+                None => continue,
+            },
+            entry => entry,
+        };
+        match entry {
             DirectoryEntry::File(file) => {
                 if let Some((stem, ext)) = basename.split_once('.') {
                     if page_extensions_value.iter().any(|e| e == ext) {
@@ -329,6 +359,7 @@ async fn get_directory_tree(
                             "not-found" => components.not_found = Some(file),
                             "default" => components.default = Some(file),
                             "route" => components.route = Some(file),
+                            "global-error" => components.global_error = Some(file),
                             _ => {}
                         }
                     }
@@ -382,14 +413,19 @@ async fn get_directory_tree(
                     },
                 ));
             }
-            DirectoryEntry::Directory(dir) => {
+            DirectoryEntry::Directory(subdir) => {
                 // appDir ignores paths starting with an underscore
-                if !basename.starts_with('_') {
-                    let result = get_directory_tree(dir, page_extensions);
-                    subdirectories.insert(get_underscore_normalized_path(basename), result);
+                if basename.starts_with('_') {
+                    continue;
+                }
+                // a symlink to an ancestor would recurse forever
+                if ancestors.await?.contains(&subdir.await?.path) {
+                    symlink_issue(dir.join(basename.clone()), "creates a cycle").await?;
+                    continue;
                 }
+                let result = get_directory_tree_internal(subdir, page_extensions, ancestors);
+                subdirectories.insert(get_underscore_normalized_path(basename), result);
             }
-            // TODO(WEB-952) handle symlinks in app dir
             _ => {}
         }
     }
@@ -993,7 +1029,14 @@ pub async fn get_global_metadata(
     let mut metadata = GlobalMetadata::default();
 
     for (basename, entry) in entries {
-        let DirectoryEntry::File(file) = *entry else {
+        let entry = match *entry {
+            DirectoryEntry::Symlink(link) => match resolve_symlink(link).await? {
+                Some(entry) => entry,
+                None => continue,
+            },
+            entry => entry,
+        };
+        let DirectoryEntry::File(file) = entry else {
             continue;
         };
 
@@ -1017,12 +1060,69 @@ pub async fn get_global_metadata(
         } else {
             *entry = Some(MetadataItem::Static { path: file });
         }
-        // TODO(WEB-952) handle symlinks in app dir
     }
 
     Ok(metadata.cell())
 }
 
+/// The maximum number of symlinks that are followed to resolve a single entry,
+/// like `MAXSYMLINKS` on Linux.
+const MAX_SYMLINK_DEPTH: usize = 40;
+
+/// Resolves a symlink in the app directory to the file or directory it points
+/// to. Emits an issue and returns `None` when the symlink can't be followed.
+async fn resolve_symlink(link: Vc<FileSystemPath>) -> Result<Option<DirectoryEntry>> {
+    let mut path = link;
+    for _ in 0..MAX_SYMLINK_DEPTH {
+        let target = match &*path.read_link().await? {
+            LinkContent::Link { target, link_type } => {
+                let base = if link_type.contains(LinkType::ABSOLUTE) {
+                    path.fs().root()
+                } else {
+                    path.parent()
+                };
+                *base.try_join(target.clone()).await?
+            }
+            // The link is broken.
+            LinkContent::NotFound => return Ok(None),
+            LinkContent::Invalid => None,
+        };
+        let Some(target) = target else {
+            symlink_issue(link, "points outside of the root path").await?;
+            return Ok(None);
+        };
+
+        path = target;
+        match *path.get_type().await? {
+            FileSystemEntryType::File => return Ok(Some(DirectoryEntry::File(path))),
+            FileSystemEntryType::Directory => return Ok(Some(DirectoryEntry::Directory(path))),
+            FileSystemEntryType::Symlink => continue,
+            _ => return Ok(None),
+        }
+    }
+
+    symlink_issue(
+        link,
+        "can't be resolved, as there are too many levels of symlinks",
+    )
+    .await?;
+    Ok(None)
+}
+
+async fn symlink_issue(link: Vc<FileSystemPath>, reason: &str) -> Result<()> {
+    DirectoryTreeIssue {
+        app_dir: link,
+        message: Vc::cell(format!(
+            "The symlink at {} {reason} and is ignored",
+            link.to_string().await?
+        )),
+        severity: IssueSeverity::Warning.cell(),
+    }
+    .cell()
+    .emit();
+    Ok(())
+}
+
 #[turbo_tasks::value(shared)]
 struct DirectoryTreeIssue {
     pub severity: Vc<IssueSeverity>,
@@ -1059,3 +1159,97 @@ impl Issue for DirectoryTreeIssue {
         self.message
     }
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{fs, os::unix::fs::symlink};
+
+    use anyhow::Result;
+    use turbo_tasks::{TurboTasks, Vc};
+    use turbopack_binding::{
+        turbo::{
+            tasks_fs::{DiskFileSystem, FileSystem},
+            tasks_memory::MemoryBackend,
+        },
+        turbopack::core::issue::{IssueDescriptionExt, IssueSeverity},
+    };
+
+    use super::get_directory_tree;
+
+    #[tokio::test]
+    async fn follows_symlinks_in_the_app_dir() -> Result<()> {
+        crate::register();
+
+        let root = tempfile::tempdir()?;
+        let project = root.path().join("project");
+        let app = project.join("app");
+        fs::create_dir_all(app.join("nested"))?;
+        fs::create_dir_all(project.join("shared"))?;
+        fs::create_dir_all(root.path().join("outside"))?;
+        fs::write(app.join("page.js"), "")?;
+        fs::write(project.join("shared/page.js"), "")?;
+        fs::write(root.path().join("outside/page.js"), "")?;
+
+        // a followed directory symlink
+        symlink("../shared", app.join("linked"))?;
+        // cycles back to the directories that are being walked
+        symlink(".", app.join("self"))?;
+        symlink("..", app.join("nested/parent"))?;
+        // symlinks pointing at each other
+        symlink("pong", app.join("ping"))?;
+        symlink("ping", app.join("pong"))?;
+        // a symlink outside of the project root
+        symlink("../../outside", app.join("outside"))?;
+
+        let project = project.to_string_lossy().to_string();
+        let tt = TurboTasks::new(MemoryBackend::default());
+        tt.run_once(async move {
+            let fs = DiskFileSystem::new("project".to_string(), project);
+            let tree = get_directory_tree(
+                fs.root().join("app".to_string()),
+                Vc::cell(vec!["js".to_string()]),
+            );
+
+            let tree_value = tree.await?;
+            let subdirectories: Vec<_> = tree_value.subdirectories.keys().collect();
+            assert_eq!(subdirectories, ["linked", "nested"]);
+            let linked = tree_value.subdirectories["linked"].await?;
+            let page = linked.components.await?.page.expect("linked page");
+            assert_eq!(page.await?.path, "shared/page.js");
+            let nested = tree_value.subdirectories["nested"].await?;
+            assert!(nested.subdirectories.is_empty());
+
+            let issues = tree
+                .peek_issues_with_path()
+                .await?
+                .strongly_consistent()
+                .await?
+                .get_plain_issues()
+                .await?;
+            let mut warnings: Vec<_> = issues
+                .iter()
+                .map(|issue| {
+                    assert_eq!(issue.severity, IssueSeverity::Warning);
+                    issue.description.as_str()
+                })
+                .collect();
+            warnings.sort_unstable();
+            assert_eq!(
+                warnings,
+                [
+                    "The symlink at [project]/app/nested/parent creates a cycle and is ignored",
+                    "The symlink at [project]/app/outside points outside of the root path and is \
+                     ignored",
+                    "The symlink at [project]/app/ping can't be resolved, as there are too many \
+                     levels of symlinks and is ignored",
+                    "The symlink at [project]/app/pong can't be resolved, as there are too many \
+                     levels of symlinks and is ignored",
+                    "The symlink at [project]/app/self creates a cycle and is ignored",
+                ]
+            );
+
+            Ok(())
+        })
+        .await
+    }
+}
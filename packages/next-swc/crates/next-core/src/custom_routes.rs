@@ -0,0 +1,773 @@
+//! Matching of the custom routes (`headers`, `redirects`, `rewrites`) from
+//! next.config.js against incoming requests.
+//!
+//! The source syntax is the one of `path-to-regexp`, as used by
+//! next.js/packages/next/src/shared/lib/router/utils/path-match.ts, and
+//! `has`/`missing` conditions follow `matchHas` from
+//! next.js/packages/next/src/shared/lib/router/utils/prepare-destination.ts.
+
+use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use turbo_tasks::{trace::TraceRawVcs, Vc};
+use turbopack_binding::{
+    turbo::{tasks::primitives::Regex as SerializableRegex, tasks_fs::FileSystemPath},
+    turbopack::core::issue::{Issue, IssueSeverity},
+};
+
+use crate::next_config::{HeaderValue, Headers, Redirects, RouteHas};
+
+/// Parameters extracted from a matched source and from `has` conditions.
+pub type RouteParams = IndexMap<String, String>;
+
+/// The parts of an incoming request that custom routes can match against.
+pub struct RouteRequest<'a> {
+    pub pathname: &'a str,
+    pub raw_query: &'a str,
+    pub raw_headers: &'a [(String, String)],
+}
+
+impl<'a> RouteRequest<'a> {
+    pub(crate) fn header(&self, name: &str) -> Option<&'a str> {
+        self.raw_headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub(crate) fn cookie(&self, name: &str) -> Option<&'a str> {
+        self.raw_headers
+            .iter()
+            .filter(|(key, _)| key.eq_ignore_ascii_case("cookie"))
+            .flat_map(|(_, value)| value.split(';'))
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| key.trim() == name)
+            .map(|(_, value)| value.trim())
+    }
+
+    fn query(&self, name: &str) -> Option<String> {
+        qstring::QString::from(self.raw_query)
+            .get(name)
+            .map(ToString::to_string)
+    }
+
+    pub(crate) fn hostname(&self) -> Option<String> {
+        self.header("host")
+            .and_then(|host| host.split(':').next())
+            .map(|hostname| hostname.to_lowercase())
+    }
+}
+
+/// A compiled custom route source, e.g. `/blog/:slug*`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, TraceRawVcs)]
+pub struct SourceMatcher {
+    #[turbo_tasks(trace_ignore)]
+    regex: SerializableRegex,
+    keys: Vec<String>,
+}
+
+impl SourceMatcher {
+    /// Compiles a `path-to-regexp` source into a matcher.
+    pub fn new(source: &str) -> Result<Self> {
+        let (regex_str, keys) = source_to_regex(source)?;
+        let regex = regex::RegexBuilder::new(&regex_str)
+            .case_insensitive(true)
+            .build()
+            .with_context(|| format!("invalid regular expression for source `{source}`"))?;
+        Ok(Self {
+            regex: SerializableRegex(regex),
+            keys,
+        })
+    }
+
+    /// Returns the params of the source when `pathname` matches it.
+    pub fn params(&self, pathname: &str) -> Option<RouteParams> {
+        let captures = self.regex.captures(pathname)?;
+        Some(
+            self.keys
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, key)| {
+                    let value = captures.get(idx + 1)?;
+                    Some((key.clone(), value.as_str().to_string()))
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Compiles the source of a custom route. next.js has already validated it,
+/// but a source can still use regular expression features that only
+/// JavaScript supports, in which case it's reported and `None` is returned.
+fn compile_source(
+    source: &str,
+    route_type: &str,
+    project_path: Vc<FileSystemPath>,
+) -> Option<SourceMatcher> {
+    match SourceMatcher::new(source) {
+        Ok(matcher) => Some(matcher),
+        Err(err) => {
+            InvalidCustomRouteIssue {
+                path: project_path,
+                route_type: route_type.to_string(),
+                source: source.to_string(),
+                message: format!("`source` parse failed: {err:#}"),
+            }
+            .cell()
+            .emit();
+            None
+        }
+    }
+}
+
+enum Token {
+    Literal(String),
+    Param {
+        name: String,
+        prefix: String,
+        pattern: String,
+        modifier: Option<char>,
+    },
+}
+
+const DEFAULT_PATTERN: &str = r"[^\/#\?]+?";
+
+fn escape_string(str: &str) -> String {
+    let mut escaped = String::with_capacity(str.len());
+    for c in str.chars() {
+        if ".+*?=^!:${}()[]|/\\".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Splits a `path-to-regexp` source into literals and params.
+fn parse_source(source: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut literal = String::new();
+    let mut unnamed_key = 0;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\\' => {
+                let Some(escaped) = chars.get(i + 1) else {
+                    bail!("unexpected end of source `{source}` after `\\`");
+                };
+                literal.push(*escaped);
+                i += 2;
+                continue;
+            }
+            '{' | '}' => bail!("groups (`{{...}}`) in source `{source}` are not supported"),
+            ':' | '(' => {}
+            _ => {
+                literal.push(c);
+                i += 1;
+                continue;
+            }
+        }
+
+        let mut name = String::new();
+        if c == ':' {
+            i += 1;
+            while let Some(&c) = chars.get(i) {
+                if !(c.is_ascii_alphanumeric() || c == '_') {
+                    break;
+                }
+                name.push(c);
+                i += 1;
+            }
+            if name.is_empty() {
+                bail!("missing parameter name at {} in source `{source}`", i - 1);
+            }
+        }
+
+        let mut pattern = String::new();
+        if chars.get(i) == Some(&'(') {
+            let start = i;
+            let mut depth = 1;
+            i += 1;
+            if chars.get(i) == Some(&'?') {
+                bail!("pattern cannot start with \"?\" at {i} in source `{source}`");
+            }
+            while i < chars.len() {
+                match chars[i] {
+                    '\\' => {
+                        pattern.push('\\');
+                        if let Some(&escaped) = chars.get(i + 1) {
+                            pattern.push(escaped);
+                        }
+                        i += 2;
+                        continue;
+                    }
+                    ')' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            i += 1;
+                            break;
+                        }
+                    }
+                    '(' => {
+                        depth += 1;
+                        if chars.get(i + 1) != Some(&'?') {
+                            bail!("capturing groups are not allowed at {i} in source `{source}`");
+                        }
+                    }
+                    _ => {}
+                }
+                pattern.push(chars[i]);
+                i += 1;
+            }
+            if depth != 0 {
+                bail!("unbalanced pattern at {start} in source `{source}`");
+            }
+            if pattern.is_empty() {
+                bail!("missing pattern at {start} in source `{source}`");
+            }
+        }
+
+        if name.is_empty() {
+            name = unnamed_key.to_string();
+            unnamed_key += 1;
+        }
+
+        let modifier = match chars.get(i) {
+            Some(&m @ ('?' | '*' | '+')) => {
+                i += 1;
+                Some(m)
+            }
+            _ => None,
+        };
+
+        let prefix = match literal.pop() {
+            Some(p @ ('/' | '.')) => p.to_string(),
+            Some(other) => {
+                literal.push(other);
+                String::new()
+            }
+            None => String::new(),
+        };
+        if !literal.is_empty() {
+            tokens.push(Token::Literal(std::mem::take(&mut literal)));
+        }
+
+        tokens.push(Token::Param {
+            name,
+            prefix,
+            pattern: if pattern.is_empty() {
+                DEFAULT_PATTERN.to_string()
+            } else {
+                pattern
+            },
+            modifier,
+        });
+    }
+
+    if !literal.is_empty() {
+        tokens.push(Token::Literal(literal));
+    }
+
+    Ok(tokens)
+}
+
+/// Converts a `path-to-regexp` source into a JavaScript compatible regular
+/// expression (as written to the routes manifest) and the names of its
+/// capture groups, in order.
+pub fn source_to_regex(source: &str) -> Result<(String, Vec<String>)> {
+    let mut route = "^".to_string();
+    let mut keys = Vec::new();
+
+    for token in parse_source(source)? {
+        match token {
+            Token::Literal(literal) => route.push_str(&escape_string(&literal)),
+            Token::Param {
+                name,
+                prefix,
+                pattern,
+                modifier,
+            } => {
+                let prefix = escape_string(&prefix);
+                let modifier_str = modifier.map(String::from).unwrap_or_default();
+                route.push_str(&match (prefix.is_empty(), modifier) {
+                    (false, Some(m @ ('*' | '+'))) => format!(
+                        "(?:{prefix}((?:{pattern})(?:{prefix}(?:{pattern}))*)){}",
+                        if m == '*' { "?" } else { "" }
+                    ),
+                    (false, _) => format!("(?:{prefix}({pattern})){modifier_str}"),
+                    (true, Some('*' | '+')) => format!("((?:{pattern}){modifier_str})"),
+                    (true, _) => format!("({pattern}){modifier_str}"),
+                });
+                keys.push(name);
+            }
+        }
+    }
+
+    route.push('$');
+    Ok((route, keys))
+}
+
+/// Strips every character which isn't an ASCII letter so that `has` keys can
+/// be used as params.
+fn safe_param_name(name: &str) -> String {
+    name.chars().filter(|c| c.is_ascii_alphabetic()).collect()
+}
+
+fn match_has_item(item: &RouteHas, request: &RouteRequest, params: &mut RouteParams) -> bool {
+    let (key, expected, value) = match item {
+        RouteHas::Header { key, value } => (
+            key.as_str(),
+            value.as_deref(),
+            request.header(key).map(ToString::to_string),
+        ),
+        RouteHas::Cookie { key, value } => (
+            key.as_str(),
+            value.as_deref(),
+            request.cookie(key).map(ToString::to_string),
+        ),
+        RouteHas::Query { key, value } => (key.as_str(), value.as_deref(), request.query(key)),
+        RouteHas::Host { value } => ("host", Some(value.as_str()), request.hostname()),
+    };
+
+    let Some(value) = value else {
+        return false;
+    };
+
+    let Some(expected) = expected else {
+        params.insert(safe_param_name(key), value);
+        return true;
+    };
+
+    let Ok(regex) = Regex::new(&format!("^(?:{expected})$")) else {
+        return false;
+    };
+    let Some(captures) = regex.captures(&value) else {
+        return false;
+    };
+
+    if regex.capture_names().flatten().next().is_some() {
+        add_named_captures(&regex, &captures, params);
+    } else if matches!(item, RouteHas::Host { .. }) {
+        params.insert("host".to_string(), captures[0].to_string());
+    }
+    true
+}
+
+fn add_named_captures(regex: &Regex, captures: &regex::Captures, params: &mut RouteParams) {
+    for name in regex.capture_names().flatten() {
+        if let Some(value) = captures.name(name) {
+            params.insert(name.to_string(), value.as_str().to_string());
+        }
+    }
+}
+
+/// Checks the `has` and `missing` conditions of a custom route, adding the
+/// captured values to `params`.
+pub fn match_has(
+    has: Option<&[RouteHas]>,
+    missing: Option<&[RouteHas]>,
+    request: &RouteRequest,
+    params: &mut RouteParams,
+) -> bool {
+    let mut has_params = RouteParams::new();
+    let all_match = has
+        .unwrap_or_default()
+        .iter()
+        .all(|item| match_has_item(item, request, &mut has_params))
+        && !missing
+            .unwrap_or_default()
+            .iter()
+            .any(|item| match_has_item(item, request, &mut RouteParams::new()));
+
+    if all_match {
+        params.extend(has_params);
+    }
+    all_match
+}
+
+static PARAM_REFERENCE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r":([A-Za-z0-9_]+)\*?").unwrap());
+
+/// Substitutes `:param` references in a header key/value or a destination
+/// with their matched values. Unknown params are left untouched.
+pub fn compile_non_path(value: &str, params: &RouteParams) -> String {
+    if !value.contains(':') {
+        return value.to_string();
+    }
+    PARAM_REFERENCE_REGEX
+        .replace_all(value, |captures: &regex::Captures| {
+            match params.get(&captures[1]) {
+                Some(param) => param.clone(),
+                None => captures[0].to_string(),
+            }
+        })
+        .into_owned()
+}
+
+/// A `headers` entry from next.config.js with its compiled source.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, TraceRawVcs)]
+pub struct HeaderRoute {
+    matcher: SourceMatcher,
+    headers: Vec<HeaderValue>,
+    has: Option<Vec<RouteHas>>,
+    missing: Option<Vec<RouteHas>>,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct HeaderRoutes(Vec<HeaderRoute>);
+
+/// Compiles the sources of the `headers` from next.config.js. Entries with a
+/// source that can't be matched are reported and skipped.
+#[turbo_tasks::function]
+pub async fn header_routes(
+    headers: Vc<Headers>,
+    project_path: Vc<FileSystemPath>,
+) -> Result<Vc<HeaderRoutes>> {
+    Ok(Vc::cell(
+        headers
+            .await?
+            .iter()
+            .filter_map(|header| {
+                Some(HeaderRoute {
+                    matcher: compile_source(&header.source, "header", project_path)?,
+                    headers: header.headers.clone(),
+                    has: header.has.clone(),
+                    missing: header.missing.clone(),
+                })
+            })
+            .collect(),
+    ))
+}
+
+/// Returns the response headers of all `headers` entries matching the
+/// request, in config order.
+pub fn matched_headers(routes: &[HeaderRoute], request: &RouteRequest) -> Vec<(String, String)> {
+    let mut matched = Vec::new();
+    for route in routes {
+        let Some(mut params) = route.matcher.params(request.pathname) else {
+            continue;
+        };
+        if !match_has(
+            route.has.as_deref(),
+            route.missing.as_deref(),
+            request,
+            &mut params,
+        ) {
+            continue;
+        }
+        for HeaderValue { key, value } in &route.headers {
+            matched.push((
+                compile_non_path(key, &params),
+                compile_non_path(value, &params),
+            ));
+        }
+    }
+    matched
+}
+
+/// Sets `custom` headers on top of `headers`, replacing existing values with
+/// the same (case-insensitive) name.
+pub fn merge_headers(headers: &mut Vec<(String, String)>, custom: Vec<(String, String)>) {
+    for (key, value) in custom {
+        headers.retain(|(existing, _)| !existing.eq_ignore_ascii_case(&key));
+        headers.push((key, value));
+    }
+}
+
+/// Substitutes the params into a redirect destination and merges the query of
+/// the request into the one of the destination.
+pub fn prepare_destination(destination: &str, params: &RouteParams, raw_query: &str) -> String {
+    let (destination, hash) = match destination.split_once('#') {
+        Some((destination, hash)) => (destination, Some(hash)),
+        None => (destination, None),
+    };
+    let (pathname, destination_query) = destination.split_once('?').unwrap_or((destination, ""));
+
+    let mut query: IndexMap<String, String> = qstring::QString::from(raw_query)
+        .into_pairs()
+        .into_iter()
+        .collect();
+    for (key, value) in qstring::QString::from(destination_query).into_pairs() {
+        query.insert(key, compile_non_path(&value, params));
+    }
+
+    let mut location = compile_non_path(pathname, params);
+    if !query.is_empty() {
+        location.push('?');
+        location.push_str(&qstring::QString::new(query.into_iter().collect()).to_string());
+    }
+    if let Some(hash) = hash {
+        location.push('#');
+        location.push_str(&compile_non_path(hash, params));
+    }
+    location
+}
+
+/// A `redirects` entry from next.config.js with its compiled source.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, TraceRawVcs)]
+pub struct RedirectRoute {
+    matcher: SourceMatcher,
+    destination: String,
+    status_code: u16,
+    has: Option<Vec<RouteHas>>,
+    missing: Option<Vec<RouteHas>>,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct RedirectRoutes(Vec<RedirectRoute>);
+
+/// Compiles the sources of the `redirects` from next.config.js. Entries with
+/// a source that can't be matched are reported and skipped.
+///
+/// The redirects next.js adds itself for `trailingSlash` are skipped too, as
+/// the Next.js router applies them.
+#[turbo_tasks::function]
+pub async fn redirect_routes(
+    redirects: Vc<Redirects>,
+    project_path: Vc<FileSystemPath>,
+) -> Result<Vc<RedirectRoutes>> {
+    Ok(Vc::cell(
+        redirects
+            .await?
+            .iter()
+            .filter(|redirect| redirect.internal != Some(true))
+            .filter_map(|redirect| {
+                Some(RedirectRoute {
+                    matcher: compile_source(&redirect.source, "redirect", project_path)?,
+                    destination: redirect.destination.clone(),
+                    status_code: redirect.status_code(),
+                    has: redirect.has.clone(),
+                    missing: redirect.missing.clone(),
+                })
+            })
+            .collect(),
+    ))
+}
+
+/// A redirect response for a request.
+#[derive(Debug, PartialEq, Eq)]
+pub struct MatchedRedirect {
+    pub status_code: u16,
+    pub location: String,
+}
+
+impl MatchedRedirect {
+    /// The headers to send with the redirect response.
+    pub fn headers(&self) -> Vec<(String, String)> {
+        let mut headers = vec![("Location".to_string(), self.location.clone())];
+        // Legacy user agents don't follow 308s, so we add a refresh header for
+        // them.
+        if self.status_code == 308 {
+            headers.push(("Refresh".to_string(), format!("0;url={}", self.location)));
+        }
+        headers
+    }
+}
+
+/// Returns the first `redirects` entry matching the request.
+pub fn matched_redirect(
+    routes: &[RedirectRoute],
+    request: &RouteRequest,
+) -> Option<MatchedRedirect> {
+    routes.iter().find_map(|route| {
+        let mut params = route.matcher.params(request.pathname)?;
+        if !match_has(
+            route.has.as_deref(),
+            route.missing.as_deref(),
+            request,
+            &mut params,
+        ) {
+            return None;
+        }
+        Some(MatchedRedirect {
+            status_code: route.status_code,
+            location: prepare_destination(&route.destination, &params, request.raw_query),
+        })
+    })
+}
+
+/// An invalid `headers`, `redirects` or `rewrites` entry in next.config.js.
+#[turbo_tasks::value(shared)]
+pub struct InvalidCustomRouteIssue {
+    pub path: Vc<FileSystemPath>,
+    /// The kind of custom route, e.g. `redirect`.
+    pub route_type: String,
+    pub source: String,
+    pub message: String,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for InvalidCustomRouteIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("config".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell(format!("Invalid {} found", self.route_type))
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "{} for `{}`. The {} will be ignored.",
+            self.message, self.source, self.route_type
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        compile_non_path, match_has, prepare_destination, source_to_regex, RouteParams,
+        RouteRequest, SourceMatcher,
+    };
+    use crate::next_config::RouteHas;
+
+    #[test]
+    fn converts_sources_to_js_regexes() {
+        assert_eq!(
+            source_to_regex("/blog/:slug").unwrap(),
+            (
+                r"^\/blog(?:\/([^\/#\?]+?))$".to_string(),
+                vec!["slug".to_string()]
+            )
+        );
+        assert_eq!(
+            source_to_regex("/:path*").unwrap().0,
+            r"^(?:\/((?:[^\/#\?]+?)(?:\/(?:[^\/#\?]+?))*))?$"
+        );
+        assert_eq!(
+            source_to_regex("/docs/(.*)").unwrap(),
+            (r"^\/docs(?:\/(.*))$".to_string(), vec!["0".to_string()])
+        );
+        assert!(source_to_regex("/:slug((a))").is_err());
+        assert!(source_to_regex("/{foo}").is_err());
+    }
+
+    #[test]
+    fn matches_params() {
+        let matcher = SourceMatcher::new("/blog/:slug/:rest*").unwrap();
+        let params = matcher.params("/Blog/hello/a/b").unwrap();
+        assert_eq!(params["slug"], "hello");
+        assert_eq!(params["rest"], "a/b");
+        assert!(matcher.params("/blog").is_none());
+
+        let matcher = SourceMatcher::new(r"/post/:id(\d{1,})").unwrap();
+        assert_eq!(matcher.params("/post/42").unwrap()["id"], "42");
+        assert!(matcher.params("/post/abc").is_none());
+    }
+
+    #[test]
+    fn matches_has_and_missing() {
+        let raw_headers = vec![
+            ("Host".to_string(), "en.example.com:3000".to_string()),
+            ("x-flag".to_string(), "on".to_string()),
+            ("cookie".to_string(), "a=1; session=abc".to_string()),
+        ];
+        let request = RouteRequest {
+            pathname: "/",
+            raw_query: "page=home",
+            raw_headers: &raw_headers,
+        };
+
+        let mut params = RouteParams::new();
+        assert!(match_has(
+            Some(&[
+                RouteHas::Header {
+                    key: "X-Flag".to_string(),
+                    value: None,
+                },
+                RouteHas::Cookie {
+                    key: "session".to_string(),
+                    value: Some("(?<session>.*)".to_string()),
+                },
+                RouteHas::Host {
+                    value: "(?<lang>[a-z]+)\\.example\\.com".to_string(),
+                },
+            ]),
+            Some(&[RouteHas::Query {
+                key: "preview".to_string(),
+                value: None,
+            }]),
+            &request,
+            &mut params,
+        ));
+        assert_eq!(params["XFlag"], "on");
+        assert_eq!(params["session"], "abc");
+        assert_eq!(params["lang"], "en");
+
+        let mut params = RouteParams::new();
+        assert!(!match_has(
+            None,
+            Some(&[RouteHas::Query {
+                key: "page".to_string(),
+                value: Some("home".to_string()),
+            }]),
+            &request,
+            &mut params,
+        ));
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn compiles_param_references() {
+        let params = RouteParams::from([
+            ("slug".to_string(), "hello".to_string()),
+            ("path".to_string(), "a/b".to_string()),
+        ]);
+        assert_eq!(
+            compile_non_path("/posts/:slug?from=:path*&keep=:other", &params),
+            "/posts/hello?from=a/b&keep=:other"
+        );
+        assert_eq!(compile_non_path("max-age=60", &params), "max-age=60");
+    }
+
+    #[test]
+    fn prepares_redirect_destinations() {
+        let params = RouteParams::from([
+            ("slug".to_string(), "hello".to_string()),
+            ("ref".to_string(), "mail".to_string()),
+        ]);
+        assert_eq!(
+            prepare_destination("/news/:slug?from=:ref#top", &params, "utm=1&from=x"),
+            "/news/hello?utm=1&from=mail#top"
+        );
+        assert_eq!(
+            prepare_destination("https://example.com:8080/:slug", &params, ""),
+            "https://example.com:8080/hello"
+        );
+    }
+
+    #[test]
+    fn converts_trailing_slash_redirect_sources() {
+        // The redirects next.js/packages/next/src/lib/load-custom-routes.ts
+        // adds for `trailingSlash` use lookaheads, so they can't be matched
+        // here. They are still written to the routes manifest.
+        let source = r"/:file((?!\.well-known(?:/.*)?)(?:[^/]+/)*[^/]+\.\w+)/";
+        assert_eq!(
+            source_to_regex(source).unwrap(),
+            (
+                r"^(?:\/((?!\.well-known(?:/.*)?)(?:[^/]+/)*[^/]+\.\w+))\/$".to_string(),
+                vec!["file".to_string()]
+            )
+        );
+        assert!(SourceMatcher::new(source).is_err());
+    }
+}
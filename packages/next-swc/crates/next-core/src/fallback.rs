@@ -51,6 +51,8 @@ pub async fn get_fallback_page(
     let chunking_context = get_client_chunking_context(
         project_path,
         dev_server_root,
+        next_config.computed_asset_prefix(),
+        next_config.production_browser_source_maps(),
         client_compile_time_info.environment(),
         mode,
     );
@@ -0,0 +1,337 @@
+//! Locale detection for the `i18n` config from next.config.js, following
+//! next.js/packages/next/src/shared/lib/i18n and
+//! next.js/packages/next/src/server/accept-header.ts.
+
+use std::{cmp::Ordering, collections::HashSet};
+
+use indexmap::IndexMap;
+
+use crate::{
+    custom_routes::RouteRequest,
+    next_config::{DomainLocale, I18NConfig},
+};
+
+/// A pathname with its locale prefix removed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PathLocale<'a> {
+    pub detected_locale: Option<&'a str>,
+    pub pathname: String,
+}
+
+/// Removes the locale from the first segment of `pathname`, if it is one of
+/// `locales`. Locales are compared case-insensitively.
+pub fn normalize_locale_path<'a>(pathname: &str, locales: &'a [String]) -> PathLocale<'a> {
+    let mut parts = pathname.splitn(3, '/');
+    let root = parts.next().unwrap_or_default();
+    let detected_locale = parts.next().and_then(|segment| {
+        locales
+            .iter()
+            .find(|locale| !segment.is_empty() && locale.eq_ignore_ascii_case(segment))
+    });
+    let Some(detected_locale) = detected_locale else {
+        return PathLocale {
+            detected_locale: None,
+            pathname: pathname.to_string(),
+        };
+    };
+    let pathname = match parts.next() {
+        Some(rest) => format!("{root}/{rest}"),
+        None => root.to_string(),
+    };
+    PathLocale {
+        detected_locale: Some(detected_locale),
+        pathname: if pathname.is_empty() {
+            "/".to_string()
+        } else {
+            pathname
+        },
+    }
+}
+
+/// Finds the domain locale that either serves `hostname` or handles
+/// `detected_locale`.
+pub fn detect_domain_locale<'a>(
+    domains: &'a [DomainLocale],
+    hostname: Option<&str>,
+    detected_locale: Option<&str>,
+) -> Option<&'a DomainLocale> {
+    domains.iter().find(|item| {
+        // Remove the port if present.
+        let domain_hostname = item.domain.split(':').next().unwrap_or_default();
+        hostname.is_some_and(|hostname| hostname.eq_ignore_ascii_case(domain_hostname))
+            || detected_locale.is_some_and(|detected_locale| {
+                item.default_locale.eq_ignore_ascii_case(detected_locale)
+                    || item
+                        .locales
+                        .iter()
+                        .flatten()
+                        .any(|locale| locale.eq_ignore_ascii_case(detected_locale))
+            })
+    })
+}
+
+struct Selection {
+    token: String,
+    pos: usize,
+    pref: Option<usize>,
+    q: f64,
+}
+
+/// Picks the locale from `locales` that is preferred by an `Accept-Language`
+/// header. A language also matches the locales it is a prefix of, e.g. `en`
+/// matches `en-US`. Returns `None` for malformed headers.
+pub fn accept_language<'a>(header: &str, locales: &'a [String]) -> Option<&'a str> {
+    // Lowercased locales and their prefixes, mapped to the original locale and
+    // its priority.
+    let mut preferences = IndexMap::<String, (&str, usize)>::new();
+    let mut pos = 0;
+    for locale in locales {
+        let lower = locale.to_lowercase();
+        preferences.insert(lower.clone(), (locale, pos));
+        pos += 1;
+        let mut prefix = lower.as_str();
+        while let Some((head, _)) = prefix.rsplit_once('-') {
+            prefix = head;
+            if !preferences.contains_key(prefix) {
+                preferences.insert(prefix.to_string(), (locale, pos));
+                pos += 1;
+            }
+        }
+    }
+
+    let header = header.replace([' ', '\t'], "");
+    let mut tokens = HashSet::new();
+    let mut selections = Vec::new();
+    for (pos, part) in header.split(',').enumerate() {
+        if part.is_empty() {
+            continue;
+        }
+        let mut params = part.split(';');
+        let token = params.next().unwrap_or_default().to_lowercase();
+        let q = params.next();
+        if token.is_empty() || params.next().is_some() {
+            return None;
+        }
+        let mut selection = Selection {
+            pref: preferences.get(&token).map(|(_, pos)| *pos),
+            token,
+            pos,
+            q: 1.0,
+        };
+        tokens.insert(selection.token.clone());
+        if let Some(q) = q {
+            let mut q = q.split('=');
+            let key = q.next().unwrap_or_default();
+            let value = q.next().unwrap_or_default();
+            if value.is_empty() || (key != "q" && key != "Q") {
+                return None;
+            }
+            let score = value.parse::<f64>().unwrap_or(f64::NAN);
+            if score == 0.0 {
+                continue;
+            }
+            if (0.001..=1.0).contains(&score) {
+                selection.q = score;
+            }
+        }
+        selections.push(selection);
+    }
+
+    selections.sort_by(|a, b| {
+        b.q.partial_cmp(&a.q)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| match (a.pref, b.pref) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (None, None) => Ordering::Equal,
+            })
+            .then(a.pos.cmp(&b.pos))
+    });
+
+    selections.iter().find_map(|selection| {
+        if selection.token == "*" {
+            preferences
+                .iter()
+                .find(|(preference, _)| !tokens.contains(*preference))
+                .map(|(_, (locale, _))| *locale)
+        } else {
+            preferences.get(&selection.token).map(|(locale, _)| *locale)
+        }
+    })
+}
+
+/// Returns where a request for the root page should be redirected to, based
+/// on the domain locales, the `NEXT_LOCALE` cookie and the `Accept-Language`
+/// header. Mirrors `getLocaleRedirect` from
+/// next.js/packages/next/src/shared/lib/i18n/get-locale-redirect.ts.
+pub fn locale_redirect(
+    i18n: &I18NConfig,
+    base_path: &str,
+    request: &RouteRequest,
+) -> Option<String> {
+    if i18n.locale_detection == Some(false) {
+        return None;
+    }
+    let pathname = request
+        .pathname
+        .strip_prefix(base_path)
+        .filter(|pathname| pathname.is_empty() || pathname.starts_with('/'))?;
+    if !matches!(pathname, "" | "/" | "/index") {
+        return None;
+    }
+
+    let domains = i18n.domains.as_deref().unwrap_or_default();
+    let hostname = request.hostname();
+    let domain_locale = detect_domain_locale(domains, hostname.as_deref(), None);
+    let default_locale = domain_locale.map_or(&i18n.default_locale, |domain_locale| {
+        &domain_locale.default_locale
+    });
+
+    let preferred_locale = request
+        .header("accept-language")
+        .and_then(|header| accept_language(header, &i18n.locales));
+    let cookie_locale = request.cookie("NEXT_LOCALE").and_then(|cookie| {
+        i18n.locales
+            .iter()
+            .find(|locale| locale.eq_ignore_ascii_case(cookie))
+    });
+    let detected_locale = domain_locale
+        .map(|domain_locale| domain_locale.default_locale.as_str())
+        .or(cookie_locale.map(String::as_str))
+        .or(preferred_locale)
+        .unwrap_or(&i18n.default_locale);
+
+    if let (Some(domain_locale), Some(preferred_locale)) = (domain_locale, preferred_locale) {
+        if let Some(preferred_domain) = detect_domain_locale(domains, None, Some(preferred_locale))
+        {
+            let is_preferred_domain = preferred_domain.domain == domain_locale.domain;
+            let is_preferred_locale = preferred_domain.default_locale == preferred_locale;
+            if !is_preferred_domain || !is_preferred_locale {
+                let scheme = if preferred_domain.http == Some(true) {
+                    "http"
+                } else {
+                    "https"
+                };
+                let locale = if is_preferred_locale {
+                    ""
+                } else {
+                    preferred_locale
+                };
+                return Some(format!("{scheme}://{}/{locale}", preferred_domain.domain));
+            }
+        }
+    }
+
+    if detected_locale.eq_ignore_ascii_case(default_locale) {
+        return None;
+    }
+    Some(if request.raw_query.is_empty() {
+        format!("{base_path}/{detected_locale}")
+    } else {
+        format!("{base_path}/{detected_locale}?{}", request.raw_query)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{accept_language, locale_redirect, normalize_locale_path, PathLocale};
+    use crate::{
+        custom_routes::RouteRequest,
+        next_config::{DomainLocale, I18NConfig},
+    };
+
+    fn locales() -> Vec<String> {
+        ["en-US", "fr", "nl-NL"].map(ToString::to_string).to_vec()
+    }
+
+    #[test]
+    fn normalizes_locale_paths() {
+        let locales = locales();
+        assert_eq!(
+            normalize_locale_path("/fr/about", &locales),
+            PathLocale {
+                detected_locale: Some("fr"),
+                pathname: "/about".to_string()
+            }
+        );
+        assert_eq!(
+            normalize_locale_path("/EN-us", &locales),
+            PathLocale {
+                detected_locale: Some("en-US"),
+                pathname: "/".to_string()
+            }
+        );
+        assert_eq!(
+            normalize_locale_path("/de/about", &locales),
+            PathLocale {
+                detected_locale: None,
+                pathname: "/de/about".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn negotiates_accept_language() {
+        let locales = locales();
+        assert_eq!(accept_language("fr;q=0.8, nl-NL", &locales), Some("nl-NL"));
+        assert_eq!(accept_language("en", &locales), Some("en-US"));
+        assert_eq!(accept_language("de, *;q=0.5", &locales), Some("en-US"));
+        assert_eq!(accept_language("de", &locales), None);
+        assert_eq!(accept_language("fr;q", &locales), None);
+    }
+
+    #[test]
+    fn redirects_root_to_detected_locale() {
+        let i18n = I18NConfig {
+            default_locale: "en-US".to_string(),
+            domains: Some(vec![DomainLocale {
+                default_locale: "nl-NL".to_string(),
+                domain: "example.nl".to_string(),
+                http: None,
+                locales: None,
+            }]),
+            locale_detection: None,
+            locales: locales(),
+        };
+        let redirect = |pathname: &str, headers: &[(&str, &str)]| {
+            let raw_headers = headers
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect::<Vec<_>>();
+            locale_redirect(
+                &i18n,
+                "",
+                &RouteRequest {
+                    pathname,
+                    raw_query: "",
+                    raw_headers: &raw_headers,
+                },
+            )
+        };
+
+        assert_eq!(
+            redirect("/", &[("accept-language", "fr")]),
+            Some("/fr".to_string())
+        );
+        assert_eq!(
+            redirect(
+                "/",
+                &[("accept-language", "fr"), ("cookie", "NEXT_LOCALE=en-us")]
+            ),
+            None
+        );
+        assert_eq!(redirect("/about", &[("accept-language", "fr")]), None);
+        assert_eq!(
+            redirect(
+                "/",
+                &[("host", "example.com"), ("accept-language", "nl-NL")]
+            ),
+            Some("/nl-NL".to_string())
+        );
+        assert_eq!(
+            redirect("/", &[("host", "example.nl"), ("accept-language", "fr")]),
+            None
+        );
+    }
+}
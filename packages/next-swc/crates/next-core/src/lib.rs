@@ -12,11 +12,13 @@ mod app_source;
 pub mod app_structure;
 mod babel;
 mod bootstrap;
+pub mod custom_routes;
 pub mod dev_manifest;
 mod embed_js;
 mod emit;
 pub mod env;
 mod fallback;
+pub mod i18n;
 pub mod loader_tree;
 pub mod middleware;
 pub mod mode;
@@ -36,6 +38,7 @@ pub mod next_manifests;
 pub mod next_pages;
 mod next_route_matcher;
 pub mod next_server;
+pub mod next_server_actions;
 pub mod next_server_component;
 pub mod next_shared;
 pub mod next_telemetry;
@@ -53,7 +56,8 @@ pub mod util;
 mod web_entry_source;
 
 pub use app_segment_config::{
-    parse_segment_config_from_loader_tree, parse_segment_config_from_source,
+    parse_segment_config_from_loader_tree, parse_segment_config_from_source, NextRevalidate,
+    NextSegmentConfig, NextSegmentDynamic,
 };
 pub use app_source::create_app_source;
 pub use emit::{all_assets_from_entries, all_server_paths, emit_all_assets, emit_assets};
@@ -27,6 +27,13 @@ use crate::{
     next_image::module::{BlurPlaceholderMode, StructuredImageModuleType},
 };
 
+/// The inner asset of the custom `global-error` component, if the app
+/// directory has one.
+pub const GLOBAL_ERROR: &str = "GLOBAL_ERROR_MODULE";
+
+/// The error boundary used when there is no custom `global-error` component.
+pub const DEFAULT_GLOBAL_ERROR: &str = "next/dist/client/components/error-boundary";
+
 pub struct LoaderTreeBuilder {
     inner_assets: IndexMap<String, Vc<Box<dyn Module>>>,
     counter: usize,
@@ -145,26 +152,29 @@ impl LoaderTreeBuilder {
                 }
             }
 
-            let source = Vc::upcast(FileSource::new(component));
-            let reference_ty = Value::new(ReferenceType::EcmaScriptModules(
-                EcmaScriptModulesReferenceSubType::Undefined,
-            ));
-
-            let module = match &self.server_component_transition {
-                ServerComponentTransition::Transition(transition) => {
-                    transition.process(source, self.context, reference_ty)
-                }
-                ServerComponentTransition::TransitionName(transition_name) => self
-                    .context
-                    .with_transition(transition_name.clone())
-                    .process(source, reference_ty),
-            };
-
+            let module = self.process_component(component);
             self.inner_assets.insert(format!("COMPONENT_{i}"), module);
         }
         Ok(())
     }
 
+    fn process_component(&self, component: Vc<FileSystemPath>) -> Vc<Box<dyn Module>> {
+        let source = Vc::upcast(FileSource::new(component));
+        let reference_ty = Value::new(ReferenceType::EcmaScriptModules(
+            EcmaScriptModulesReferenceSubType::Undefined,
+        ));
+
+        match &self.server_component_transition {
+            ServerComponentTransition::Transition(transition) => {
+                transition.process(source, self.context, reference_ty)
+            }
+            ServerComponentTransition::TransitionName(transition_name) => self
+                .context
+                .with_transition(transition_name.clone())
+                .process(source, reference_ty),
+        }
+    }
+
     async fn write_metadata(
         &mut self,
         app_page: &AppPage,
@@ -387,6 +397,7 @@ impl LoaderTreeBuilder {
             not_found,
             metadata,
             route: _,
+            global_error: _,
         } = &*components.await?;
         self.write_component(ComponentType::Page, *page).await?;
         self.write_component(ComponentType::DefaultPage, *default)
@@ -405,8 +416,21 @@ impl LoaderTreeBuilder {
         Ok(())
     }
 
+    /// Adds the custom global error component of the root segment as the
+    /// [`GLOBAL_ERROR`] inner asset.
+    async fn write_global_error(&mut self, loader_tree: Vc<LoaderTree>) -> Result<()> {
+        let Some(global_error) = loader_tree.await?.components.await?.global_error else {
+            return Ok(());
+        };
+
+        let module = self.process_component(global_error);
+        self.inner_assets.insert(GLOBAL_ERROR.to_string(), module);
+        Ok(())
+    }
+
     async fn build(mut self, loader_tree: Vc<LoaderTree>) -> Result<LoaderTreeModule> {
         self.walk_tree(loader_tree).await?;
+        self.write_global_error(loader_tree).await?;
         Ok(LoaderTreeModule {
             imports: self.imports,
             loader_tree_code: self.loader_tree_code,
@@ -434,4 +458,14 @@ impl LoaderTreeModule {
             .build(loader_tree)
             .await
     }
+
+    /// The request of the global error component, which resolves to an inner
+    /// asset for a custom `global-error` component.
+    pub fn global_error_request(&self) -> &'static str {
+        if self.inner_assets.contains_key(GLOBAL_ERROR) {
+            GLOBAL_ERROR
+        } else {
+            DEFAULT_GLOBAL_ERROR
+        }
+    }
 }
@@ -1,5 +1,7 @@
 use turbo_tasks::Vc;
-use turbopack_binding::turbopack::ecmascript::chunk::EcmascriptChunkPlaceable;
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPath, turbopack::ecmascript::chunk::EcmascriptChunkPlaceable,
+};
 
 use crate::app_segment_config::NextSegmentConfig;
 
@@ -15,4 +17,7 @@ pub struct AppEntry {
     pub rsc_entry: Vc<Box<dyn EcmascriptChunkPlaceable>>,
     /// The source code config for this entry.
     pub config: Vc<NextSegmentConfig>,
+    /// The page or route file of this entry, which issues about it are
+    /// reported for.
+    pub source_path: Vc<FileSystemPath>,
 }
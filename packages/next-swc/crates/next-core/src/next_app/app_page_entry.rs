@@ -1,13 +1,14 @@
 use std::io::Write;
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
+use indexmap::indexmap;
 use turbo_tasks::{TryJoinIterExt, Value, ValueToString, Vc};
 use turbopack_binding::{
     turbo::tasks_fs::{rope::RopeBuilder, File, FileSystemPath},
     turbopack::{
         core::{
-            asset::AssetContent, context::AssetContext, reference_type::ReferenceType,
-            virtual_source::VirtualSource,
+            asset::AssetContent, context::AssetContext, module::Module,
+            reference_type::ReferenceType, virtual_source::VirtualSource,
         },
         ecmascript::{chunk::EcmascriptChunkPlaceable, utils::StringifyJs},
         turbopack::ModuleAssetContext,
@@ -20,6 +21,7 @@ use crate::{
     loader_tree::{LoaderTreeModule, ServerComponentTransition},
     mode::NextMode,
     next_app::{AppPage, AppPath},
+    next_edge::entry::wrap_edge_entry,
     next_server_component::NextServerComponentTransition,
     parse_segment_config_from_loader_tree,
     util::{load_next_js_template, virtual_next_js_template_path, NextRuntime},
@@ -33,8 +35,12 @@ pub async fn get_app_page_entry(
     loader_tree: Vc<LoaderTree>,
     page: AppPage,
     project_root: Vc<FileSystemPath>,
+    build_id: Vc<String>,
+    next_config_json: Vc<String>,
+    mode: NextMode,
 ) -> Result<Vc<AppEntry>> {
     let config = parse_segment_config_from_loader_tree(loader_tree, Vc::upcast(nodejs_context));
+    let source_path = get_page_path(loader_tree).await?;
     let is_edge = matches!(config.await?.runtime, Some(NextRuntime::Edge));
     let context = if is_edge {
         edge_context
@@ -52,6 +58,8 @@ pub async fn get_app_page_entry(
     )
     .await?;
 
+    let global_error = loader_tree.global_error_request();
+
     let LoaderTreeModule {
         inner_assets,
         imports,
@@ -89,10 +97,9 @@ pub async fn get_app_page_entry(
             "\"VAR_ORIGINAL_PATHNAME\"",
             &StringifyJs(&original_name).to_string(),
         )
-        // TODO(alexkirsz) Support custom global error.
         .replace(
             "\"VAR_MODULE_GLOBAL_ERROR\"",
-            &StringifyJs("next/dist/client/components/error-boundary").to_string(),
+            &StringifyJs(global_error).to_string(),
         )
         .replace(
             "// INJECT:tree",
@@ -124,13 +131,21 @@ pub async fn get_app_page_entry(
 
     let source = VirtualSource::new(template_path, AssetContent::file(file.into()));
 
-    let rsc_entry = context.process(
+    let mut rsc_entry = context.process(
         Vc::upcast(source),
         Value::new(ReferenceType::Internal(Vc::cell(inner_assets))),
     );
 
     if is_edge {
-        todo!("edge pages are not supported yet")
+        rsc_entry = wrap_edge_page(
+            context,
+            project_root,
+            rsc_entry,
+            page,
+            build_id,
+            next_config_json,
+            mode,
+        );
     }
 
     let Some(rsc_entry) =
@@ -144,6 +159,85 @@ pub async fn get_app_page_entry(
         original_name,
         rsc_entry,
         config,
+        source_path,
     }
     .cell())
 }
+
+/// Finds the page file rendered for a loader tree by following its
+/// `children` parallel routes.
+async fn get_page_path(loader_tree: Vc<LoaderTree>) -> Result<Vc<FileSystemPath>> {
+    let mut loader_tree = loader_tree.await?;
+    loop {
+        if let Some(page) = loader_tree.components.await?.page {
+            return Ok(page);
+        }
+        let children = loader_tree
+            .parallel_routes
+            .get("children")
+            .with_context(|| format!("app page {} has no page file", loader_tree.page))?;
+        loader_tree = children.await?;
+    }
+}
+
+/// Wraps an app page module into the edge SSR handler and registers it as an
+/// edge function entry.
+#[turbo_tasks::function]
+async fn wrap_edge_page(
+    context: Vc<ModuleAssetContext>,
+    project_root: Vc<FileSystemPath>,
+    entry: Vc<Box<dyn Module>>,
+    page: AppPage,
+    build_id: Vc<String>,
+    next_config_json: Vc<String>,
+    mode: NextMode,
+) -> Result<Vc<Box<dyn Module>>> {
+    let template_file = "build/templates/edge-ssr-app.js";
+
+    // Load the file from the next.js codebase.
+    let file = load_next_js_template(project_root, template_file.to_string()).await?;
+
+    let page_name = page.to_string();
+    let dev = !matches!(mode, NextMode::Build);
+
+    let mut file = file
+        .to_str()?
+        .replace(
+            "\"VAR_DEFINITION_PAGE\"",
+            &StringifyJs(&page_name).to_string(),
+        )
+        .replace(
+            "\"VAR_BUILD_ID\"",
+            &StringifyJs(&*build_id.await?).to_string(),
+        )
+        .replace(
+            "// INJECT:nextConfig",
+            format!("const nextConfig = {};", next_config_json.await?).as_str(),
+        )
+        .replace("// INJECT:dev", format!("const dev = {dev};").as_str());
+
+    // Ensure that the last line is a newline.
+    if !file.ends_with('\n') {
+        file.push('\n');
+    }
+
+    let template_path = virtual_next_js_template_path(project_root, template_file.to_string());
+
+    let source = VirtualSource::new(template_path, AssetContent::file(File::from(file).into()));
+
+    let inner_assets = indexmap! {
+        "VAR_USERLAND".to_string() => entry
+    };
+
+    let wrapped = context.process(
+        Vc::upcast(source),
+        Value::new(ReferenceType::Internal(Vc::cell(inner_assets))),
+    );
+
+    Ok(wrap_edge_entry(
+        Vc::upcast(context),
+        project_root,
+        wrapped,
+        AppPath::from(page).to_string(),
+    ))
+}
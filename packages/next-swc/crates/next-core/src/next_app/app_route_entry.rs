@@ -22,6 +22,7 @@ use turbopack_binding::{
 
 use crate::{
     next_app::{AppEntry, AppPage, AppPath},
+    next_edge::entry::edge_entry_global_var_name,
     parse_segment_config_from_source,
     util::{load_next_js_template, virtual_next_js_template_path, NextRuntime},
 };
@@ -135,6 +136,7 @@ pub async fn get_app_route_entry(
         original_name,
         rsc_entry,
         config,
+        source_path: path,
     }
     .cell())
 }
@@ -159,7 +161,7 @@ pub async fn wrap_edge_entry(
                 default: EdgeRouteModuleWrapper.wrap(module.routeModule),
             }}
         "#,
-        StringifyJs(&format_args!("middleware_{}", pathname))
+        StringifyJs(&edge_entry_global_var_name(&pathname))
     )?;
     let file = File::from(source.build());
     // TODO(alexkirsz) Figure out how to name this virtual asset.
@@ -47,12 +47,12 @@ use crate::{
     },
     next_shared::{
         resolve::{
-            ModuleFeatureReportResolvePlugin, NextSharedRuntimeResolvePlugin,
-            UnsupportedModulesResolvePlugin,
+            ModuleFeatureReportResolvePlugin, NextBarrelOptimizeResolvePlugin,
+            NextSharedRuntimeResolvePlugin, UnsupportedModulesResolvePlugin,
         },
         transforms::{
-            emotion::get_emotion_transform_plugin, get_relay_transform_plugin,
-            styled_components::get_styled_components_transform_plugin,
+            emotion::get_emotion_transform_plugin, get_react_remove_properties_transform_plugin,
+            get_relay_transform_plugin, styled_components::get_styled_components_transform_plugin,
             styled_jsx::get_styled_jsx_transform_plugin,
             swc_ecma_transform_plugins::get_swc_ecma_transform_plugin,
         },
@@ -65,27 +65,33 @@ use crate::{
     util::foreign_code_context_condition,
 };
 
-fn defines(mode: NextMode) -> CompileTimeDefines {
+fn defines(mode: NextMode, i18n_support: bool) -> CompileTimeDefines {
     compile_time_defines!(
         process.turbopack = true,
         process.env.NODE_ENV = mode.node_env(),
         process.env.__NEXT_CLIENT_ROUTER_FILTER_ENABLED = false,
         process.env.__NEXT_HAS_REWRITES = true,
-        process.env.__NEXT_I18N_SUPPORT = false,
+        process.env.__NEXT_I18N_SUPPORT = i18n_support,
     )
     // TODO(WEB-937) there are more defines needed, see
     // packages/next/src/build/webpack-config.ts
 }
 
 #[turbo_tasks::function]
-fn next_client_defines(mode: NextMode) -> Vc<CompileTimeDefines> {
-    defines(mode).cell()
+async fn next_client_defines(
+    mode: NextMode,
+    next_config: Vc<NextConfig>,
+) -> Result<Vc<CompileTimeDefines>> {
+    Ok(defines(mode, next_config.i18n().await?.is_some()).cell())
 }
 
 #[turbo_tasks::function]
-async fn next_client_free_vars(mode: NextMode) -> Result<Vc<FreeVarReferences>> {
+async fn next_client_free_vars(
+    mode: NextMode,
+    next_config: Vc<NextConfig>,
+) -> Result<Vc<FreeVarReferences>> {
     Ok(free_var_references!(
-        ..defines(mode).into_iter(),
+        ..defines(mode, next_config.i18n().await?.is_some()).into_iter(),
         Buffer = FreeVarReference::EcmaScriptModule {
             request: "node:buffer".to_string(),
             lookup_path: None,
@@ -104,6 +110,7 @@ async fn next_client_free_vars(mode: NextMode) -> Result<Vc<FreeVarReferences>>
 pub fn get_client_compile_time_info(
     mode: NextMode,
     browserslist_query: String,
+    next_config: Vc<NextConfig>,
 ) -> Vc<CompileTimeInfo> {
     CompileTimeInfo::builder(Environment::new(Value::new(ExecutionEnvironment::Browser(
         BrowserEnvironment {
@@ -114,8 +121,8 @@ pub fn get_client_compile_time_info(
         }
         .into(),
     ))))
-    .defines(next_client_defines(mode))
-    .free_var_references(next_client_free_vars(mode))
+    .defines(next_client_defines(mode, next_config))
+    .free_var_references(next_client_free_vars(mode, next_config))
     .cell()
 }
 
@@ -152,6 +159,7 @@ pub async fn get_client_resolve_options_context(
             Vc::upcast(ModuleFeatureReportResolvePlugin::new(project_path)),
             Vc::upcast(UnsupportedModulesResolvePlugin::new(project_path)),
             Vc::upcast(NextSharedRuntimeResolvePlugin::new(project_path)),
+            Vc::upcast(NextBarrelOptimizeResolvePlugin::new(project_path)),
         ],
         ..Default::default()
     };
@@ -211,13 +219,19 @@ pub async fn get_client_module_options_context(
         *get_swc_ecma_transform_plugin(project_path, next_config).await?,
         *get_relay_transform_plugin(next_config).await?,
         *get_emotion_transform_plugin(next_config).await?,
+        *get_react_remove_properties_transform_plugin(next_config).await?,
         *get_styled_components_transform_plugin(next_config).await?,
         *get_styled_jsx_transform_plugin().await?,
-        Some(Vc::cell(Box::new(ServerDirectiveTransformer::new(
-            // ServerDirective is not implemented yet and always reports an issue.
-            // We don't have to pass a valid transition name yet, but the API is prepared.
-            &Vc::cell("TODO".to_string()),
-        )) as _)),
+        // Server actions are only supported in the app dir, where they are
+        // handled by the Server Actions transform rule.
+        match ty.into_value() {
+            ClientContextType::App { .. } => None,
+            _ => Some(Vc::cell(Box::new(ServerDirectiveTransformer::new(
+                // ServerDirective is not implemented yet and always reports an issue.
+                // We don't have to pass a valid transition name yet, but the API is prepared.
+                &Vc::cell("TODO".to_string()),
+            )) as _)),
+        },
     ]
     .into_iter()
     .flatten()
@@ -279,13 +293,33 @@ pub async fn get_client_module_options_context(
     Ok(module_options_context)
 }
 
+/// Returns the base chunks are loaded from at runtime. An absolute
+/// `assetPrefix` URL is kept whole, so chunks loaded on demand come from its
+/// origin rather than the current one.
+fn chunk_base_path(asset_prefix: &str, mode: NextMode) -> Option<String> {
+    let prefix = asset_prefix.trim_end_matches('/');
+    let prefix = if prefix.contains("://") {
+        prefix
+    } else {
+        prefix.trim_start_matches('/')
+    };
+    match (mode, prefix.is_empty()) {
+        (NextMode::DevServer, true) => None,
+        (NextMode::DevServer, false) => Some(format!("{prefix}/")),
+        (NextMode::Development | NextMode::Build, true) => Some("_next/".to_string()),
+        (NextMode::Development | NextMode::Build, false) => Some(format!("{prefix}/_next/")),
+    }
+}
+
 #[turbo_tasks::function]
-pub fn get_client_chunking_context(
+pub async fn get_client_chunking_context(
     project_path: Vc<FileSystemPath>,
     client_root: Vc<FileSystemPath>,
+    asset_prefix: Vc<String>,
+    production_browser_source_maps: Vc<bool>,
     environment: Vc<Environment>,
     mode: NextMode,
-) -> Vc<Box<dyn EcmascriptChunkingContext>> {
+) -> Result<Vc<Box<dyn EcmascriptChunkingContext>>> {
     let output_root = match mode {
         NextMode::DevServer => client_root,
         NextMode::Development | NextMode::Build => client_root.join("_next".to_string()),
@@ -299,14 +333,19 @@ pub fn get_client_chunking_context(
     );
 
     let builder = match mode {
-        NextMode::DevServer => builder.hot_module_replacement(),
-        NextMode::Development => builder
-            .hot_module_replacement()
-            .chunk_base_path(Vc::cell(Some("_next/".to_string()))),
-        NextMode::Build => builder.chunk_base_path(Vc::cell(Some("_next/".to_string()))),
+        NextMode::DevServer | NextMode::Development => builder.hot_module_replacement(),
+        // Production chunks only link their source maps (which embed the original
+        // sources) when `productionBrowserSourceMaps` is enabled.
+        NextMode::Build => {
+            builder.reference_chunk_source_maps(*production_browser_source_maps.await?)
+        }
+    };
+    let builder = match chunk_base_path(&asset_prefix.await?, mode) {
+        Some(chunk_base_path) => builder.chunk_base_path(Vc::cell(Some(chunk_base_path))),
+        None => builder,
     };
 
-    Vc::upcast(builder.build())
+    Ok(Vc::upcast(builder.build()))
 }
 
 #[turbo_tasks::function]
@@ -422,3 +461,33 @@ pub async fn get_client_runtime_entries(
 
     Ok(Vc::cell(runtime_entries))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_base_path_honors_asset_prefix() {
+        assert_eq!(chunk_base_path("", NextMode::DevServer), None);
+        assert_eq!(
+            chunk_base_path("", NextMode::Build),
+            Some("_next/".to_string())
+        );
+        assert_eq!(
+            chunk_base_path("/custom-asset-prefix/", NextMode::Build),
+            Some("custom-asset-prefix/_next/".to_string())
+        );
+        assert_eq!(
+            chunk_base_path("/custom-asset-prefix", NextMode::DevServer),
+            Some("custom-asset-prefix/".to_string())
+        );
+        assert_eq!(
+            chunk_base_path("https://cdn.example.com/assets/", NextMode::Build),
+            Some("https://cdn.example.com/assets/_next/".to_string())
+        );
+        assert_eq!(
+            chunk_base_path("https://cdn.example.com", NextMode::DevServer),
+            Some("https://cdn.example.com/".to_string())
+        );
+    }
+}
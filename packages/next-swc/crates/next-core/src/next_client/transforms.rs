@@ -9,7 +9,9 @@ use crate::{
     next_config::NextConfig,
     next_shared::transforms::{
         get_next_dynamic_transform_rule, get_next_font_transform_rule, get_next_image_rule,
-        get_next_modularize_imports_rule, get_next_pages_transforms_rule,
+        get_next_modularize_imports_rule, get_next_optimize_package_imports_rule,
+        get_next_pages_transforms_rule, get_remove_console_transform_rule,
+        get_server_actions_transform_rule, ActionsTransform,
     },
 };
 
@@ -27,6 +29,13 @@ pub async fn get_next_client_transforms_rules(
         rules.push(get_next_modularize_imports_rule(modularize_imports_config));
     }
 
+    let optimize_package_imports = &*next_config.optimize_package_imports().await?;
+    if !optimize_package_imports.is_empty() {
+        rules.push(get_next_optimize_package_imports_rule(
+            optimize_package_imports,
+        ));
+    }
+
     rules.push(get_next_font_transform_rule());
 
     let pages_dir = match context_ty {
@@ -36,14 +45,23 @@ pub async fn get_next_client_transforms_rules(
             );
             Some(pages_dir)
         }
-        ClientContextType::App { .. } | ClientContextType::Fallback | ClientContextType::Other => {
+        ClientContextType::App { .. } => {
+            rules.push(get_server_actions_transform_rule(
+                ActionsTransform::Client,
+                *next_config.enable_server_actions().await?,
+            ));
             None
         }
+        ClientContextType::Fallback | ClientContextType::Other => None,
     };
 
     rules.push(get_next_dynamic_transform_rule(false, false, pages_dir, mode).await?);
 
     rules.push(get_next_image_rule());
 
+    if let Some(remove_console_rule) = get_remove_console_transform_rule(next_config).await? {
+        rules.push(remove_console_rule);
+    }
+
     Ok(rules)
 }
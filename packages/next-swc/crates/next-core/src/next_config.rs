@@ -2,7 +2,7 @@ use anyhow::{Context, Result};
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use turbo_tasks::{trace::TraceRawVcs, Completion, Value, Vc};
+use turbo_tasks::{trace::TraceRawVcs, Completion, TaskInput, Value, Vc};
 use turbo_tasks_fs::json::parse_json_with_source_context;
 use turbopack_binding::{
     turbo::{tasks_env::EnvMap, tasks_fs::FileSystemPath},
@@ -51,6 +51,9 @@ struct NextConfigAndCustomRoutesRaw {
 #[turbo_tasks::value]
 struct NextConfigAndCustomRoutes {
     config: Vc<NextConfig>,
+    /// The config as resolved by Next.js, including the options Turbopack
+    /// doesn't know about.
+    config_json: Vc<String>,
     custom_routes: Vc<CustomRoutes>,
 }
 
@@ -58,8 +61,6 @@ struct NextConfigAndCustomRoutes {
 #[serde(rename_all = "camelCase")]
 struct CustomRoutesRaw {
     rewrites: Rewrites,
-
-    // unsupported
     headers: Vec<Header>,
     redirects: Vec<Redirect>,
 }
@@ -67,6 +68,8 @@ struct CustomRoutesRaw {
 #[turbo_tasks::value]
 struct CustomRoutes {
     rewrites: Vc<Rewrites>,
+    headers: Vc<Headers>,
+    redirects: Vc<Redirects>,
 }
 
 #[turbo_tasks::value(serialization = "custom", eq = "manual")]
@@ -89,42 +92,42 @@ pub struct NextConfig {
     pub compiler: Option<CompilerConfig>,
 
     pub output: Option<OutputType>,
+    pub output_file_tracing: bool,
+    pub asset_prefix: String,
+    pub base_path: String,
+    pub i18n: Option<I18NConfig>,
+    pub trailing_slash: bool,
+    pub skip_trailing_slash_redirect: Option<bool>,
+    // this is a function in js land, only its presence is known
+    pub export_path_map: Option<serde_json::Value>,
+    pub production_browser_source_maps: bool,
 
     // unsupported
     cross_origin: Option<String>,
     amp: AmpConfig,
     analytics_id: String,
-    asset_prefix: String,
-    base_path: String,
     clean_dist_dir: bool,
     compress: bool,
     dev_indicators: DevIndicatorsConfig,
     dist_dir: String,
     eslint: EslintConfig,
     exclude_default_moment_locales: bool,
-    // this can be a function in js land
-    export_path_map: Option<serde_json::Value>,
     // this is a function in js land
     generate_build_id: Option<serde_json::Value>,
     generate_etags: bool,
     http_agent_options: HttpAgentConfig,
-    i18n: Option<I18NConfig>,
     on_demand_entries: OnDemandEntriesConfig,
     optimize_fonts: bool,
-    output_file_tracing: bool,
     powered_by_header: bool,
-    production_browser_source_maps: bool,
     public_runtime_config: IndexMap<String, serde_json::Value>,
     server_runtime_config: IndexMap<String, serde_json::Value>,
     static_page_generation_timeout: f64,
     swc_minify: Option<bool>,
     target: Option<String>,
-    trailing_slash: bool,
     typescript: TypeScriptConfig,
     use_file_system_public_routes: bool,
     webpack: Option<serde_json::Value>,
     skip_middleware_url_normalize: Option<bool>,
-    skip_trailing_slash_redirect: Option<bool>,
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, TraceRawVcs)]
@@ -170,24 +173,27 @@ struct HttpAgentConfig {
     keep_alive: bool,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "camelCase")]
-struct DomainLocale {
-    default_locale: String,
-    domain: String,
-    http: Option<bool>,
-    locales: Option<Vec<String>>,
+pub struct DomainLocale {
+    pub default_locale: String,
+    pub domain: String,
+    pub http: Option<bool>,
+    pub locales: Option<Vec<String>>,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "camelCase")]
-struct I18NConfig {
-    default_locale: String,
-    domains: Option<Vec<DomainLocale>>,
-    locale_detection: Option<bool>,
-    locales: Vec<String>,
+pub struct I18NConfig {
+    pub default_locale: String,
+    pub domains: Option<Vec<DomainLocale>>,
+    pub locale_detection: Option<bool>,
+    pub locales: Vec<String>,
 }
 
+#[turbo_tasks::value(transparent)]
+pub struct OptionI18NConfig(Option<I18NConfig>);
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "kebab-case")]
 pub enum OutputType {
@@ -195,7 +201,7 @@ pub enum OutputType {
     Export,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(tag = "type", rename_all = "kebab-case")]
 pub enum RouteHas {
     Header {
@@ -218,14 +224,14 @@ pub enum RouteHas {
     },
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "camelCase")]
 pub struct HeaderValue {
     pub key: String,
     pub value: String,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "camelCase")]
 pub struct Header {
     pub source: String,
@@ -240,6 +246,10 @@ pub struct Header {
     pub missing: Option<Vec<RouteHas>>,
 }
 
+#[turbo_tasks::value(transparent)]
+#[derive(Clone, Debug, Default)]
+pub struct Headers(Vec<Header>);
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "camelCase")]
 pub enum RedirectStatus {
@@ -260,11 +270,30 @@ pub struct Redirect {
     pub has: Option<Vec<RouteHas>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub missing: Option<Vec<RouteHas>>,
+    /// Set on the redirects next.js adds itself, e.g. for `trailingSlash`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub internal: Option<bool>,
 
     #[serde(flatten)]
     pub status: RedirectStatus,
 }
 
+impl Redirect {
+    /// The HTTP status code to respond with, defaulting to 307/308 depending
+    /// on `permanent`.
+    pub fn status_code(&self) -> u16 {
+        match self.status {
+            RedirectStatus::StatusCode(status_code) => status_code as u16,
+            RedirectStatus::Permanent(true) => 308,
+            RedirectStatus::Permanent(false) => 307,
+        }
+    }
+}
+
+#[turbo_tasks::value(transparent, eq = "manual")]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Redirects(Vec<Redirect>);
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "camelCase")]
 pub struct Rewrite {
@@ -346,7 +375,7 @@ pub enum ImageLoader {
     Custom,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize, TaskInput, TraceRawVcs)]
 pub enum ImageFormat {
     #[serde(rename = "image/webp")]
     Webp,
@@ -410,7 +439,13 @@ pub struct ExperimentalConfig {
     pub isr_memory_cache_size: Option<f64>,
     pub isr_flush_to_disk: Option<bool>,
     mdx_rs: Option<bool>,
+    pub server_actions: Option<bool>,
+    pub optimize_package_imports: Option<Vec<String>>,
+    pub optimize_server_react: Option<bool>,
+    pub output_file_tracing_ignores: Option<Vec<String>>,
+    pub output_file_tracing_root: Option<String>,
     pub swc_plugins: Option<Vec<(String, serde_json::Value)>>,
+    pub sri: Option<SubResourceIntegrity>,
 
     // unsupported
     adjust_font_fallbacks: Option<bool>,
@@ -435,12 +470,9 @@ pub struct ExperimentalConfig {
     next_script_workers: Option<bool>,
     optimistic_client_cache: Option<bool>,
     optimize_css: Option<serde_json::Value>,
-    output_file_tracing_ignores: Option<Vec<String>>,
-    output_file_tracing_root: Option<String>,
     proxy_timeout: Option<f64>,
     scroll_restoration: Option<bool>,
     shared_pool: Option<bool>,
-    sri: Option<serde_json::Value>,
     swc_minify_debug_options: Option<serde_json::Value>,
     swc_trace_profiling: Option<bool>,
     transpile_packages: Option<Vec<String>>,
@@ -450,6 +482,20 @@ pub struct ExperimentalConfig {
     worker_threads: Option<bool>,
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(rename_all = "camelCase")]
+pub struct SubResourceIntegrity {
+    pub algorithm: Option<SubResourceIntegrityAlgorithm>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(rename_all = "lowercase")]
+pub enum SubResourceIntegrityAlgorithm {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "kebab-case")]
 enum MiddlewarePrefetchType {
@@ -474,7 +520,7 @@ pub enum StyledComponentsTransformOptionsOrBoolean {
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "camelCase")]
 pub struct CompilerConfig {
-    pub react_remove_properties: Option<bool>,
+    pub react_remove_properties: Option<ReactRemoveProperties>,
     pub relay: Option<RelayConfig>,
     pub emotion: Option<EmotionTransformOptionsOrBoolean>,
     pub remove_console: Option<RemoveConsoleConfig>,
@@ -488,6 +534,17 @@ pub enum ReactRemoveProperties {
     Config { properties: Option<Vec<String>> },
 }
 
+impl ReactRemoveProperties {
+    /// Whether the transform is enabled: `false` disables it, options enable
+    /// it.
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            Self::Boolean(enabled) => *enabled,
+            _ => true,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(untagged)]
 pub enum RemoveConsoleConfig {
@@ -495,6 +552,17 @@ pub enum RemoveConsoleConfig {
     Config { exclude: Option<Vec<String>> },
 }
 
+impl RemoveConsoleConfig {
+    /// Whether the transform is enabled: `false` disables it, options enable
+    /// it.
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            Self::Boolean(enabled) => *enabled,
+            _ => true,
+        }
+    }
+}
+
 #[turbo_tasks::value_impl]
 impl NextConfig {
     #[turbo_tasks::function]
@@ -559,6 +627,27 @@ impl NextConfig {
         ))
     }
 
+    #[turbo_tasks::function]
+    pub async fn optimize_package_imports(self: Vc<Self>) -> Result<Vc<Vec<String>>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .optimize_package_imports
+                .clone()
+                .unwrap_or_default(),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn optimize_server_react(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .optimize_server_react
+                .unwrap_or(false),
+        ))
+    }
+
     #[turbo_tasks::function]
     pub async fn webpack_rules(self: Vc<Self>) -> Result<Vc<OptionWebpackRules>> {
         let this = self.await?;
@@ -625,6 +714,13 @@ impl NextConfig {
         Ok(Vc::cell(self.await?.experimental.mdx_rs.unwrap_or(false)))
     }
 
+    #[turbo_tasks::function]
+    pub async fn enable_server_actions(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?.experimental.server_actions.unwrap_or(false),
+        ))
+    }
+
     #[turbo_tasks::function]
     pub async fn sass_config(self: Vc<Self>) -> Result<Vc<JsonValue>> {
         Ok(Vc::cell(
@@ -650,6 +746,52 @@ impl NextConfig {
             self.await?.skip_trailing_slash_redirect.unwrap_or(false),
         ))
     }
+
+    #[turbo_tasks::function]
+    pub async fn production_browser_source_maps(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(self.await?.production_browser_source_maps))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn base_path(self: Vc<Self>) -> Result<Vc<String>> {
+        Ok(Vc::cell(self.await?.base_path.clone()))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn i18n(self: Vc<Self>) -> Result<Vc<OptionI18NConfig>> {
+        Ok(Vc::cell(self.await?.i18n.clone()))
+    }
+
+    /// The locales pages are served under, empty when i18n is not configured.
+    #[turbo_tasks::function]
+    pub async fn locales(self: Vc<Self>) -> Result<Vc<Vec<String>>> {
+        Ok(Vc::cell(
+            self.await?
+                .i18n
+                .as_ref()
+                .map(|i18n| i18n.locales.clone())
+                .unwrap_or_default(),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn dist_dir(self: Vc<Self>) -> Result<Vc<String>> {
+        Ok(Vc::cell(self.await?.dist_dir.clone()))
+    }
+
+    /// The prefix under which `_next` assets are served. Falls back to the
+    /// `basePath` when no `assetPrefix` is configured, and may be an absolute
+    /// URL when assets are served from a CDN.
+    #[turbo_tasks::function]
+    pub async fn computed_asset_prefix(self: Vc<Self>) -> Result<Vc<String>> {
+        let this = self.await?;
+        let asset_prefix = if this.asset_prefix.is_empty() {
+            &this.base_path
+        } else {
+            &this.asset_prefix
+        };
+        Ok(Vc::cell(asset_prefix.trim_end_matches('/').to_string()))
+    }
 }
 
 fn next_configs() -> Vc<Vec<String>> {
@@ -668,6 +810,14 @@ pub async fn load_next_config(execution_context: Vc<ExecutionContext>) -> Result
         .config)
 }
 
+/// Returns next.config.js as resolved by Next.js, serialized to JSON.
+#[turbo_tasks::function]
+pub async fn load_next_config_json(execution_context: Vc<ExecutionContext>) -> Result<Vc<String>> {
+    Ok(load_config_and_custom_routes(execution_context)
+        .await?
+        .config_json)
+}
+
 #[turbo_tasks::function]
 pub async fn load_rewrites(execution_context: Vc<ExecutionContext>) -> Result<Vc<Rewrites>> {
     Ok(load_config_and_custom_routes(execution_context)
@@ -677,6 +827,24 @@ pub async fn load_rewrites(execution_context: Vc<ExecutionContext>) -> Result<Vc
         .rewrites)
 }
 
+#[turbo_tasks::function]
+pub async fn load_headers(execution_context: Vc<ExecutionContext>) -> Result<Vc<Headers>> {
+    Ok(load_config_and_custom_routes(execution_context)
+        .await?
+        .custom_routes
+        .await?
+        .headers)
+}
+
+#[turbo_tasks::function]
+pub async fn load_redirects(execution_context: Vc<ExecutionContext>) -> Result<Vc<Redirects>> {
+    Ok(load_config_and_custom_routes(execution_context)
+        .await?
+        .custom_routes
+        .await?
+        .redirects)
+}
+
 #[turbo_tasks::function]
 async fn load_config_and_custom_routes(
     execution_context: Vc<ExecutionContext>,
@@ -747,22 +915,23 @@ async fn load_next_config_and_custom_routes_internal(
     else {
         return Ok(NextConfigAndCustomRoutes {
             config: NextConfig::default().cell(),
+            config_json: Vc::cell(serde_json::to_string(&NextConfig::default())?),
             custom_routes: CustomRoutes {
                 rewrites: Rewrites::default().cell(),
+                headers: Headers::default().cell(),
+                redirects: Redirects::default().cell(),
             }
             .cell(),
         }
         .cell());
     };
-    let next_config_and_custom_routes: NextConfigAndCustomRoutesRaw =
-        parse_json_with_source_context(val.to_str()?)?;
-
-    if let Some(turbo) = next_config_and_custom_routes
-        .config
-        .experimental
-        .turbo
-        .as_ref()
-    {
+    let NextConfigAndCustomRoutesRaw {
+        config,
+        custom_routes,
+    } = parse_json_with_source_context(val.to_str()?)?;
+    let config_json: JsonValue = parse_json_with_source_context(val.to_str()?)?;
+
+    if let Some(turbo) = config.experimental.turbo.as_ref() {
         if turbo.loaders.is_some() {
             OutdatedConfigIssue {
                 path: config_file.unwrap_or(project_path),
@@ -779,9 +948,12 @@ async fn load_next_config_and_custom_routes_internal(
     }
 
     Ok(NextConfigAndCustomRoutes {
-        config: next_config_and_custom_routes.config.cell(),
+        config: config.cell(),
+        config_json: Vc::cell(config_json["config"].to_string()),
         custom_routes: CustomRoutes {
-            rewrites: next_config_and_custom_routes.custom_routes.rewrites.cell(),
+            rewrites: custom_routes.rewrites.cell(),
+            headers: Vc::cell(custom_routes.headers),
+            redirects: Vc::cell(custom_routes.redirects),
         }
         .cell(),
     }
@@ -1,29 +1,52 @@
 use anyhow::{bail, Result};
-use turbo_tasks::Vc;
-use turbopack_binding::turbopack::core::{
-    asset::{Asset, AssetContent},
-    chunk::{ChunkableModule, ChunkingContext},
-    ident::AssetIdent,
-    module::Module,
-    output::OutputAssets,
-    reference::ModuleReferences,
+use turbo_tasks::{Value, Vc};
+use turbo_tasks_fs::File;
+use turbopack_binding::turbopack::{
+    core::{
+        asset::{Asset, AssetContent},
+        chunk::{
+            availability_info::AvailabilityInfo, Chunk, ChunkItem, ChunkableModule, ChunkingContext,
+        },
+        context::AssetContext,
+        ident::AssetIdent,
+        module::Module,
+        output::OutputAssets,
+        reference::ModuleReferences,
+        reference_type::ReferenceType,
+        virtual_source::VirtualSource,
+    },
+    ecmascript::{
+        chunk::{
+            EcmascriptChunk, EcmascriptChunkItem, EcmascriptChunkItemContent,
+            EcmascriptChunkPlaceable, EcmascriptChunkingContext, EcmascriptExports,
+        },
+        EcmascriptModuleAsset,
+    },
 };
 
 /// A [`NextDynamicEntryModule`] is a marker asset used to indicate which
 /// dynamic assets should appear in the dynamic manifest.
+///
+/// On the server, it is placed as an empty module, whose module ID is the key
+/// of its entry in the dynamic manifest.
 #[turbo_tasks::value(transparent)]
 pub struct NextDynamicEntryModule {
     pub client_entry_module: Vc<Box<dyn Module>>,
+    server_asset_context: Vc<Box<dyn AssetContext>>,
 }
 
 #[turbo_tasks::value_impl]
 impl NextDynamicEntryModule {
-    /// Create a new [`NextDynamicEntryModule`] from the given source CSS
-    /// asset.
+    /// Create a new [`NextDynamicEntryModule`] for the given client entry
+    /// module, imported from the given server context.
     #[turbo_tasks::function]
-    pub fn new(client_entry_module: Vc<Box<dyn Module>>) -> Vc<NextDynamicEntryModule> {
+    pub fn new(
+        client_entry_module: Vc<Box<dyn Module>>,
+        server_asset_context: Vc<Box<dyn AssetContext>>,
+    ) -> Vc<NextDynamicEntryModule> {
         NextDynamicEntryModule {
             client_entry_module,
+            server_asset_context,
         }
         .cell()
     }
@@ -44,6 +67,32 @@ impl NextDynamicEntryModule {
         let client_entry_chunk = client_entry_module.as_root_chunk(client_chunking_context);
         Ok(client_chunking_context.chunk_group(client_entry_chunk))
     }
+
+    #[turbo_tasks::function]
+    async fn placeholder_module(self: Vc<Self>) -> Result<Vc<EcmascriptModuleAsset>> {
+        let this = self.await?;
+
+        let placeholder_source = VirtualSource::new(
+            this.client_entry_module
+                .ident()
+                .path()
+                .join("dynamic.js".to_string()),
+            AssetContent::file(File::from("export {};").into()),
+        );
+
+        let placeholder_module = this.server_asset_context.process(
+            Vc::upcast(placeholder_source),
+            Value::new(ReferenceType::Undefined),
+        );
+
+        let Some(placeholder_module) =
+            Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(placeholder_module).await?
+        else {
+            bail!("dynamic placeholder asset is not an ecmascript module");
+        };
+
+        Ok(placeholder_module)
+    }
 }
 
 #[turbo_tasks::function]
@@ -74,3 +123,87 @@ impl Asset for NextDynamicEntryModule {
         bail!("NextDynamicEntryModule has no content")
     }
 }
+
+#[turbo_tasks::value_impl]
+impl ChunkableModule for NextDynamicEntryModule {
+    #[turbo_tasks::function]
+    fn as_chunk(
+        self: Vc<Self>,
+        context: Vc<Box<dyn ChunkingContext>>,
+        availability_info: Value<AvailabilityInfo>,
+    ) -> Vc<Box<dyn Chunk>> {
+        Vc::upcast(EcmascriptChunk::new(
+            context,
+            Vc::upcast(self),
+            availability_info,
+        ))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl EcmascriptChunkPlaceable for NextDynamicEntryModule {
+    #[turbo_tasks::function]
+    fn as_chunk_item(
+        self: Vc<Self>,
+        chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+    ) -> Vc<Box<dyn EcmascriptChunkItem>> {
+        Vc::upcast(
+            DynamicEntryChunkItem {
+                dynamic_entry_module: self,
+                inner_placeholder_chunk_item: self
+                    .placeholder_module()
+                    .as_chunk_item(chunking_context),
+            }
+            .cell(),
+        )
+    }
+
+    #[turbo_tasks::function]
+    fn get_exports(self: Vc<Self>) -> Vc<EcmascriptExports> {
+        self.placeholder_module().get_exports()
+    }
+}
+
+/// This wrapper only exists to overwrite the `asset_ident` method of the
+/// wrapped [`Vc<Box<dyn EcmascriptChunkItem>>`], so that the module ID of the
+/// chunk item is derived from the [`Vc<NextDynamicEntryModule>`].
+#[turbo_tasks::value]
+struct DynamicEntryChunkItem {
+    dynamic_entry_module: Vc<NextDynamicEntryModule>,
+    inner_placeholder_chunk_item: Vc<Box<dyn EcmascriptChunkItem>>,
+}
+
+#[turbo_tasks::value_impl]
+impl ChunkItem for DynamicEntryChunkItem {
+    #[turbo_tasks::function]
+    fn asset_ident(&self) -> Vc<AssetIdent> {
+        self.dynamic_entry_module.ident()
+    }
+
+    #[turbo_tasks::function]
+    fn references(&self) -> Vc<ModuleReferences> {
+        self.dynamic_entry_module.references()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl EcmascriptChunkItem for DynamicEntryChunkItem {
+    #[turbo_tasks::function]
+    fn content(&self) -> Vc<EcmascriptChunkItemContent> {
+        self.inner_placeholder_chunk_item.content()
+    }
+
+    #[turbo_tasks::function]
+    fn content_with_availability_info(
+        &self,
+        availability_info: Value<AvailabilityInfo>,
+    ) -> Vc<EcmascriptChunkItemContent> {
+        self.inner_placeholder_chunk_item
+            .content_with_availability_info(availability_info)
+    }
+
+    #[turbo_tasks::function]
+    fn chunking_context(&self) -> Vc<Box<dyn EcmascriptChunkingContext>> {
+        self.inner_placeholder_chunk_item.chunking_context()
+    }
+}
@@ -39,6 +39,9 @@ impl Transition for NextDynamicTransition {
             self.client_transition
                 .process(source, context, Value::new(ReferenceType::Undefined));
 
-        Ok(Vc::upcast(NextDynamicEntryModule::new(client_module)))
+        Ok(Vc::upcast(NextDynamicEntryModule::new(
+            client_module,
+            Vc::upcast(context),
+        )))
     }
 }
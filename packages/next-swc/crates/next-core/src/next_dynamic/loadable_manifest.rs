@@ -0,0 +1,91 @@
+use anyhow::{bail, Context, Result};
+use turbo_tasks::{TryJoinIterExt, Vc};
+use turbo_tasks_fs::FileSystemPath;
+use turbopack_binding::turbopack::{
+    core::{
+        chunk::ModuleId as TurbopackModuleId,
+        output::{OutputAsset, OutputAssets},
+    },
+    ecmascript::chunk::{
+        EcmascriptChunkItemExt, EcmascriptChunkPlaceable, EcmascriptChunkingContext,
+    },
+};
+
+use super::NextDynamicEntries;
+use crate::next_manifests::{ModuleId, ReactLoadableManifest, ReactLoadableManifestEntry};
+
+/// Computes the client chunks of each of the dynamic entries, and adds them to
+/// `manifest`.
+///
+/// Entries are keyed by the module ID of their marker in
+/// `server_chunking_context`, which the server side of `next/dynamic` reports
+/// to the renderer. Returns the client chunks, which need to be emitted.
+pub async fn add_dynamic_entries_to_manifest(
+    manifest: &mut ReactLoadableManifest,
+    dynamic_entries: Vc<NextDynamicEntries>,
+    server_chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+    client_chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+    client_relative_path: &FileSystemPath,
+) -> Result<Vec<Vc<Box<dyn OutputAsset>>>> {
+    let mut all_client_chunks = vec![];
+
+    for dynamic_entry in dynamic_entries.await?.iter().copied() {
+        let marker_id = Vc::upcast::<Box<dyn EcmascriptChunkPlaceable>>(dynamic_entry)
+            .as_chunk_item(server_chunking_context)
+            .id()
+            .await?;
+        // The manifest is a plain JS object, so numeric IDs are coerced to
+        // strings.
+        let key = match &*marker_id {
+            TurbopackModuleId::String(string) => string.clone(),
+            TurbopackModuleId::Number(number) => number.to_string(),
+        };
+        if manifest.manifest.contains_key(&key) {
+            continue;
+        }
+
+        let client_entry_module = dynamic_entry.await?.client_entry_module;
+        let Some(client_entry_module) =
+            Vc::try_resolve_sidecast::<Box<dyn EcmascriptChunkPlaceable>>(client_entry_module)
+                .await?
+        else {
+            bail!("dynamic client asset must be an ecmascript module");
+        };
+        let id = client_entry_module
+            .as_chunk_item(client_chunking_context)
+            .id()
+            .await?;
+
+        let client_chunks = dynamic_entry.client_chunks(Vc::upcast(client_chunking_context));
+        let files = client_chunk_paths(client_chunks, client_relative_path).await?;
+        all_client_chunks.extend(client_chunks.await?.iter().copied());
+
+        manifest.manifest.insert(
+            key,
+            ReactLoadableManifestEntry {
+                id: ModuleId::from(&*id),
+                files,
+            },
+        );
+    }
+
+    Ok(all_client_chunks)
+}
+
+async fn client_chunk_paths(
+    client_chunks: Vc<OutputAssets>,
+    client_relative_path: &FileSystemPath,
+) -> Result<Vec<String>> {
+    client_chunks
+        .await?
+        .iter()
+        .map(|chunk| async move {
+            let chunk_path = chunk.ident().path().await?;
+            Ok(client_relative_path
+                .get_path_to(&chunk_path)
+                .context("dynamic client chunk path must be inside the client root")?
+                .to_string())
+        })
+        .try_join()
+        .await
+}
@@ -1,7 +1,9 @@
 pub(crate) mod dynamic_module;
 pub(crate) mod dynamic_transition;
+pub(crate) mod loadable_manifest;
 pub(crate) mod visit_dynamic;
 
 pub use dynamic_module::NextDynamicEntryModule;
 pub use dynamic_transition::NextDynamicTransition;
+pub use loadable_manifest::add_dynamic_entries_to_manifest;
 pub use visit_dynamic::NextDynamicEntries;
@@ -25,8 +25,8 @@ use crate::{
     next_import_map::get_next_edge_import_map,
     next_server::context::ServerContextType,
     next_shared::resolve::{
-        ModuleFeatureReportResolvePlugin, NextSharedRuntimeResolvePlugin,
-        UnsupportedModulesResolvePlugin,
+        ModuleFeatureReportResolvePlugin, NextBarrelOptimizeResolvePlugin,
+        NextSharedRuntimeResolvePlugin, UnsupportedModulesResolvePlugin,
     },
     util::foreign_code_context_condition,
 };
@@ -117,6 +117,7 @@ pub async fn get_edge_resolve_options_context(
             Vc::upcast(ModuleFeatureReportResolvePlugin::new(project_path)),
             Vc::upcast(UnsupportedModulesResolvePlugin::new(project_path)),
             Vc::upcast(NextSharedRuntimeResolvePlugin::new(project_path)),
+            Vc::upcast(NextBarrelOptimizeResolvePlugin::new(project_path)),
         ],
         ..Default::default()
     };
@@ -11,6 +11,13 @@ use turbopack_binding::turbopack::{
     ecmascript::utils::StringifyJs,
 };
 
+/// Returns the key under which the edge entry of the edge function `name`
+/// registers its exports in `self._ENTRIES`. The edge runtime looks up the
+/// entry with the same key.
+pub fn edge_entry_global_var_name(name: &str) -> String {
+    format!("middleware_{name}")
+}
+
 #[turbo_tasks::function]
 pub async fn wrap_edge_entry(
     context: Vc<Box<dyn AssetContext>>,
@@ -25,7 +32,7 @@ pub async fn wrap_edge_entry(
             self._ENTRIES ||= {{}}
             self._ENTRIES[{}] = module
         "#,
-        StringifyJs(&format_args!("middleware_{}", pathname))
+        StringifyJs(&edge_entry_global_var_name(&pathname))
     );
     let file = File::from(source);
 
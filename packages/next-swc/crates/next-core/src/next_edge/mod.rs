@@ -1,5 +1,6 @@
 pub mod context;
 pub mod entry;
 pub mod page_transition;
+pub mod paths;
 pub mod route_regex;
 pub mod route_transition;
@@ -0,0 +1,80 @@
+use anyhow::Result;
+use turbo_tasks::{TryFlatJoinIterExt, Vc};
+use turbo_tasks_fs::FileSystemPath;
+use turbopack_binding::turbopack::core::output::{OutputAsset, OutputAssets};
+
+use crate::{emit::all_assets_from_entries, next_manifests::AssetBinding};
+
+/// The files an edge function consists of, relative to the node root.
+#[derive(Debug, Default)]
+pub struct EdgeFunctionPaths {
+    /// The JavaScript files that are evaluated to load the function.
+    pub files: Vec<String>,
+    /// WebAssembly modules, bound to the global names the runtime loads them
+    /// from.
+    pub wasm: Vec<AssetBinding>,
+    /// Other assets referenced by the function.
+    pub assets: Vec<AssetBinding>,
+}
+
+/// Computes the [EdgeFunctionPaths] for the chunk group of an edge function.
+pub async fn get_edge_function_paths(
+    chunks: Vc<OutputAssets>,
+    node_root: &FileSystemPath,
+) -> Result<EdgeFunctionPaths> {
+    let files = get_paths_from_root(node_root, chunks)
+        .await?
+        .into_iter()
+        .filter(|path| path.ends_with(".js"))
+        .collect();
+
+    let mut wasm = vec![];
+    let mut assets = vec![];
+    for path in get_paths_from_root(node_root, all_assets_from_entries(chunks)).await? {
+        if path.ends_with(".wasm") {
+            wasm.push(AssetBinding {
+                name: wasm_binding_name(&path),
+                file_path: path,
+            });
+        } else if !path.ends_with(".js") && !path.ends_with(".map") {
+            assets.push(AssetBinding {
+                name: path.clone(),
+                file_path: path,
+            });
+        }
+    }
+
+    Ok(EdgeFunctionPaths {
+        files,
+        wasm,
+        assets,
+    })
+}
+
+async fn get_paths_from_root(
+    root: &FileSystemPath,
+    assets: Vc<OutputAssets>,
+) -> Result<Vec<String>> {
+    assets
+        .await?
+        .iter()
+        .map(|&asset| async move {
+            Ok(root
+                .get_path_to(&*asset.ident().path().await?)
+                .map(|path| path.to_string()))
+        })
+        .try_flat_join()
+        .await
+}
+
+/// WebAssembly modules are exposed to edge functions as globals, so their
+/// paths need to be turned into valid identifiers.
+fn wasm_binding_name(path: &str) -> String {
+    format!(
+        "wasm_{}",
+        path.replace(
+            |c: char| !c.is_ascii_alphanumeric() && c != '$' && c != '_',
+            "_"
+        )
+    )
+}
@@ -6,6 +6,8 @@ use std::collections::HashMap;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+use crate::util::remove_trailing_slash;
+
 const INTERCEPTION_ROUTE_MARKERS: [&str; 4] = ["(..)(..)", "(.)", "(..)", "(...)"];
 const NEXT_QUERY_PARAM_PREFIX: &str = "nxtP";
 const NEXT_INTERCEPTION_MARKER_PREFIX: &str = "nxtI";
@@ -68,10 +70,6 @@ fn escape_string_regexp(segment: &str) -> String {
     regex::escape(segment)
 }
 
-fn remove_trailing_slash(route: &str) -> &str {
-    route.trim_end_matches('/')
-}
-
 static PARAM_MATCH_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[((?:\[.*\])|.+)\]").unwrap());
 
 fn get_parametrized_route(route: &str) -> (String, HashMap<String, Group>) {
@@ -0,0 +1,222 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use anyhow::Result;
+use base64::{display::Base64Display, engine::general_purpose::STANDARD};
+use lazy_regex::{lazy_regex, Lazy};
+use regex::{Captures, Regex};
+use sha2::{Digest, Sha256};
+use turbo_tasks::{Completion, Completions, Vc};
+use turbopack_binding::{
+    turbo::{
+        tasks_fetch::fetch,
+        tasks_fs::{to_sys_path, FileSystemPath},
+    },
+    turbopack::core::issue::{IssueExt, IssueSeverity},
+};
+
+/// The directory, relative to `distDir`, in which Google Fonts stylesheets
+/// and font files are cached, so they remain available when offline.
+pub(super) const CACHE_DIR: &str = "cache/google-fonts";
+
+/// Matches the urls of font files in a Google Fonts stylesheet, e.g.
+/// `src: url(https://fonts.gstatic.com/s/inter/v12/a.woff2)`.
+static FONT_URL: Lazy<Regex> = lazy_regex!(r#"url\(['"]?(https?://[^'")]+)['"]?\)"#);
+
+/// The on-disk cache of Google Fonts responses, keyed by request url.
+///
+/// The cache lives outside of the turbo-tasks file system: reading it doesn't
+/// make the calling task depend on the cached files, and writes happen in the
+/// background rather than as part of the (memoized) task that fetched the
+/// response.
+pub(super) struct FontCache {
+    dir: PathBuf,
+}
+
+impl FontCache {
+    /// Returns the cache of the project, or `None` when the project isn't on
+    /// disk.
+    pub(super) async fn new(
+        project_path: Vc<FileSystemPath>,
+        dist_dir: Vc<String>,
+    ) -> Result<Option<Self>> {
+        let dist_dir = dist_dir.await?;
+        Ok(to_sys_path(project_path).await?.map(|project_dir| Self {
+            dir: project_dir.join(&*dist_dir).join(CACHE_DIR),
+        }))
+    }
+
+    /// The file the response for `url` is cached in, named after the hex
+    /// encoded SHA-256 of the url.
+    fn path(&self, url: &str, extension: &str) -> PathBuf {
+        self.dir
+            .join(format!("{:x}.{extension}", Sha256::digest(url.as_bytes())))
+    }
+
+    /// Reads a cached response, if there is one.
+    pub(super) async fn read(&self, url: &str, extension: &str) -> Option<Vec<u8>> {
+        tokio::fs::read(self.path(url, extension)).await.ok()
+    }
+
+    /// Caches the response for `url` in the background. Failures are ignored,
+    /// as the cache is only used when Google Fonts can't be reached.
+    pub(super) fn write(&self, url: &str, extension: &str, content: Vec<u8>) {
+        let path = self.path(url, extension);
+        tokio::spawn(async move {
+            if let Some(dir) = path.parent() {
+                tokio::fs::create_dir_all(dir).await?;
+            }
+            tokio::fs::write(path, content).await
+        });
+    }
+
+    /// Replaces the urls of font files in `stylesheet` with data urls of their
+    /// cached contents. Fonts that aren't cached keep their url and fail to
+    /// load when offline, so the fallback fonts are used instead.
+    pub(super) async fn inline_font_files(&self, stylesheet: &str) -> String {
+        let mut fonts = HashMap::new();
+        for url in font_urls(stylesheet) {
+            if let Some(content) = self.read(url, font_extension(url)).await {
+                fonts.insert(url.to_string(), content);
+            }
+        }
+        replace_font_urls(stylesheet, &fonts)
+    }
+}
+
+/// Downloads the font files referenced by `stylesheet` that aren't cached yet,
+/// each in its own task so they are fetched concurrently. Failures are
+/// reported as warnings, as the fonts are still loaded from Google Fonts when
+/// online.
+#[turbo_tasks::function]
+pub(super) async fn cache_font_files(
+    project_path: Vc<FileSystemPath>,
+    dist_dir: Vc<String>,
+    stylesheet: Vc<String>,
+    issue_path: Vc<FileSystemPath>,
+) -> Result<Vc<Completion>> {
+    let Some(cache) = FontCache::new(project_path, dist_dir).await? else {
+        return Ok(Completion::immutable());
+    };
+
+    let downloads = font_urls(&stylesheet.await?)
+        .into_iter()
+        .filter(|url| !cache.path(url, font_extension(url)).exists())
+        .map(|url| cache_font_file(project_path, dist_dir, url.to_string(), issue_path))
+        .collect();
+    Ok(Completions::all(downloads))
+}
+
+#[turbo_tasks::function]
+async fn cache_font_file(
+    project_path: Vc<FileSystemPath>,
+    dist_dir: Vc<String>,
+    url: String,
+    issue_path: Vc<FileSystemPath>,
+) -> Result<Vc<Completion>> {
+    let Some(cache) = FontCache::new(project_path, dist_dir).await? else {
+        return Ok(Completion::immutable());
+    };
+
+    match &*fetch(Vc::cell(url.clone()), Vc::cell(None)).await? {
+        Ok(response) => {
+            let response = response.await?;
+            if (200..300).contains(&response.status) {
+                cache.write(&url, font_extension(&url), response.body.await?.0.clone());
+            }
+        }
+        Err(err) => err
+            .to_issue(IssueSeverity::Warning.into(), issue_path)
+            .emit(),
+    }
+    Ok(Completion::new())
+}
+
+fn font_urls(stylesheet: &str) -> Vec<&str> {
+    FONT_URL
+        .captures_iter(stylesheet)
+        .filter_map(|captures| captures.get(1))
+        .map(|url| url.as_str())
+        .collect()
+}
+
+fn replace_font_urls(stylesheet: &str, fonts: &HashMap<String, Vec<u8>>) -> String {
+    FONT_URL
+        .replace_all(stylesheet, |captures: &Captures| {
+            let url = &captures[1];
+            match fonts.get(url) {
+                Some(content) => format!(
+                    "url(data:{};base64,{})",
+                    font_mime_type(url),
+                    Base64Display::new(content, &STANDARD)
+                ),
+                None => captures[0].to_string(),
+            }
+        })
+        .into_owned()
+}
+
+fn font_extension(url: &str) -> &str {
+    url.rsplit_once('.')
+        .map(|(_, extension)| extension)
+        .filter(|extension| extension.chars().all(|c| c.is_ascii_alphanumeric()))
+        .unwrap_or("font")
+}
+
+fn font_mime_type(url: &str) -> &'static str {
+    match font_extension(url) {
+        "woff2" => "font/woff2",
+        "woff" => "font/woff",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{font_urls, replace_font_urls};
+
+    const STYLESHEET: &str = r#"
+        /* latin */
+        @font-face {
+          font-family: 'Inter';
+          font-style: normal;
+          font-weight: 400;
+          src: url(https://fonts.gstatic.com/s/inter/v12/a.woff2) format('woff2');
+        }
+        /* cyrillic */
+        @font-face {
+          font-family: 'Inter';
+          font-style: normal;
+          font-weight: 400;
+          src: url('https://fonts.gstatic.com/s/inter/v12/b.ttf') format('truetype');
+        }
+    "#;
+
+    #[test]
+    fn test_font_urls() {
+        assert_eq!(
+            font_urls(STYLESHEET),
+            vec![
+                "https://fonts.gstatic.com/s/inter/v12/a.woff2",
+                "https://fonts.gstatic.com/s/inter/v12/b.ttf"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replace_font_urls() {
+        let fonts = HashMap::from([(
+            "https://fonts.gstatic.com/s/inter/v12/a.woff2".to_string(),
+            b"font".to_vec(),
+        )]);
+        let stylesheet = replace_font_urls(STYLESHEET, &fonts);
+
+        assert!(stylesheet.contains("src: url(data:font/woff2;base64,Zm9udA==) format('woff2');"));
+        assert!(stylesheet.contains(
+            "src: url('https://fonts.gstatic.com/s/inter/v12/b.ttf') format('truetype');"
+        ));
+    }
+}
@@ -36,6 +36,7 @@ use turbopack_binding::{
 };
 
 use self::{
+    cache::{cache_font_files, FontCache, CACHE_DIR},
     font_fallback::get_font_fallback,
     options::{options_from_request, FontDataEntry, FontWeights, NextFontGoogleOptions},
     stylesheet::build_stylesheet,
@@ -43,12 +44,14 @@ use self::{
 };
 use super::{
     font_fallback::FontFallback,
+    issue::NextFontIssue,
     util::{
         get_request_hash, get_request_id, get_scoped_font_family, FontCssProperties, FontFamilyType,
     },
 };
-use crate::{embed_js::next_js_file_path, util::load_next_js_templateon};
+use crate::{embed_js::next_js_file_path, mode::NextMode, util::load_next_js_templateon};
 
+mod cache;
 pub mod font_fallback;
 pub mod options;
 pub mod request;
@@ -161,6 +164,8 @@ impl ImportMappingReplacement for NextFontGoogleReplacer {
 pub struct NextFontGoogleCssModuleReplacer {
     project_path: Vc<FileSystemPath>,
     execution_context: Vc<ExecutionContext>,
+    dist_dir: Vc<String>,
+    mode: NextMode,
 }
 
 #[turbo_tasks::value_impl]
@@ -169,10 +174,14 @@ impl NextFontGoogleCssModuleReplacer {
     pub fn new(
         project_path: Vc<FileSystemPath>,
         execution_context: Vc<ExecutionContext>,
+        dist_dir: Vc<String>,
+        mode: NextMode,
     ) -> Vc<Self> {
         Self::cell(NextFontGoogleCssModuleReplacer {
             project_path,
             execution_context,
+            dist_dir,
+            mode,
         })
     }
 }
@@ -232,10 +241,21 @@ impl ImportMappingReplacement for NextFontGoogleCssModuleReplacer {
                 |p| get_mock_stylesheet(stylesheet_url, p, self.execution_context).boxed(),
             )
             .await?;
+        let stylesheet_str = stylesheet_with_cache(
+            self.project_path,
+            self.dist_dir,
+            stylesheet_url,
+            stylesheet_str,
+            options,
+            css_virtual_path,
+            self.mode,
+            mocked_responses_path.is_some(),
+        )
+        .await?;
 
         let stylesheet = match stylesheet_str {
             Some(s) => Some(
-                update_google_stylesheet(s, options, scoped_font_family)
+                update_google_stylesheet(Vc::cell(s), options, scoped_font_family)
                     .await?
                     .clone_value(),
             ),
@@ -393,15 +413,14 @@ async fn fetch_real_stylesheet(
     .await?;
 
     Ok(match &*stylesheet {
-        Ok(r) => Some(r.await?.body.to_string()),
+        Ok(r) => {
+            let r = r.await?;
+            (200..300).contains(&r.status).then(|| r.body.to_string())
+        }
         Err(err) => {
             // Inform the user of the failure to retreive the stylesheet, but don't
-            // propagate this error. We don't want e.g. offline connections to prevent page
-            // renders during development. During production builds, however, this error
-            // should propagate.
-            //
-            // TODO(WEB-283): Use fallback in dev in this case
-            // TODO(WEB-293): Fail production builds (not dev) in this case
+            // propagate this error. The cache, or the fallback fonts, are used instead,
+            // so e.g. offline connections don't prevent page renders during development.
             err.to_issue(IssueSeverity::Warning.into(), css_virtual_path)
                 .emit();
 
@@ -410,6 +429,77 @@ async fn fetch_real_stylesheet(
     })
 }
 
+/// Caches fetched stylesheets, and the font files they reference in
+/// development, on disk. When a stylesheet can't be fetched, the cached one is
+/// used instead. Without a cached stylesheet, development falls back to the
+/// fallback fonts, while production builds fail.
+///
+/// With `mocked` responses nothing is fetched from Google Fonts, including the
+/// font files of the stylesheet.
+async fn stylesheet_with_cache(
+    project_path: Vc<FileSystemPath>,
+    dist_dir: Vc<String>,
+    stylesheet_url: Vc<String>,
+    fetched: Option<Vc<String>>,
+    options: Vc<NextFontGoogleOptions>,
+    css_virtual_path: Vc<FileSystemPath>,
+    mode: NextMode,
+    mocked: bool,
+) -> Result<Option<String>> {
+    let stylesheet_url = &*stylesheet_url.await?;
+    let cache = FontCache::new(project_path, dist_dir).await?;
+
+    if let Some(stylesheet_vc) = fetched {
+        let stylesheet = stylesheet_vc.await?.clone_value();
+        if let Some(cache) = &cache {
+            cache.write(stylesheet_url, "css", stylesheet.clone().into_bytes());
+            if mode != NextMode::Build && !mocked {
+                // Not awaited, so the font resolves without waiting for the
+                // downloads. The task is still a child of this one, so its
+                // warnings are reported.
+                let _ = cache_font_files(project_path, dist_dir, stylesheet_vc, css_virtual_path);
+            }
+        }
+        return Ok(Some(stylesheet));
+    }
+
+    let cached = match &cache {
+        Some(cache) => cache
+            .read(stylesheet_url, "css")
+            .await
+            .and_then(|stylesheet| String::from_utf8(stylesheet).ok()),
+        None => None,
+    };
+    match (cache, cached) {
+        (Some(cache), Some(stylesheet)) => Ok(Some(if mode == NextMode::Build {
+            stylesheet
+        } else {
+            cache.inline_font_files(&stylesheet).await
+        })),
+        _ => {
+            if mode == NextMode::Build {
+                NextFontIssue {
+                    path: css_virtual_path,
+                    title: Vc::cell(format!(
+                        "Failed to fetch font `{}` from Google Fonts",
+                        options.await?.font_family
+                    )),
+                    description: Vc::cell(format!(
+                        "The stylesheet {stylesheet_url} could not be fetched and is not cached \
+                         in {}/{CACHE_DIR}. Production builds require the stylesheet, so make \
+                         sure Google Fonts is reachable or build once while online.",
+                        &*dist_dir.await?
+                    )),
+                    severity: IssueSeverity::Fatal.cell(),
+                }
+                .cell()
+                .emit();
+            }
+            Ok(None)
+        }
+    }
+}
+
 async fn get_mock_stylesheet(
     stylesheet_url: Vc<String>,
     mocked_responses_path: &str,
@@ -5,6 +5,7 @@ use turbopack_binding::{
     turbo::{
         tasks::Value,
         tasks_fs::{json::parse_json_with_source_context, FileContent, FileSystemPath},
+        tasks_hash::hash_xxh3_hash64,
     },
     turbopack::core::{
         asset::AssetContent,
@@ -21,6 +22,7 @@ use self::{
     font_fallback::get_font_fallbacks,
     options::{options_from_request, FontDescriptors, NextFontLocalOptions},
     stylesheet::build_stylesheet,
+    subset::get_font_subset,
     util::build_font_family_string,
 };
 use super::{font_fallback::FontFallbacks, util::FontCssProperties};
@@ -33,7 +35,13 @@ pub mod font_fallback;
 pub mod options;
 pub mod request;
 pub mod stylesheet;
+mod subset;
 pub mod util;
+mod woff2;
+
+/// The request the stylesheet makes for each subset of a font file, with the
+/// path of the file and the `unicodeRange` of the subset as query.
+pub const SUBSET_REQUEST: &str = "@vercel/turbopack-next/internal/font/local/subset.woff2";
 
 #[turbo_tasks::value(shared)]
 pub(crate) struct NextFontLocalReplacer {
@@ -172,6 +180,7 @@ impl ImportMappingReplacement for NextFontLocalCssModuleReplacer {
         let fallback = get_font_fallbacks(context, options, request_hash);
 
         let stylesheet = build_stylesheet(
+            context,
             font_options_from_query_map(*query_vc),
             fallback,
             get_font_css_properties(options, fallback, request_hash),
@@ -188,6 +197,69 @@ impl ImportMappingReplacement for NextFontLocalCssModuleReplacer {
     }
 }
 
+#[turbo_tasks::value(shared)]
+pub struct NextFontLocalSubsetReplacer {}
+
+#[turbo_tasks::value_impl]
+impl NextFontLocalSubsetReplacer {
+    #[turbo_tasks::function]
+    pub fn new() -> Vc<Self> {
+        Self::cell(NextFontLocalSubsetReplacer {})
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ImportMappingReplacement for NextFontLocalSubsetReplacer {
+    #[turbo_tasks::function]
+    fn replace(&self, _capture: String) -> Vc<ImportMapping> {
+        ImportMapping::Ignore.into()
+    }
+
+    /// Intercepts requests for the subsets of font files made by the
+    /// stylesheet generated by the above replacer. Returns a VirtualSource of
+    /// the WOFF2 encoded subset, which is emitted like any other font file.
+    #[turbo_tasks::function]
+    async fn result(
+        &self,
+        context: Vc<FileSystemPath>,
+        request: Vc<Request>,
+    ) -> Result<Vc<ImportMapResult>> {
+        let Request::Module {
+            module: _,
+            path: _,
+            query: query_vc,
+        } = &*request.await?
+        else {
+            return Ok(ImportMapResult::NoEntry.into());
+        };
+
+        let query_map = qstring::QString::from(&**query_vc.await?);
+        let (Some(path), Some(unicode_range)) =
+            (query_map.get("path"), query_map.get("unicodeRange"))
+        else {
+            bail!("next/font/local subset requests need a path and a unicodeRange");
+        };
+
+        let font_path = context.join(path.to_owned());
+        let subset = get_font_subset(font_path, unicode_range.to_owned());
+        if let FileContent::NotFound = &*subset.await? {
+            return Ok(ImportMapResult::NoEntry.into());
+        }
+
+        let file_stem = font_path.file_stem().await?;
+        let file_stem = file_stem.as_deref().unwrap_or("font");
+        let subset_asset = VirtualSource::new(
+            font_path.parent().join(format!(
+                "{file_stem}.{:016x}.woff2",
+                hash_xxh3_hash64(unicode_range)
+            )),
+            AssetContent::file(subset),
+        );
+
+        Ok(ImportMapResult::Result(ResolveResult::source(Vc::upcast(subset_asset)).into()).into())
+    }
+}
+
 #[turbo_tasks::function]
 async fn get_font_css_properties(
     options_vc: Vc<NextFontLocalOptions>,
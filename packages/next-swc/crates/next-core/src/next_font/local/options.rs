@@ -5,9 +5,12 @@ use serde::{Deserialize, Serialize};
 use turbo_tasks::Vc;
 use turbopack_binding::turbo::tasks::{trace::TraceRawVcs, Value};
 
-use super::request::{
-    AdjustFontFallback, NextFontLocalRequest, NextFontLocalRequestArguments, SrcDescriptor,
-    SrcRequest,
+use super::{
+    request::{
+        AdjustFontFallback, NextFontLocalRequest, NextFontLocalRequestArguments, SrcDescriptor,
+        SrcRequest,
+    },
+    subset::{named_subset_unicode_range, parse_unicode_range},
 };
 
 /// A normalized, Vc-friendly struct derived from validating and transforming
@@ -32,6 +35,9 @@ pub(super) struct NextFontLocalOptions {
     /// The name of the variable assigned to the results of calling the
     /// `localFont` function. This is used as the font family's base name.
     pub variable_name: String,
+    /// The css `unicode-range`s of the subsets each font file is split into.
+    /// Empty when the font files are used as-is.
+    pub unicode_ranges: Vec<String>,
 }
 
 #[turbo_tasks::value_impl]
@@ -140,6 +146,8 @@ pub(super) fn options_from_request(request: &NextFontLocalRequest) -> Result<Nex
         src,
         adjust_font_fallback,
         variable,
+        subsets,
+        unicode_ranges: custom_unicode_ranges,
     } = &request.arguments.0;
 
     let fonts = match src {
@@ -158,6 +166,16 @@ pub(super) fn options_from_request(request: &NextFontLocalRequest) -> Result<Nex
         }
     };
 
+    let mut unicode_ranges = subsets
+        .iter()
+        .flatten()
+        .map(|subset| named_subset_unicode_range(subset).map(ToOwned::to_owned))
+        .collect::<Result<Vec<_>>>()?;
+    for unicode_range in custom_unicode_ranges.iter().flatten() {
+        parse_unicode_range(unicode_range)?;
+        unicode_ranges.push(unicode_range.to_owned());
+    }
+
     Ok(NextFontLocalOptions {
         fonts,
         display: display.to_owned(),
@@ -168,6 +186,7 @@ pub(super) fn options_from_request(request: &NextFontLocalRequest) -> Result<Nex
         variable_name: request.variable_name.to_owned(),
         default_weight: weight.as_ref().and_then(|s| s.parse().ok()),
         default_style: style.to_owned(),
+        unicode_ranges,
     })
 }
 
@@ -213,7 +232,8 @@ mod tests {
                 fallback: None,
                 adjust_font_fallback: AdjustFontFallback::Arial,
                 variable: None,
-                variable_name: "myFont".to_owned()
+                variable_name: "myFont".to_owned(),
+                unicode_ranges: vec![],
             },
         );
 
@@ -268,7 +288,8 @@ mod tests {
                 fallback: None,
                 adjust_font_fallback: AdjustFontFallback::Arial,
                 variable: None,
-                variable_name: "myFont".to_owned()
+                variable_name: "myFont".to_owned(),
+                unicode_ranges: vec![],
             },
         );
 
@@ -341,10 +362,60 @@ mod tests {
                 fallback: Some(vec!["Fallback".to_owned()]),
                 adjust_font_fallback: AdjustFontFallback::TimesNewRoman,
                 variable: Some("myvar".to_owned()),
-                variable_name: "myFont".to_owned()
+                variable_name: "myFont".to_owned(),
+                unicode_ranges: vec![],
             },
         );
 
         Ok(())
     }
+
+    #[test]
+    fn test_subsets() -> Result<()> {
+        let request: NextFontLocalRequest = parse_json_with_source_context(
+            r#"
+            {
+                "import": "",
+                "path": "index.js",
+                "variableName": "myFont",
+                "arguments": [{
+                    "src": "./NotoSansSC-Regular.otf",
+                    "subsets": ["greek"],
+                    "unicodeRanges": ["U+4E00-9FFF"]
+                }]
+            }
+        "#,
+        )?;
+
+        assert_eq!(
+            options_from_request(&request)?.unicode_ranges,
+            vec!["U+0370-03FF".to_owned(), "U+4E00-9FFF".to_owned()],
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_subset_fails() -> Result<()> {
+        let request: NextFontLocalRequest = parse_json_with_source_context(
+            r#"
+            {
+                "import": "",
+                "path": "index.js",
+                "variableName": "myFont",
+                "arguments": [{
+                    "src": "./Roboto-Regular.ttf",
+                    "subsets": ["klingon"]
+                }]
+            }
+        "#,
+        )?;
+
+        match options_from_request(&request) {
+            Ok(r) => panic!("Expected failure, received {:?}", r),
+            Err(err) => assert!(err.to_string().starts_with("Unknown subset `klingon`")),
+        }
+
+        Ok(())
+    }
 }
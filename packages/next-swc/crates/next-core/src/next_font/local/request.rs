@@ -28,6 +28,10 @@ pub(super) struct NextFontLocalRequestArguments {
     )]
     pub adjust_font_fallback: AdjustFontFallback,
     pub variable: Option<String>,
+    /// Named subsets, e.g. `latin`, to split the font files into.
+    pub subsets: Option<Vec<String>>,
+    /// Custom css `unicode-range`s to split the font files into.
+    pub unicode_ranges: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize)]
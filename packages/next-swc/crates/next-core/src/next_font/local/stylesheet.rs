@@ -1,8 +1,13 @@
 use anyhow::{bail, Result};
 use indoc::formatdoc;
 use turbo_tasks::Vc;
+use turbopack_binding::turbo::tasks_fs::{FileContent, FileSystemPath};
 
-use super::options::{FontDescriptors, NextFontLocalOptions};
+use super::{
+    options::{FontDescriptors, NextFontLocalOptions},
+    subset::get_font_subset,
+    SUBSET_REQUEST,
+};
 use crate::next_font::{
     font_fallback::FontFallbacks,
     stylesheet::{build_fallback_definition, build_font_class_rules},
@@ -11,6 +16,7 @@ use crate::next_font::{
 
 #[turbo_tasks::function]
 pub(super) async fn build_stylesheet(
+    context: Vc<FileSystemPath>,
     options: Vc<NextFontLocalOptions>,
     fallbacks: Vc<FontFallbacks>,
     css_properties: Vc<FontCssProperties>,
@@ -28,15 +34,19 @@ pub(super) async fn build_stylesheet(
         {}
         {}
     "#,
-        *build_font_face_definitions(scoped_font_family, options).await?,
+        *build_font_face_definitions(context, scoped_font_family, options).await?,
         (*build_fallback_definition(fallbacks).await?),
         *build_font_class_rules(css_properties).await?
     )))
 }
 
-/// Builds a string of `@font-face` definitions for each local font file
+/// Builds a string of `@font-face` definitions for each local font file, or
+/// for each subset of each file when `unicode_ranges` are given. Subsets are
+/// requested from [super::NextFontLocalSubsetReplacer], so they're emitted like
+/// any other font file referenced by the stylesheet.
 #[turbo_tasks::function]
 pub(super) async fn build_font_face_definitions(
+    context: Vc<FileSystemPath>,
     scoped_font_family: Vc<String>,
     options: Vc<NextFontLocalOptions>,
 ) -> Result<Vc<String>> {
@@ -49,35 +59,77 @@ pub(super) async fn build_font_face_definitions(
     };
 
     for font in fonts {
-        definitions.push_str(&formatdoc!(
-            r#"
-            @font-face {{
-                font-family: '{}';
-                src: url('{}') format('{}');
-                font-display: {};
-                {}{}
-            }}
-        "#,
-            *scoped_font_family.await?,
-            &font.path,
-            ext_to_format(&font.ext)?,
-            options.display,
-            &font
-                .weight
-                .as_ref()
-                .or(options.default_weight.as_ref())
-                .map_or_else(|| "".to_owned(), |w| format!("font-weight: {};", w)),
-            &font
-                .style
-                .as_ref()
-                .or(options.default_style.as_ref())
-                .map_or_else(|| "".to_owned(), |s| format!("font-style: {};", s)),
-        ));
+        let weight = font
+            .weight
+            .as_ref()
+            .or(options.default_weight.as_ref())
+            .map_or_else(|| "".to_owned(), |w| format!("font-weight: {};", w));
+        let style = font
+            .style
+            .as_ref()
+            .or(options.default_style.as_ref())
+            .map_or_else(|| "".to_owned(), |s| format!("font-style: {};", s));
+
+        if options.unicode_ranges.is_empty() {
+            definitions.push_str(&formatdoc!(
+                r#"
+                @font-face {{
+                    font-family: '{}';
+                    src: url('{}') format('{}');
+                    font-display: {};
+                    {}{}
+                }}
+            "#,
+                *scoped_font_family.await?,
+                &font.path,
+                ext_to_format(&font.ext)?,
+                options.display,
+                weight,
+                style,
+            ));
+            continue;
+        }
+
+        let font_path = context.join(font.path.clone());
+        for unicode_range in &options.unicode_ranges {
+            if let FileContent::NotFound =
+                *get_font_subset(font_path, unicode_range.clone()).await?
+            {
+                continue;
+            }
+
+            definitions.push_str(&formatdoc!(
+                r#"
+                @font-face {{
+                    font-family: '{}';
+                    src: url('{}') format('woff2');
+                    font-display: {};
+                    {}{}
+                    unicode-range: {};
+                }}
+            "#,
+                *scoped_font_family.await?,
+                subset_request(&font.path, unicode_range),
+                options.display,
+                weight,
+                style,
+                unicode_range,
+            ));
+        }
     }
 
     Ok(Vc::cell(definitions))
 }
 
+/// The request for the subset of the font file at `path`, relative to the
+/// stylesheet, for `unicode_range`.
+fn subset_request(path: &str, unicode_range: &str) -> String {
+    format!(
+        "{SUBSET_REQUEST}?{}",
+        qstring::QString::new(vec![("path", path), ("unicodeRange", unicode_range)])
+    )
+}
+
 /// Used as e.g. `format('woff')` in `src` properties in `@font-face`
 /// definitions above.
 fn ext_to_format(ext: &str) -> Result<String> {
@@ -0,0 +1,249 @@
+use std::ops::RangeInclusive;
+
+use allsorts::{
+    binary::read::ReadScope, font::MatchingPresentation, font_data::FontData, subset::subset, Font,
+};
+use anyhow::{bail, Context, Result};
+use turbo_tasks::Vc;
+use turbopack_binding::turbo::tasks_fs::{rope::Rope, File, FileContent, FileSystemPath};
+
+use super::woff2::sfnt_to_woff2;
+
+/// The tag of font collection files, e.g. `.ttc` files, at the start of sfnt
+/// fonts, or at the start of the WOFF/WOFF2 flavor.
+const COLLECTION_TAG: &[u8; 4] = b"ttcf";
+
+/// The unicode ranges of the named subsets, as used by Google Fonts.
+const NAMED_SUBSETS: &[(&str, &str)] = &[
+    (
+        "cyrillic-ext",
+        "U+0460-052F, U+1C80-1C88, U+20B4, U+2DE0-2DFF, U+A640-A69F, U+FE2E-FE2F",
+    ),
+    (
+        "cyrillic",
+        "U+0301, U+0400-045F, U+0490-0491, U+04B0-04B1, U+2116",
+    ),
+    ("greek-ext", "U+1F00-1FFF"),
+    ("greek", "U+0370-03FF"),
+    (
+        "vietnamese",
+        "U+0102-0103, U+0110-0111, U+0128-0129, U+0168-0169, U+01A0-01A1, U+01AF-01B0, \
+         U+1EA0-1EF9, U+20AB",
+    ),
+    (
+        "latin-ext",
+        "U+0100-024F, U+0259, U+1E00-1EFF, U+2020, U+20A0-20AB, U+20AD-20CF, U+2113, U+2C60-2C7F, \
+         U+A720-A7FF",
+    ),
+    (
+        "latin",
+        "U+0000-00FF, U+0131, U+0152-0153, U+02BB-02BC, U+02C6, U+02DA, U+02DC, U+2000-206F, \
+         U+2074, U+20AC, U+2122, U+2191, U+2193, U+2212, U+2215, U+FEFF, U+FFFD",
+    ),
+];
+
+/// Returns the css `unicode-range` of a named subset, e.g. `latin`.
+pub(super) fn named_subset_unicode_range(name: &str) -> Result<&'static str> {
+    match NAMED_SUBSETS.iter().find(|(subset, _)| *subset == name) {
+        Some((_, unicode_range)) => Ok(unicode_range),
+        None => bail!(
+            "Unknown subset `{}`. Available subsets: {}",
+            name,
+            NAMED_SUBSETS
+                .iter()
+                .map(|(subset, _)| format!("`{subset}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Parses a css `unicode-range` value, e.g. `U+0000-00FF, U+4??`, into
+/// ranges of code points.
+pub(super) fn parse_unicode_range(unicode_range: &str) -> Result<Vec<RangeInclusive<u32>>> {
+    unicode_range
+        .split(',')
+        .map(|range| {
+            let range = range.trim();
+            let hex = range
+                .strip_prefix("U+")
+                .or_else(|| range.strip_prefix("u+"))
+                .with_context(|| format!("Invalid unicode range `{range}`"))?;
+            let parse = |hex: &str| {
+                u32::from_str_radix(hex, 16)
+                    .with_context(|| format!("Invalid unicode range `{range}`"))
+            };
+
+            let (start, end) = if let Some((start, end)) = hex.split_once('-') {
+                (parse(start)?, parse(end)?)
+            } else if hex.contains('?') {
+                // Wildcards, e.g. `U+4??` for `U+400-4FF`
+                (
+                    parse(&hex.replace('?', "0"))?,
+                    parse(&hex.replace('?', "F"))?,
+                )
+            } else {
+                let code_point = parse(hex)?;
+                (code_point, code_point)
+            };
+            if start > end || end > 0x10ffff {
+                bail!("Invalid unicode range `{range}`");
+            }
+            Ok(start..=end)
+        })
+        .collect()
+}
+
+/// Subsets the font file at `font_path` for `unicode_range` and encodes it as
+/// WOFF2. The content is `NotFound` when the font has none of the glyphs in the
+/// range.
+#[turbo_tasks::function]
+pub(super) async fn get_font_subset(
+    font_path: Vc<FileSystemPath>,
+    unicode_range: String,
+) -> Result<Vc<FileContent>> {
+    let content = font_path.read().await?;
+    let font_file = match &*content {
+        FileContent::NotFound => bail!("Expected font file content"),
+        FileContent::Content(file) => file.content().to_bytes()?,
+    };
+    let font_path_str = font_path.to_string().await?;
+    let subset = subset_font(&font_file, &unicode_range)
+        .with_context(|| format!("Unable to subset the font file at {}", font_path_str))?;
+
+    Ok(match subset {
+        Some(subset) => FileContent::Content(File::from(Rope::from(subset))),
+        None => FileContent::NotFound,
+    }
+    .cell())
+}
+
+/// Subsets a font file to the glyphs of the code points in `unicode_range`,
+/// and encodes the subset as WOFF2. Returns `None` when the font has none of
+/// these glyphs.
+fn subset_font(font_file: &[u8], unicode_range: &str) -> Result<Option<Vec<u8>>> {
+    let ranges = parse_unicode_range(unicode_range)?;
+    if is_font_collection(font_file) {
+        bail!(
+            "Font collections can't be subset. Extract the font to use from the collection, or \
+             remove `subsets` and `unicodeRanges`"
+        );
+    }
+    let scope = ReadScope::new(font_file);
+    let font_data = scope.read::<FontData>()?;
+    let provider = font_data.table_provider(0)?;
+    let mut font = Font::new(font_data.table_provider(0)?)?.context("Unable to read font")?;
+
+    // The `.notdef` glyph must always be the first glyph.
+    let mut glyph_ids = vec![0];
+    for code_point in ranges.into_iter().flatten() {
+        let Some(c) = char::from_u32(code_point) else {
+            continue;
+        };
+        let (glyph_id, _) = font.lookup_glyph_index(c, MatchingPresentation::NotRequired, None);
+        if glyph_id != 0 {
+            glyph_ids.push(glyph_id);
+        }
+    }
+    glyph_ids[1..].sort_unstable();
+    glyph_ids.dedup();
+    if glyph_ids.len() == 1 {
+        return Ok(None);
+    }
+
+    let sfnt = subset(&provider, &glyph_ids)?;
+    Ok(Some(sfnt_to_woff2(&sfnt)?))
+}
+
+/// Whether `font_file` is a collection of fonts, either as a plain sfnt file or
+/// wrapped in WOFF or WOFF2.
+fn is_font_collection(font_file: &[u8]) -> bool {
+    match font_file.get(0..4) {
+        Some(b"wOFF" | b"wOF2") => font_file.get(4..8) == Some(&COLLECTION_TAG[..]),
+        Some(tag) => tag == COLLECTION_TAG,
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use allsorts::{
+        binary::read::ReadScope, font::MatchingPresentation, font_data::FontData, Font,
+    };
+    use anyhow::Result;
+
+    use super::{named_subset_unicode_range, parse_unicode_range, subset_font};
+
+    const NOTO_SANS: &[u8] = include_bytes!(
+        "../../../../../../next/src/compiled/@vercel/og/noto-sans-v27-latin-regular.ttf"
+    );
+
+    #[test]
+    fn test_parse_unicode_range() -> Result<()> {
+        assert_eq!(
+            parse_unicode_range("U+0000-00FF, U+0131, u+4??")?,
+            vec![0x0..=0xff, 0x131..=0x131, 0x400..=0x4ff]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_invalid_unicode_range() {
+        match parse_unicode_range("U+00FF-0000") {
+            Ok(_) => panic!(),
+            Err(err) => assert_eq!(err.to_string(), "Invalid unicode range `U+00FF-0000`"),
+        }
+        assert!(parse_unicode_range("0000-00FF").is_err());
+    }
+
+    #[test]
+    fn test_named_subsets() -> Result<()> {
+        for (subset, _) in super::NAMED_SUBSETS {
+            parse_unicode_range(named_subset_unicode_range(subset)?)?;
+        }
+        assert!(named_subset_unicode_range("klingon").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_subset_font() -> Result<()> {
+        let woff2 = subset_font(NOTO_SANS, "U+0041-005A")?.expect("Noto Sans has latin glyphs");
+        assert_eq!(&woff2[0..4], b"wOF2");
+        assert!(woff2.len() < NOTO_SANS.len());
+
+        let scope = ReadScope::new(&woff2);
+        let font_data = scope.read::<FontData>()?;
+        let mut font = Font::new(font_data.table_provider(0)?)?.expect("Unable to read subset");
+        // `.notdef` and the 26 uppercase letters
+        assert_eq!(font.num_glyphs(), 27);
+        for c in ['A', 'M', 'Z'] {
+            let (glyph_id, _) = font.lookup_glyph_index(c, MatchingPresentation::NotRequired, None);
+            assert_ne!(glyph_id, 0, "{c} is in the subset");
+        }
+        for c in ['a', '0'] {
+            let (glyph_id, _) = font.lookup_glyph_index(c, MatchingPresentation::NotRequired, None);
+            assert_eq!(glyph_id, 0, "{c} isn't in the subset");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_subset_font_without_glyphs_in_range() -> Result<()> {
+        // Noto Sans' latin build has no CJK glyphs
+        assert_eq!(subset_font(NOTO_SANS, "U+4E00-9FFF")?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_subset_font_collection() {
+        let mut collection = b"ttcf".to_vec();
+        collection.extend_from_slice(&NOTO_SANS[4..]);
+
+        match subset_font(&collection, "U+0041-005A") {
+            Ok(_) => panic!(),
+            Err(err) => assert!(err
+                .to_string()
+                .starts_with("Font collections can't be subset")),
+        }
+    }
+}
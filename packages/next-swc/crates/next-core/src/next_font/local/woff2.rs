@@ -0,0 +1,203 @@
+use std::io::Write;
+
+use anyhow::{bail, Context, Result};
+
+const WOFF2_SIGNATURE: u32 = 0x774F_4632; // "wOF2"
+const WOFF2_HEADER_SIZE: usize = 48;
+const SFNT_HEADER_SIZE: usize = 12;
+const SFNT_TABLE_RECORD_SIZE: usize = 16;
+/// The tag index in the table directory flags signalling that an explicit tag
+/// follows the flags.
+const ARBITRARY_TAG_INDEX: u8 = 0x3f;
+/// The transform version of `glyf` and `loca` tables signalling that they're
+/// stored untransformed.
+const NULL_TRANSFORM: u8 = 3;
+const GLYF: [u8; 4] = *b"glyf";
+const LOCA: [u8; 4] = *b"loca";
+
+struct SfntTable<'a> {
+    tag: [u8; 4],
+    data: &'a [u8],
+}
+
+/// Encodes an OpenType or TrueType font as WOFF2.
+///
+/// Tables are stored untransformed, so the size reduction comes from brotli
+/// compression only.
+pub(super) fn sfnt_to_woff2(sfnt: &[u8]) -> Result<Vec<u8>> {
+    let flavor = read_u32(sfnt, 0)?;
+    let tables = read_sfnt_tables(sfnt)?;
+
+    let mut directory = Vec::new();
+    let mut table_data = Vec::new();
+    let mut total_sfnt_size = SFNT_HEADER_SIZE + SFNT_TABLE_RECORD_SIZE * tables.len();
+    for table in &tables {
+        let transform = if table.tag == GLYF || table.tag == LOCA {
+            NULL_TRANSFORM
+        } else {
+            0
+        };
+        directory.push(ARBITRARY_TAG_INDEX | (transform << 6));
+        directory.extend_from_slice(&table.tag);
+        write_uint_base128(&mut directory, table.data.len() as u32);
+
+        table_data.extend_from_slice(table.data);
+        total_sfnt_size += pad4(table.data.len());
+    }
+
+    let mut compressed = Vec::new();
+    {
+        let mut writer = brotli::CompressorWriter::new(&mut compressed, 4096, 11, 22);
+        writer.write_all(&table_data)?;
+    }
+
+    let length = pad4(WOFF2_HEADER_SIZE + directory.len() + compressed.len());
+    let mut woff2 = Vec::with_capacity(length);
+    woff2.extend_from_slice(&WOFF2_SIGNATURE.to_be_bytes());
+    woff2.extend_from_slice(&flavor.to_be_bytes());
+    woff2.extend_from_slice(&(length as u32).to_be_bytes());
+    woff2.extend_from_slice(&(tables.len() as u16).to_be_bytes());
+    // reserved
+    woff2.extend_from_slice(&0u16.to_be_bytes());
+    woff2.extend_from_slice(&(total_sfnt_size as u32).to_be_bytes());
+    woff2.extend_from_slice(&(compressed.len() as u32).to_be_bytes());
+    // majorVersion and minorVersion
+    woff2.extend_from_slice(&1u16.to_be_bytes());
+    woff2.extend_from_slice(&0u16.to_be_bytes());
+    // metaOffset, metaLength, metaOrigLength, privOffset and privLength, as there
+    // is neither metadata nor private data
+    woff2.extend_from_slice(&[0; 20]);
+    woff2.extend_from_slice(&directory);
+    woff2.extend_from_slice(&compressed);
+    woff2.resize(length, 0);
+
+    Ok(woff2)
+}
+
+/// Reads the tables of an sfnt font, sorted by tag, except for `loca` which has
+/// to immediately follow `glyf` in WOFF2.
+fn read_sfnt_tables(sfnt: &[u8]) -> Result<Vec<SfntTable<'_>>> {
+    let num_tables = read_u16(sfnt, 4)? as usize;
+    let mut tables = (0..num_tables)
+        .map(|i| {
+            let record = SFNT_HEADER_SIZE + i * SFNT_TABLE_RECORD_SIZE;
+            let tag = sfnt
+                .get(record..record + 4)
+                .context("sfnt table record out of bounds")?;
+            let offset = read_u32(sfnt, record + 8)? as usize;
+            let length = read_u32(sfnt, record + 12)? as usize;
+            Ok(SfntTable {
+                tag: tag.try_into()?,
+                data: sfnt
+                    .get(offset..offset + length)
+                    .context("sfnt table data out of bounds")?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    tables.sort_by_key(|table| table.tag);
+
+    if let Some(loca) = tables.iter().position(|table| table.tag == LOCA) {
+        let loca = tables.remove(loca);
+        let Some(glyf) = tables.iter().position(|table| table.tag == GLYF) else {
+            bail!("sfnt font has a loca table, but no glyf table");
+        };
+        tables.insert(glyf + 1, loca);
+    }
+
+    Ok(tables)
+}
+
+/// Writes a variable-length UIntBase128 as defined by the WOFF2 specification.
+fn write_uint_base128(out: &mut Vec<u8>, value: u32) {
+    let len = (1..5).find(|i| value >> (7 * i) == 0).unwrap_or(5);
+    for i in (0..len).rev() {
+        let byte = ((value >> (7 * i)) & 0x7f) as u8;
+        out.push(if i == 0 { byte } else { byte | 0x80 });
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    let bytes = data
+        .get(offset..offset + 2)
+        .context("unexpected end of sfnt data")?;
+    Ok(u16::from_be_bytes(bytes.try_into()?))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    let bytes = data
+        .get(offset..offset + 4)
+        .context("unexpected end of sfnt data")?;
+    Ok(u32::from_be_bytes(bytes.try_into()?))
+}
+
+fn pad4(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::{sfnt_to_woff2, write_uint_base128};
+
+    fn sfnt(tables: &[(&[u8; 4], &[u8])]) -> Vec<u8> {
+        let mut sfnt = Vec::new();
+        sfnt.extend_from_slice(&0x0001_0000u32.to_be_bytes());
+        sfnt.extend_from_slice(&(tables.len() as u16).to_be_bytes());
+        sfnt.extend_from_slice(&[0; 6]);
+        let mut offset = 12 + 16 * tables.len();
+        for (tag, data) in tables {
+            sfnt.extend_from_slice(*tag);
+            sfnt.extend_from_slice(&0u32.to_be_bytes());
+            sfnt.extend_from_slice(&(offset as u32).to_be_bytes());
+            sfnt.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            offset += data.len();
+        }
+        for (_, data) in tables {
+            sfnt.extend_from_slice(data);
+        }
+        sfnt
+    }
+
+    #[test]
+    fn test_uint_base128() {
+        let mut out = Vec::new();
+        write_uint_base128(&mut out, 63);
+        assert_eq!(out, vec![63]);
+
+        let mut out = Vec::new();
+        write_uint_base128(&mut out, 128);
+        assert_eq!(out, vec![0x81, 0x00]);
+
+        let mut out = Vec::new();
+        write_uint_base128(&mut out, u32::MAX);
+        assert_eq!(out, vec![0x8f, 0xff, 0xff, 0xff, 0x7f]);
+    }
+
+    #[test]
+    fn test_sfnt_to_woff2() -> Result<()> {
+        let woff2 = sfnt_to_woff2(&sfnt(&[
+            (b"loca", &[0, 0, 0, 3]),
+            (b"head", &[1; 54]),
+            (b"glyf", &[2; 3]),
+        ]))?;
+
+        assert_eq!(&woff2[0..4], b"wOF2");
+        assert_eq!(&woff2[4..8], &0x0001_0000u32.to_be_bytes());
+        assert_eq!(&woff2[8..12], &(woff2.len() as u32).to_be_bytes());
+        assert_eq!(&woff2[12..14], &3u16.to_be_bytes());
+        // 12 + 3 * 16 bytes of headers, and the tables padded to 4 bytes
+        assert_eq!(&woff2[16..20], &(60u32 + 4 + 56 + 4).to_be_bytes());
+        assert_eq!(woff2.len() % 4, 0);
+        // glyf and loca are stored untransformed, with loca following glyf
+        assert_eq!(
+            &woff2[48..66],
+            &[
+                0xff, b'g', b'l', b'y', b'f', 3, //
+                0xff, b'l', b'o', b'c', b'a', 4, //
+                0x3f, b'h', b'e', b'a', b'd', 54,
+            ]
+        );
+        Ok(())
+    }
+}
@@ -1,33 +1,65 @@
+use std::{future::IntoFuture, time::Duration};
+
 use anyhow::{bail, Result};
-use turbo_tasks::{Value, Vc};
-use turbo_tasks_fs::FileSystem;
-use turbopack_binding::turbopack::{
-    core::{
-        asset::AssetContent, ident::AssetIdent, introspect::Introspectable,
-        server_fs::ServerFileSystem, version::VersionedContent,
+use turbo_tasks::{get_invalidator, Value, Vc};
+use turbo_tasks_fs::{File, FileContent, FileSystem, FileSystemPath};
+use turbopack_binding::{
+    turbo::{
+        tasks_fetch::{fetch, FetchErrorKind},
+        tasks_fs::rope::Rope,
     },
-    dev_server::source::{
-        query::QueryValue,
-        route_tree::{RouteTree, RouteType},
-        wrapping_source::{ContentSourceProcessor, WrappedGetContentSourceContent},
-        ContentSource, ContentSourceContent, ContentSourceData, ContentSourceDataFilter,
-        ContentSourceDataVary, GetContentSourceContent, ProxyResult, RewriteBuilder,
+    turbopack::{
+        core::{
+            asset::AssetContent,
+            ident::AssetIdent,
+            introspect::Introspectable,
+            issue::{Issue, IssueExt, IssueSeverity},
+            server_fs::ServerFileSystem,
+            version::VersionedContent,
+        },
+        dev_server::source::{
+            headers::HeaderValue,
+            query::QueryValue,
+            route_tree::{RouteTree, RouteType},
+            wrapping_source::{ContentSourceProcessor, WrappedGetContentSourceContent},
+            Body, ContentSource, ContentSourceContent, ContentSourceData, ContentSourceDataFilter,
+            ContentSourceDataVary, GetContentSourceContent, HeaderList, ProxyResult,
+            RewriteBuilder,
+        },
+        image::process::optimize,
     },
-    image::process::optimize,
 };
+use url::Url;
+
+use super::{
+    format::{encode_image, encoded_image_format, image_format_mime_type, negotiate_image_format},
+    remote_pattern::has_match,
+};
+use crate::next_config::{ImageConfig, ImageFormat};
 
 /// Serves, resizes, optimizes, and re-encodes images to be used with
 /// next/image.
 #[turbo_tasks::value(shared)]
 pub struct NextImageContentSource {
     asset_source: Vc<Box<dyn ContentSource>>,
+    base_path: Vc<String>,
+    image_config: Vc<ImageConfig>,
 }
 
 #[turbo_tasks::value_impl]
 impl NextImageContentSource {
     #[turbo_tasks::function]
-    pub fn new(asset_source: Vc<Box<dyn ContentSource>>) -> Vc<NextImageContentSource> {
-        NextImageContentSource { asset_source }.cell()
+    pub fn new(
+        asset_source: Vc<Box<dyn ContentSource>>,
+        base_path: Vc<String>,
+        image_config: Vc<ImageConfig>,
+    ) -> Vc<NextImageContentSource> {
+        NextImageContentSource {
+            asset_source,
+            base_path,
+            image_config,
+        }
+        .cell()
     }
 }
 
@@ -47,6 +79,9 @@ impl GetContentSourceContent for NextImageContentSource {
             query: Some(ContentSourceDataFilter::Subset(
                 ["url".to_string(), "w".to_string(), "q".to_string()].into(),
             )),
+            headers: Some(ContentSourceDataFilter::Subset(
+                ["accept".to_string()].into(),
+            )),
             ..Default::default()
         }
         .cell()
@@ -89,35 +124,299 @@ impl GetContentSourceContent for NextImageContentSource {
             _ => bail!("missing w query argument"),
         };
 
-        // TODO: re-encode into next-gen formats.
+        let accept = match data
+            .headers
+            .as_ref()
+            .and_then(|headers| headers.get("accept"))
+        {
+            Some(HeaderValue::SingleString(accept)) => accept.clone(),
+            Some(HeaderValue::MultiStrings(accept)) => accept.join(","),
+            _ => String::new(),
+        };
+        let image_config = this.image_config.await?;
+        let format = negotiate_image_format(&accept, &image_config.formats);
 
-        if let Some(path) = url.strip_prefix('/') {
+        if url.starts_with('/') {
+            // Local images are requested with the basePath, but the asset source
+            // serves them without it.
+            let base_path = this.base_path.await?;
+            let path = &url
+                .strip_prefix(base_path.as_str())
+                .filter(|path| path.starts_with('/'))
+                .unwrap_or(url)[1..];
+            if path.ends_with(".svg") && !image_config.dangerously_allow_svg {
+                return Ok(svg_not_allowed_response());
+            }
+            let extension = path.rsplit_once('.').map_or("", |(_, extension)| extension);
+            let format = encoded_image_format(extension, format);
             let sources = this.asset_source.get_routes().get(path.to_string()).await?;
             let sources = sources
                 .iter()
                 .map(|s| {
                     Vc::upcast(WrappedGetContentSourceContent::new(
                         *s,
-                        Vc::upcast(NextImageContentSourceProcessor::new(path.to_string(), w, q)),
+                        Vc::upcast(NextImageContentSourceProcessor::new(
+                            path.to_string(),
+                            w,
+                            q,
+                            format.clone(),
+                            image_config.dangerously_allow_svg,
+                        )),
                     ))
                 })
                 .collect();
             let sources = Vc::cell(sources);
-            return Ok(
-                ContentSourceContent::Rewrite(RewriteBuilder::new_sources(sources).build()).cell(),
-            );
+            return Ok(ContentSourceContent::Rewrite(
+                RewriteBuilder::new_sources(sources)
+                    .response_headers(HeaderList::new(image_response_headers(
+                        &image_config,
+                        path,
+                        format.as_ref(),
+                    )))
+                    .build(),
+            )
+            .cell());
         }
 
-        // TODO: This should be downloaded by the server, and resized, etc.
-        Ok(ContentSourceContent::HttpProxy(
-            ProxyResult {
-                status: 302,
-                headers: vec![("Location".to_string(), url.clone())],
-                body: "".into(),
+        let Some(url) = Url::parse(url)
+            .ok()
+            .filter(|url| matches!(url.scheme(), "http" | "https"))
+        else {
+            return Ok(error_response(400, "\"url\" parameter is invalid"));
+        };
+
+        if !has_match(&image_config.domains, &image_config.remote_patterns, &url)? {
+            return Ok(error_response(400, "\"url\" parameter is not allowed"));
+        }
+
+        Ok(optimize_remote_image(
+            url.to_string(),
+            w,
+            q,
+            format,
+            this.image_config,
+        ))
+    }
+}
+
+/// The headers next.js's image optimizer sends with every image. The
+/// `Content-Security-Policy` and `Content-Disposition` headers prevent SVGs
+/// from running scripts when they are opened directly.
+fn image_response_headers(
+    image_config: &ImageConfig,
+    path: &str,
+    format: Option<&ImageFormat>,
+) -> Vec<(String, String)> {
+    let mut headers = vec![("Vary".to_string(), "Accept".to_string())];
+
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let (stem, extension) = file_name.split_once('.').unwrap_or((file_name, ""));
+    let extension = match format {
+        Some(ImageFormat::Webp) => "webp",
+        Some(ImageFormat::Avif) => "avif",
+        None => extension,
+    };
+    if !stem.is_empty() && !extension.is_empty() {
+        headers.push((
+            "Content-Disposition".to_string(),
+            format!("inline; filename=\"{stem}.{extension}\""),
+        ));
+    }
+
+    if !image_config.content_security_policy.is_empty() {
+        headers.push((
+            "Content-Security-Policy".to_string(),
+            image_config.content_security_policy.clone(),
+        ));
+    }
+
+    headers
+}
+
+/// Whether an image is an SVG, judging by its content type or by sniffing its
+/// bytes the same way next.js's `detectContentType` does. The file extension
+/// alone can't be trusted.
+fn is_svg(content_type: Option<&str>, bytes: &[u8]) -> bool {
+    let svg_content_type = content_type
+        .and_then(|content_type| content_type.split(';').next())
+        .is_some_and(|essence| essence.trim().eq_ignore_ascii_case("image/svg+xml"));
+    svg_content_type || bytes.starts_with(b"<?xml") || bytes.starts_with(b"<svg")
+}
+
+fn svg_not_allowed_response() -> Vc<ContentSourceContent> {
+    error_response(
+        400,
+        "\"url\" parameter is valid but image type is not allowed",
+    )
+}
+
+fn error_response(status: u16, message: &str) -> Vc<ContentSourceContent> {
+    ContentSourceContent::HttpProxy(
+        ProxyResult {
+            status,
+            headers: vec![("Content-Type".to_string(), "text/plain".to_string())],
+            body: message.to_string().into(),
+        }
+        .cell(),
+    )
+    .cell()
+}
+
+/// Remote images that take longer than this to download are answered with an
+/// error rather than blocking the request.
+const REMOTE_IMAGE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Downloads, resizes and re-encodes a remote image.
+///
+/// The download goes through turbo-tasks-fetch, which keeps the response for
+/// the session and doesn't expose its headers, so the image is served with a
+/// `max-age` of `minimum_cache_ttl` seconds.
+#[turbo_tasks::function]
+async fn optimize_remote_image(
+    url: String,
+    width: u32,
+    quality: u8,
+    format: Option<ImageFormat>,
+    image_config: Vc<ImageConfig>,
+) -> Result<Vc<ContentSourceContent>> {
+    let image_config = image_config.await?;
+    let url_path = Url::parse(&url)?.path().to_string();
+    let path = ServerFileSystem::new()
+        .root()
+        .join(url_path[1..].to_string());
+
+    let response = fetch(Vc::cell(url.clone()), Vc::cell(None));
+    let Ok(response) = tokio::time::timeout(REMOTE_IMAGE_TIMEOUT, response.into_future()).await
+    else {
+        RemoteImageFetchIssue {
+            path,
+            url,
+            detail: format!(
+                "the request timed out after {} seconds",
+                REMOTE_IMAGE_TIMEOUT.as_secs()
+            ),
+        }
+        .cell()
+        .emit();
+        // The request keeps running, so the image is optimized once it's
+        // downloaded.
+        get_invalidator().invalidate();
+        return Ok(error_response(504, "unable to fetch \"url\""));
+    };
+    let response = match &*response? {
+        Ok(response) => response.await?,
+        Err(err) => {
+            if let FetchErrorKind::Status(status) = *err.await?.kind.await? {
+                return Ok(error_response(
+                    status,
+                    "\"url\" parameter is valid but upstream response is invalid",
+                ));
             }
-            .cell(),
+            err.to_issue(IssueSeverity::Error.into(), path).emit();
+            return Ok(error_response(500, "unable to fetch \"url\""));
+        }
+    };
+    let body = response.body.await?;
+
+    let svg = is_svg(None, &body.0) || url_path.ends_with(".svg");
+    if svg && !image_config.dangerously_allow_svg {
+        return Ok(svg_not_allowed_response());
+    }
+    let content = FileContent::Content(File::from(Rope::from(body.0.clone()))).cell();
+    // SVGs are served untouched.
+    let format = if svg { None } else { format };
+    let mut optimized = if svg {
+        content
+    } else {
+        optimize(
+            AssetIdent::from_path(path),
+            content,
+            width,
+            u32::MAX,
+            quality,
         )
-        .cell())
+    };
+    if let Some(format) = format.clone() {
+        optimized = encode_image(optimized, format, quality);
+    }
+    let optimized = optimized.await?;
+    let FileContent::Content(file) = &*optimized else {
+        return Ok(error_response(
+            500,
+            "\"url\" parameter is valid but upstream response is invalid",
+        ));
+    };
+    // Images that could not be encoded keep their source format.
+    let format = format.filter(|format| {
+        file.content_type().is_some_and(|content_type| {
+            content_type.essence_str() == image_format_mime_type(format)
+        })
+    });
+
+    let mut headers = image_response_headers(&image_config, &url_path, format.as_ref());
+    headers.push((
+        "Cache-Control".to_string(),
+        format!(
+            "public, max-age={}, must-revalidate",
+            image_config.minimum_cache_ttl
+        ),
+    ));
+    let content_type = match file.content_type() {
+        Some(content_type) => Some(content_type.to_string()),
+        None if svg => Some("image/svg+xml".to_string()),
+        None => path
+            .await?
+            .extension()
+            .and_then(|extension| mime_guess::from_ext(extension).first())
+            .map(|content_type| content_type.to_string()),
+    };
+    if let Some(content_type) = content_type {
+        headers.push(("Content-Type".to_string(), content_type));
+    }
+    Ok(ContentSourceContent::HttpProxy(
+        ProxyResult {
+            status: 200,
+            headers,
+            body: Body::from(file.content().to_bytes()?.into_owned()),
+        }
+        .cell(),
+    )
+    .cell())
+}
+
+/// A remote image that could not be fetched.
+#[turbo_tasks::value(shared)]
+struct RemoteImageFetchIssue {
+    path: Vc<FileSystemPath>,
+    url: String,
+    detail: String,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for RemoteImageFetchIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("image".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell("Error while requesting remote image".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(format!("Failed to fetch `{}`: {}", self.url, self.detail))
     }
 }
 
@@ -139,16 +438,26 @@ struct NextImageContentSourceProcessor {
     path: String,
     width: u32,
     quality: u8,
+    format: Option<ImageFormat>,
+    allow_svg: bool,
 }
 
 #[turbo_tasks::value_impl]
 impl NextImageContentSourceProcessor {
     #[turbo_tasks::function]
-    pub fn new(path: String, width: u32, quality: u8) -> Vc<NextImageContentSourceProcessor> {
+    pub fn new(
+        path: String,
+        width: u32,
+        quality: u8,
+        format: Option<ImageFormat>,
+        allow_svg: bool,
+    ) -> Vc<NextImageContentSourceProcessor> {
         NextImageContentSourceProcessor {
             path,
             width,
             quality,
+            format,
+            allow_svg,
         }
         .cell()
     }
@@ -166,15 +475,47 @@ impl ContentSourceProcessor for NextImageContentSourceProcessor {
         let AssetContent::File(file_content) = *asset_content else {
             return Ok(content);
         };
-        let optimized_file_content = optimize(
+        // SVGs are served untouched, if they are allowed at all.
+        if let FileContent::Content(file) = &*file_content.await? {
+            let content_type = file
+                .content_type()
+                .map(|content_type| content_type.to_string());
+            if self.path.ends_with(".svg")
+                || is_svg(content_type.as_deref(), &file.content().to_bytes()?)
+            {
+                return Ok(if self.allow_svg {
+                    content
+                } else {
+                    svg_not_allowed_response()
+                });
+            }
+        }
+        let mut optimized_file_content = optimize(
             AssetIdent::from_path(ServerFileSystem::new().root().join(self.path.clone())),
             file_content,
             self.width,
             u32::MAX,
             self.quality,
         );
+        if let Some(format) = self.format.clone() {
+            optimized_file_content = encode_image(optimized_file_content, format, self.quality);
+        }
         Ok(ContentSourceContent::static_content(
             AssetContent::File(optimized_file_content).into(),
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::is_svg;
+
+    #[test]
+    fn sniffs_svgs() {
+        assert!(is_svg(None, b"<svg></svg>"));
+        assert!(is_svg(None, b"<?xml version=\"1.0\"?><svg></svg>"));
+        assert!(is_svg(Some("image/svg+xml; charset=utf-8"), b""));
+        assert!(!is_svg(Some("image/png"), b"\x89PNG\r\n"));
+        assert!(!is_svg(None, b""));
+    }
+}
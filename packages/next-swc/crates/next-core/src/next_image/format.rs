@@ -0,0 +1,182 @@
+use anyhow::Result;
+use turbo_tasks::Vc;
+use turbo_tasks_fs::FileContent;
+
+use crate::next_config::ImageFormat;
+
+/// The MIME type an image is served with when encoded to `format`.
+pub fn image_format_mime_type(format: &ImageFormat) -> &'static str {
+    match format {
+        ImageFormat::Webp => "image/webp",
+        ImageFormat::Avif => "image/avif",
+    }
+}
+
+/// Whether this build is able to encode images to `format`.
+fn is_format_enabled(format: &ImageFormat) -> bool {
+    match format {
+        ImageFormat::Webp => cfg!(feature = "image-webp"),
+        ImageFormat::Avif => cfg!(feature = "image-avif"),
+    }
+}
+
+/// Picks the first of the configured `formats` the client explicitly accepts,
+/// similar to `getSupportedMimeType` in next.js's image optimizer.
+///
+/// Returns `None` when the image should keep its source format.
+pub fn negotiate_image_format(accept: &str, formats: &[ImageFormat]) -> Option<ImageFormat> {
+    let accepted: Vec<&str> = accept
+        .split(',')
+        .filter_map(|media_range| {
+            let mut parts = media_range.split(';').map(str::trim);
+            let media_type = parts.next()?;
+            let rejected = parts.any(|param| {
+                param
+                    .strip_prefix("q=")
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .is_some_and(|q| q <= 0.0)
+            });
+            (!rejected).then_some(media_type)
+        })
+        .collect();
+
+    formats
+        .iter()
+        .filter(|format| is_format_enabled(format))
+        .find(|format| accepted.contains(&image_format_mime_type(format)))
+        .cloned()
+}
+
+/// Returns `format` if an image with the file `extension` can be re-encoded
+/// to it. Otherwise the image keeps its source format, and with it the
+/// extension and content type it is served with.
+pub fn encoded_image_format(extension: &str, format: Option<ImageFormat>) -> Option<ImageFormat> {
+    let format = format.filter(is_format_enabled)?;
+    let encodable = match extension.to_ascii_lowercase().as_str() {
+        "png" | "jpg" | "jpeg" => true,
+        "webp" => format != ImageFormat::Webp,
+        _ => false,
+    };
+    encodable.then_some(format)
+}
+
+/// Re-encodes an (already resized) image to `format`. Images that can't be
+/// re-encoded, like SVGs or animated GIFs, are returned unchanged.
+#[turbo_tasks::function]
+pub async fn encode_image(
+    content: Vc<FileContent>,
+    format: ImageFormat,
+    quality: u8,
+) -> Result<Vc<FileContent>> {
+    #[cfg(any(feature = "image-webp", feature = "image-avif"))]
+    {
+        use turbo_tasks_fs::File;
+
+        let FileContent::Content(file) = &*content.await? else {
+            return Ok(content);
+        };
+        if let Some(encoded) = encode(&file.content().to_bytes()?, &format, quality)? {
+            return Ok(FileContent::Content(
+                File::from(encoded).with_content_type(image_format_mime_type(&format).parse()?),
+            )
+            .cell());
+        }
+    }
+    #[cfg(not(any(feature = "image-webp", feature = "image-avif")))]
+    let _ = (format, quality);
+
+    Ok(content)
+}
+
+#[cfg(any(feature = "image-webp", feature = "image-avif"))]
+fn encode(bytes: &[u8], format: &ImageFormat, quality: u8) -> Result<Option<Vec<u8>>> {
+    use image::{ColorType, ImageFormat as SourceFormat};
+
+    let Ok(source_format) = image::guess_format(bytes) else {
+        return Ok(None);
+    };
+    let already_encoded = match format {
+        ImageFormat::Webp => source_format == SourceFormat::WebP,
+        ImageFormat::Avif => source_format == SourceFormat::Avif,
+    };
+    if already_encoded
+        || !matches!(
+            source_format,
+            SourceFormat::Png | SourceFormat::Jpeg | SourceFormat::WebP
+        )
+    {
+        return Ok(None);
+    }
+    let Ok(image) = image::load_from_memory_with_format(bytes, source_format) else {
+        return Ok(None);
+    };
+    let image = image.to_rgba8();
+
+    let mut buf = Vec::new();
+    match format {
+        #[cfg(feature = "image-webp")]
+        ImageFormat::Webp => {
+            use image::codecs::webp::{WebPEncoder, WebPQuality};
+
+            WebPEncoder::new_with_quality(&mut buf, WebPQuality::lossy(quality)).encode(
+                &image,
+                image.width(),
+                image.height(),
+                ColorType::Rgba8,
+            )?;
+        }
+        #[cfg(feature = "image-avif")]
+        ImageFormat::Avif => {
+            use image::{codecs::avif::AvifEncoder, ImageEncoder};
+
+            AvifEncoder::new_with_speed_quality(&mut buf, 6, quality).write_image(
+                &image,
+                image.width(),
+                image.height(),
+                ColorType::Rgba8,
+            )?;
+        }
+        #[allow(unreachable_patterns)]
+        _ => return Ok(None),
+    }
+    Ok(Some(buf))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encoded_image_format, negotiate_image_format};
+    use crate::next_config::ImageFormat;
+
+    const AVIF: Option<ImageFormat> = if cfg!(feature = "image-avif") {
+        Some(ImageFormat::Avif)
+    } else {
+        None
+    };
+    const WEBP: Option<ImageFormat> = if cfg!(feature = "image-webp") {
+        Some(ImageFormat::Webp)
+    } else {
+        None
+    };
+
+    #[test]
+    fn negotiates_configured_formats() {
+        let formats = [ImageFormat::Avif, ImageFormat::Webp];
+        let accept = "image/avif,image/webp,image/apng,image/*,*/*;q=0.8";
+
+        assert_eq!(negotiate_image_format("*/*", &formats), None);
+        assert_eq!(negotiate_image_format("image/webp;q=0", &formats), None);
+        assert_eq!(negotiate_image_format(accept, &formats), AVIF.or(WEBP));
+        assert_eq!(negotiate_image_format(accept, &[ImageFormat::Webp]), WEBP);
+        assert_eq!(negotiate_image_format("image/webp", &formats), WEBP);
+    }
+
+    #[test]
+    fn keeps_source_format_when_it_cannot_be_encoded() {
+        assert_eq!(encoded_image_format("png", Some(ImageFormat::Webp)), WEBP);
+        assert_eq!(encoded_image_format("JPG", Some(ImageFormat::Avif)), AVIF);
+        assert_eq!(encoded_image_format("webp", Some(ImageFormat::Avif)), AVIF);
+        assert_eq!(encoded_image_format("webp", Some(ImageFormat::Webp)), None);
+        assert_eq!(encoded_image_format("gif", Some(ImageFormat::Webp)), None);
+        assert_eq!(encoded_image_format("png", None), None);
+    }
+}
@@ -0,0 +1,94 @@
+use anyhow::Result;
+use turbo_tasks_fs::glob::Glob;
+use url::Url;
+
+use crate::next_config::{RemotePattern, RemotePatternProtocal};
+
+/// Checks whether `url` matches `pattern`, following
+/// next.js/packages/next/src/shared/lib/match-remote-pattern.ts.
+///
+/// In hostnames, `*` matches a single subdomain and `**` any number of them.
+/// In pathnames, they match a single segment and any number of segments.
+pub fn match_remote_pattern(pattern: &RemotePattern, url: &Url) -> Result<bool> {
+    if let Some(protocol) = &pattern.protocol {
+        let expected = match protocol {
+            RemotePatternProtocal::Http => "http",
+            RemotePatternProtocal::Https => "https",
+        };
+        if url.scheme() != expected {
+            return Ok(false);
+        }
+    }
+
+    if let Some(port) = &pattern.port {
+        let actual = url.port().map(|port| port.to_string()).unwrap_or_default();
+        if *port != actual {
+            return Ok(false);
+        }
+    }
+
+    // Match hostnames as paths of their labels, so that wildcards don't match
+    // across dots.
+    let hostname = url.host_str().unwrap_or_default().replace('.', "/");
+    if !Glob::parse(&pattern.hostname.replace('.', "/"))?.execute(&hostname) {
+        return Ok(false);
+    }
+
+    let pathname = pattern.pathname.as_deref().unwrap_or("**");
+    Glob::parse(pathname).map(|glob| glob.execute(url.path()))
+}
+
+/// Checks whether `url` is allowed by the `images.domains` or
+/// `images.remotePatterns` config.
+pub fn has_match(domains: &[String], remote_patterns: &[RemotePattern], url: &Url) -> Result<bool> {
+    if domains
+        .iter()
+        .any(|domain| url.host_str() == Some(domain.as_str()))
+    {
+        return Ok(true);
+    }
+    for pattern in remote_patterns {
+        if match_remote_pattern(pattern, url)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::has_match;
+    use crate::next_config::{RemotePattern, RemotePatternProtocal};
+
+    #[test]
+    fn matches_domains_and_remote_patterns() {
+        let domains = vec!["example.com".to_string()];
+        let remote_patterns = vec![
+            RemotePattern {
+                hostname: "*.example.org".to_string(),
+                protocol: Some(RemotePatternProtocal::Https),
+                port: None,
+                pathname: Some("/images/**".to_string()),
+            },
+            RemotePattern {
+                hostname: "127.0.0.1".to_string(),
+                protocol: None,
+                port: Some("8080".to_string()),
+                pathname: None,
+            },
+        ];
+        let allowed =
+            |url: &str| has_match(&domains, &remote_patterns, &Url::parse(url).unwrap()).unwrap();
+
+        assert!(allowed("https://example.com/a.png"));
+        assert!(!allowed("https://sub.example.com/a.png"));
+        assert!(allowed("https://cdn.example.org/images/a/b.png"));
+        assert!(!allowed("http://cdn.example.org/images/a.png"));
+        assert!(!allowed("https://a.cdn.example.org/images/a.png"));
+        assert!(!allowed("https://cdn.example.org/other/a.png"));
+        assert!(allowed("http://127.0.0.1:8080/a.png"));
+        assert!(!allowed("http://127.0.0.1:3000/a.png"));
+    }
+}
@@ -26,7 +26,10 @@ use crate::{
     next_config::NextConfig,
     next_font::{
         google::{NextFontGoogleCssModuleReplacer, NextFontGoogleReplacer},
-        local::{NextFontLocalCssModuleReplacer, NextFontLocalReplacer},
+        local::{
+            NextFontLocalCssModuleReplacer, NextFontLocalReplacer, NextFontLocalSubsetReplacer,
+            SUBSET_REQUEST,
+        },
     },
     next_server::context::ServerContextType,
     util::NextRuntime,
@@ -631,6 +634,8 @@ pub async fn insert_next_shared_aliases(
         ImportMapping::Dynamic(Vc::upcast(NextFontGoogleCssModuleReplacer::new(
             project_path,
             execution_context,
+            next_config.dist_dir(),
+            mode,
         )))
         .into(),
     );
@@ -655,6 +660,12 @@ pub async fn insert_next_shared_aliases(
         .into(),
     );
 
+    import_map.insert_alias(
+        // Request path from the next/font/local stylesheet for subsets of font files
+        AliasPattern::exact(SUBSET_REQUEST),
+        ImportMapping::Dynamic(Vc::upcast(NextFontLocalSubsetReplacer::new())).into(),
+    );
+
     import_map.insert_singleton_alias("@swc/helpers", get_next_package(project_path));
     import_map.insert_singleton_alias("styled-jsx", get_next_package(project_path));
     import_map.insert_singleton_alias("next", project_path);
@@ -1,12 +1,15 @@
 //! Type definitions for the Next.js manifest formats.
 
 pub(crate) mod client_reference_manifest;
+pub(crate) mod subresource_integrity_manifest;
 
 use std::collections::HashMap;
 
 use serde::Serialize;
 
-use crate::next_config::Rewrites;
+use crate::next_config::{
+    Header, I18NConfig, NextConfig, Redirect, Rewrites, RouteHas as ConfigRouteHas,
+};
 
 #[derive(Serialize, Default, Debug)]
 pub struct PagesManifest {
@@ -89,16 +92,20 @@ pub struct EdgeFunctionDefinition {
     pub name: String,
     pub page: String,
     pub matchers: Vec<MiddlewareMatcher>,
-    // TODO: AssetBinding[]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub wasm: Option<Vec<()>>,
-    // TODO: AssetBinding[]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub assets: Option<Vec<()>>,
+    pub wasm: Vec<AssetBinding>,
+    pub assets: Vec<AssetBinding>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub regions: Option<Regions>,
 }
 
+/// A file that is made available to an edge function under a name.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetBinding {
+    pub name: String,
+    pub file_path: String,
+}
+
 #[derive(Serialize, Debug)]
 #[serde(untagged)]
 pub enum Regions {
@@ -120,10 +127,12 @@ pub struct ReactLoadableManifest {
     pub manifest: HashMap<String, ReactLoadableManifestEntry>,
 }
 
-#[derive(Serialize, Default, Debug)]
+#[derive(Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ReactLoadableManifestEntry {
-    pub id: u32,
+    /// Client module ID of the dynamically imported module.
+    pub id: ModuleId,
+    /// Client chunks of the dynamically imported module.
     pub files: Vec<String>,
 }
 
@@ -148,9 +157,11 @@ pub struct AppPathsManifest {
 #[derive(Serialize, Default, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ServerReferenceManifest {
-    #[serde(flatten)]
+    /// Actions of pages rendered in the Node.js runtime.
+    #[serde(rename = "node")]
     pub server_actions: ActionManifest,
-    #[serde(flatten)]
+    /// Actions of pages rendered in the edge runtime.
+    #[serde(rename = "edge")]
     pub edge_server_actions: ActionManifest,
 }
 
@@ -164,7 +175,17 @@ pub struct ActionManifest {
 #[derive(Serialize, Default, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ActionManifestEntry {
+    /// Mapping of page to the module ID of the loader exposing the action.
     pub workers: HashMap<String, ActionManifestWorkerEntry>,
+    /// Mapping of page to the layer the action is loaded in.
+    pub layer: HashMap<String, ActionLayer>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum ActionLayer {
+    Rsc,
+    ActionBrowser,
 }
 
 #[derive(Serialize, Debug)]
@@ -238,6 +259,113 @@ pub struct AppBuildManifest {
     pub pages: HashMap<String, Vec<String>>,
 }
 
+/// Maps client files to their `integrity` attribute values, for
+/// `experimental.sri`.
+#[derive(Serialize, Default, Debug)]
+pub struct SubresourceIntegrityManifest {
+    #[serde(flatten)]
+    pub files: HashMap<String, String>,
+}
+
+/// The files and config the server needs at runtime, which are copied into
+/// `.next/standalone` for `output: "standalone"`.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RequiredServerFiles<'a> {
+    pub version: u32,
+    pub config: &'a NextConfig,
+    pub app_dir: &'a str,
+    pub relative_app_dir: &'a str,
+    /// Paths relative to the app dir.
+    pub files: Vec<String>,
+    pub ignore: Vec<String>,
+}
+
+/// Maps the original names of app pages and routes to their pathnames.
+#[derive(Serialize, Default, Debug)]
+pub struct AppPathRoutesManifest {
+    #[serde(flatten)]
+    pub routes: HashMap<String, String>,
+}
+
+/// Describes how the build is statically exported with `output: "export"`.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportMarker {
+    pub version: u32,
+    pub has_export_path_map: bool,
+    pub export_trailing_slash: bool,
+    pub is_next_image_imported: bool,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutesManifest<'a> {
+    pub version: u32,
+    pub base_path: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub i18n: Option<&'a I18NConfig>,
+    pub headers: Vec<RoutesManifestHeader<'a>>,
+    pub redirects: Vec<RoutesManifestRedirect<'a>>,
+}
+
+impl<'a> Default for RoutesManifest<'a> {
+    fn default() -> Self {
+        Self {
+            version: 3,
+            base_path: "",
+            i18n: None,
+            headers: Default::default(),
+            redirects: Default::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutesManifestHeader<'a> {
+    #[serde(flatten)]
+    pub header: &'a Header,
+    /// The source compiled into a regular expression.
+    pub regex: String,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutesManifestRedirect<'a> {
+    pub source: &'a str,
+    pub destination: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_path: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has: Option<&'a Vec<ConfigRouteHas>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub missing: Option<&'a Vec<ConfigRouteHas>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub internal: Option<bool>,
+    pub status_code: u16,
+    /// The source compiled into a regular expression.
+    pub regex: String,
+}
+
+impl<'a> RoutesManifestRedirect<'a> {
+    pub fn new(redirect: &'a Redirect, regex: String) -> Self {
+        Self {
+            source: &redirect.source,
+            destination: &redirect.destination,
+            base_path: redirect.base_path,
+            locale: redirect.locale,
+            has: redirect.has.as_ref(),
+            missing: redirect.missing.as_ref(),
+            internal: redirect.internal,
+            status_code: redirect.status_code(),
+            regex,
+        }
+    }
+}
+
 // TODO(alexkirsz) Unify with the one for dev.
 #[derive(Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
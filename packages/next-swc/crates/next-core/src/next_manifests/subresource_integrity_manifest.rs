@@ -0,0 +1,73 @@
+use anyhow::{Context, Result};
+use base64::{display::Base64Display, engine::general_purpose::STANDARD};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use turbo_tasks::{TryFlatJoinIterExt, Vc};
+use turbo_tasks_fs::FileSystemPath;
+use turbopack_binding::turbopack::core::{
+    asset::{Asset, AssetContent},
+    output::OutputAsset,
+};
+
+use super::SubresourceIntegrityManifest;
+use crate::next_config::SubResourceIntegrityAlgorithm;
+
+impl SubresourceIntegrityManifest {
+    /// Adds the digests of the given client assets, keyed by their path
+    /// relative to `client_relative_path`.
+    pub async fn add_assets(
+        &mut self,
+        assets: impl IntoIterator<Item = Vc<Box<dyn OutputAsset>>>,
+        client_relative_path: &FileSystemPath,
+        algorithm: SubResourceIntegrityAlgorithm,
+    ) -> Result<()> {
+        let files = assets
+            .into_iter()
+            .map(|asset| async move {
+                let path = asset.ident().path().await?;
+                let Some(file) = client_relative_path.get_path_to(&path) else {
+                    return Ok(None);
+                };
+                let AssetContent::File(content) = *asset.content().await? else {
+                    return Ok(None);
+                };
+                let content = content.await?;
+                let content = content
+                    .as_content()
+                    .with_context(|| format!("client asset {file} has no content"))?;
+                Ok(Some((
+                    file.to_string(),
+                    integrity(&content.content().to_bytes()?, algorithm),
+                )))
+            })
+            .try_flat_join()
+            .await?;
+        self.files.extend(files);
+        Ok(())
+    }
+}
+
+/// Formats the digest of `content` as an `integrity` attribute value.
+fn integrity(content: &[u8], algorithm: SubResourceIntegrityAlgorithm) -> String {
+    let (prefix, digest) = match algorithm {
+        SubResourceIntegrityAlgorithm::Sha256 => ("sha256", Sha256::digest(content).to_vec()),
+        SubResourceIntegrityAlgorithm::Sha384 => ("sha384", Sha384::digest(content).to_vec()),
+        SubResourceIntegrityAlgorithm::Sha512 => ("sha512", Sha512::digest(content).to_vec()),
+    };
+    format!("{prefix}-{}", Base64Display::new(&digest, &STANDARD))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_integrity() {
+        assert_eq!(
+            integrity(
+                b"alert('Hello, world.');",
+                SubResourceIntegrityAlgorithm::Sha384
+            ),
+            "sha384-H8BRh8j48O9oYatfu5AZzq6A9RINhZO5H16dQZngK7T62em8MUt1FLm52t+eX6xO"
+        );
+    }
+}
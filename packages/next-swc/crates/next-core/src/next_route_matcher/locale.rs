@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use turbopack_binding::turbopack::node::route_matcher::{Params, RouteMatcherRef};
+
+use crate::i18n::normalize_locale_path;
+
+/// A composite route matcher that matches a path starting with one of the
+/// configured locales, e.g. `fr/about`, when the path without the locale
+/// matches.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct LocaleMatcher<T>
+where
+    T: RouteMatcherRef,
+{
+    locales: Vec<String>,
+    inner: T,
+}
+
+impl<T> LocaleMatcher<T>
+where
+    T: RouteMatcherRef,
+{
+    /// Creates a new [LocaleMatcher].
+    pub fn new(locales: Vec<String>, inner: T) -> Self {
+        Self { locales, inner }
+    }
+
+    fn strip_locale(&self, path: &str) -> Option<String> {
+        let path_locale = normalize_locale_path(&format!("/{path}"), &self.locales);
+        path_locale.detected_locale?;
+        Some(path_locale.pathname[1..].to_string())
+    }
+}
+
+impl<T> RouteMatcherRef for LocaleMatcher<T>
+where
+    T: RouteMatcherRef,
+{
+    fn matches(&self, path: &str) -> bool {
+        if let Some(path) = self.strip_locale(path) {
+            self.inner.matches(&path)
+        } else {
+            false
+        }
+    }
+
+    fn params(&self, path: &str) -> Params {
+        if let Some(path) = self.strip_locale(path) {
+            self.inner.params(&path)
+        } else {
+            Params(None)
+        }
+    }
+}
@@ -4,15 +4,19 @@ use turbopack_binding::turbopack::node::route_matcher::{Params, RouteMatcher, Ro
 
 use self::{
     all::AllMatch,
+    locale::LocaleMatcher,
     path_regex::{PathRegex, PathRegexBuilder},
     prefix_suffix::PrefixSuffixMatcher,
 };
+use crate::util::remove_trailing_slash;
 
 mod all;
+mod locale;
 mod path_regex;
 mod prefix_suffix;
 
-/// A route matcher that matches a path against an exact route.
+/// A route matcher that matches a path against an exact route. A trailing slash
+/// is ignored, as with `trailingSlash: true` pages are requested with one.
 #[turbo_tasks::value]
 pub(crate) struct NextExactMatcher {
     path: Vc<String>,
@@ -30,20 +34,23 @@ impl NextExactMatcher {
 impl RouteMatcher for NextExactMatcher {
     #[turbo_tasks::function]
     async fn matches(&self, path: String) -> Result<Vc<bool>> {
-        Ok(Vc::cell(path == *self.path.await?))
+        Ok(Vc::cell(remove_trailing_slash(&path) == *self.path.await?))
     }
 
     #[turbo_tasks::function]
     async fn params(&self, path: String) -> Result<Vc<Params>> {
-        Ok(Vc::cell(if path == *self.path.await? {
-            Some(Default::default())
-        } else {
-            None
-        }))
+        Ok(Vc::cell(
+            if remove_trailing_slash(&path) == *self.path.await? {
+                Some(Default::default())
+            } else {
+                None
+            },
+        ))
     }
 }
 
-/// A route matcher that matches a path against a route regex.
+/// A route matcher that matches a path against a route regex, ignoring a
+/// trailing slash.
 #[turbo_tasks::value]
 pub(crate) struct NextParamsMatcher {
     #[turbo_tasks(trace_ignore)]
@@ -64,12 +71,12 @@ impl NextParamsMatcher {
 impl RouteMatcher for NextParamsMatcher {
     #[turbo_tasks::function]
     fn matches(&self, path: String) -> Vc<bool> {
-        Vc::cell(self.matcher.matches(&path))
+        Vc::cell(self.matcher.matches(remove_trailing_slash(&path)))
     }
 
     #[turbo_tasks::function]
     fn params(&self, path: String) -> Vc<Params> {
-        Params::cell(self.matcher.params(&path))
+        Params::cell(self.matcher.params(remove_trailing_slash(&path)))
     }
 }
 
@@ -110,6 +117,84 @@ impl RouteMatcher for NextPrefixSuffixParamsMatcher {
     }
 }
 
+/// A route matcher that matches a path prefixed with one of the i18n locales
+/// against a route regex.
+#[turbo_tasks::value]
+pub(crate) struct NextLocaleParamsMatcher {
+    #[turbo_tasks(trace_ignore)]
+    matcher: LocaleMatcher<PathRegex>,
+}
+
+#[turbo_tasks::value_impl]
+impl NextLocaleParamsMatcher {
+    #[turbo_tasks::function]
+    pub async fn new(path: Vc<String>, locales: Vc<Vec<String>>) -> Result<Vc<Self>> {
+        Ok(Self::cell(NextLocaleParamsMatcher {
+            matcher: LocaleMatcher::new(
+                locales.await?.clone_value(),
+                build_path_regex(path.await?.as_str())?,
+            ),
+        }))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl RouteMatcher for NextLocaleParamsMatcher {
+    #[turbo_tasks::function]
+    fn matches(&self, path: String) -> Vc<bool> {
+        Vc::cell(self.matcher.matches(&path))
+    }
+
+    #[turbo_tasks::function]
+    fn params(&self, path: String) -> Vc<Params> {
+        Params::cell(self.matcher.params(&path))
+    }
+}
+
+/// A route matcher that strips a prefix and a suffix from a path, and then
+/// matches it against a route regex when it starts with one of the i18n
+/// locales.
+#[turbo_tasks::value]
+pub(crate) struct NextLocalePrefixSuffixParamsMatcher {
+    #[turbo_tasks(trace_ignore)]
+    matcher: PrefixSuffixMatcher<LocaleMatcher<PathRegex>>,
+}
+
+#[turbo_tasks::value_impl]
+impl NextLocalePrefixSuffixParamsMatcher {
+    #[turbo_tasks::function]
+    pub async fn new(
+        path: Vc<String>,
+        locales: Vc<Vec<String>>,
+        prefix: String,
+        suffix: String,
+    ) -> Result<Vc<Self>> {
+        Ok(Self::cell(NextLocalePrefixSuffixParamsMatcher {
+            matcher: PrefixSuffixMatcher::new(
+                prefix,
+                suffix,
+                LocaleMatcher::new(
+                    locales.await?.clone_value(),
+                    build_path_regex(path.await?.as_str())?,
+                ),
+            ),
+        }))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl RouteMatcher for NextLocalePrefixSuffixParamsMatcher {
+    #[turbo_tasks::function]
+    fn matches(&self, path: String) -> Vc<bool> {
+        Vc::cell(self.matcher.matches(&path))
+    }
+
+    #[turbo_tasks::function]
+    fn params(&self, path: String) -> Vc<Params> {
+        Params::cell(self.matcher.params(&path))
+    }
+}
+
 /// A route matcher that matches against all paths.
 #[turbo_tasks::value]
 pub(crate) struct NextFallbackMatcher {
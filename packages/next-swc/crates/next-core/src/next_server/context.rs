@@ -47,12 +47,12 @@ use crate::{
     next_server::resolve::ExternalPredicate,
     next_shared::{
         resolve::{
-            ModuleFeatureReportResolvePlugin, NextExternalResolvePlugin,
-            UnsupportedModulesResolvePlugin,
+            ModuleFeatureReportResolvePlugin, NextBarrelOptimizeResolvePlugin,
+            NextExternalResolvePlugin, UnsupportedModulesResolvePlugin,
         },
         transforms::{
-            emotion::get_emotion_transform_plugin, get_relay_transform_plugin,
-            styled_components::get_styled_components_transform_plugin,
+            emotion::get_emotion_transform_plugin, get_react_remove_properties_transform_plugin,
+            get_relay_transform_plugin, styled_components::get_styled_components_transform_plugin,
             styled_jsx::get_styled_jsx_transform_plugin,
             swc_ecma_transform_plugins::get_swc_ecma_transform_plugin,
         },
@@ -124,11 +124,15 @@ pub async fn get_server_resolve_options_context(
     );
 
     let next_external_plugin = NextExternalResolvePlugin::new(project_path);
+    // This needs to run before the externals plugins, which would otherwise
+    // externalize the barrel optimization requests.
+    let barrel_optimize_plugin = NextBarrelOptimizeResolvePlugin::new(project_path);
 
     let plugins = match ty {
         ServerContextType::Pages { .. } | ServerContextType::PagesData { .. } => {
             vec![
                 Vc::upcast(module_feature_report_resolve_plugin),
+                Vc::upcast(barrel_optimize_plugin),
                 Vc::upcast(external_cjs_modules_plugin),
                 Vc::upcast(unsupported_modules_resolve_plugin),
                 Vc::upcast(next_external_plugin),
@@ -140,6 +144,7 @@ pub async fn get_server_resolve_options_context(
         | ServerContextType::Middleware { .. } => {
             vec![
                 Vc::upcast(module_feature_report_resolve_plugin),
+                Vc::upcast(barrel_optimize_plugin),
                 Vc::upcast(server_component_externals_plugin),
                 Vc::upcast(unsupported_modules_resolve_plugin),
                 Vc::upcast(next_external_plugin),
@@ -238,6 +243,9 @@ pub async fn get_server_module_options_context(
     let styled_components_transform_plugin =
         *get_styled_components_transform_plugin(next_config).await?;
     let styled_jsx_transform_plugin = *get_styled_jsx_transform_plugin().await?;
+    // Server actions are only supported in the app dir, where they are handled
+    // by the Server Actions transform rule. Like on the client, pages report an
+    // issue for them.
     let server_directive_transform_plugin =
         Some(Vc::cell(Box::new(ServerDirectiveTransformer::new(
             // ServerDirective is not implemented yet and always reports an issue.
@@ -264,6 +272,7 @@ pub async fn get_server_module_options_context(
         *get_swc_ecma_transform_plugin(project_path, next_config).await?,
         *get_relay_transform_plugin(next_config).await?,
         *get_emotion_transform_plugin(next_config).await?,
+        *get_react_remove_properties_transform_plugin(next_config).await?,
     ]
     .into_iter()
     .flatten()
@@ -283,6 +292,7 @@ pub async fn get_server_module_options_context(
             let mut base_source_transforms: Vec<Vc<TransformPlugin>> = vec![
                 styled_components_transform_plugin,
                 styled_jsx_transform_plugin,
+                server_directive_transform_plugin,
             ]
             .into_iter()
             .flatten()
@@ -335,7 +345,6 @@ pub async fn get_server_module_options_context(
             let mut base_source_transforms: Vec<Vc<TransformPlugin>> = vec![
                 styled_components_transform_plugin,
                 styled_jsx_transform_plugin,
-                server_directive_transform_plugin,
             ]
             .into_iter()
             .flatten()
@@ -394,13 +403,11 @@ pub async fn get_server_module_options_context(
             ecmascript_client_reference_transition_name,
             ..
         } => {
-            let mut base_source_transforms: Vec<Vc<TransformPlugin>> = vec![
-                styled_components_transform_plugin,
-                server_directive_transform_plugin,
-            ]
-            .into_iter()
-            .flatten()
-            .collect();
+            let mut base_source_transforms: Vec<Vc<TransformPlugin>> =
+                vec![styled_components_transform_plugin]
+                    .into_iter()
+                    .flatten()
+                    .collect();
 
             if let Some(ecmascript_client_reference_transition_name) =
                 ecmascript_client_reference_transition_name
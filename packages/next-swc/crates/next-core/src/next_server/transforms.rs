@@ -9,8 +9,11 @@ use crate::{
     next_config::NextConfig,
     next_server::context::ServerContextType,
     next_shared::transforms::{
-        get_next_dynamic_transform_rule, get_next_font_transform_rule, get_next_image_rule,
-        get_next_modularize_imports_rule, get_next_pages_transforms_rule,
+        get_next_cjs_optimizer_rule, get_next_dynamic_transform_rule, get_next_font_transform_rule,
+        get_next_image_rule, get_next_modularize_imports_rule,
+        get_next_optimize_package_imports_rule, get_next_optimize_server_react_rule,
+        get_next_pages_transforms_rule, get_remove_console_transform_rule,
+        get_server_actions_transform_rule, ActionsTransform,
     },
 };
 
@@ -27,8 +30,23 @@ pub async fn get_next_server_transforms_rules(
     if let Some(modularize_imports_config) = modularize_imports_config {
         rules.push(get_next_modularize_imports_rule(modularize_imports_config));
     }
+
+    let optimize_package_imports = &*next_config.optimize_package_imports().await?;
+    if !optimize_package_imports.is_empty() {
+        rules.push(get_next_optimize_package_imports_rule(
+            optimize_package_imports,
+        ));
+    }
     rules.push(get_next_font_transform_rule());
 
+    let enable_server_actions = *next_config.enable_server_actions().await?;
+
+    rules.push(get_next_cjs_optimizer_rule());
+    // Like webpack, this only applies to production builds.
+    if mode == NextMode::Build && *next_config.optimize_server_react().await? {
+        rules.push(get_next_optimize_server_react_rule());
+    }
+
     let (is_server_components, pages_dir) = match context_ty {
         ServerContextType::Pages { pages_dir } => (false, Some(pages_dir)),
         ServerContextType::PagesData { pages_dir } => {
@@ -37,10 +55,20 @@ pub async fn get_next_server_transforms_rules(
             );
             (false, Some(pages_dir))
         }
-        ServerContextType::AppSSR { .. } => (false, None),
+        ServerContextType::AppSSR { .. } => {
+            rules.push(get_server_actions_transform_rule(
+                ActionsTransform::Client,
+                enable_server_actions,
+            ));
+            (false, None)
+        }
         ServerContextType::AppRSC {
             client_transition, ..
         } => {
+            rules.push(get_server_actions_transform_rule(
+                ActionsTransform::Server,
+                enable_server_actions,
+            ));
             if let Some(client_transition) = client_transition {
                 rules.push(get_next_css_client_reference_transforms_rule(
                     client_transition,
@@ -56,6 +84,10 @@ pub async fn get_next_server_transforms_rules(
 
     rules.push(get_next_image_rule());
 
+    if let Some(remove_console_rule) = get_remove_console_transform_rule(next_config).await? {
+        rules.push(remove_console_rule);
+    }
+
     Ok(rules)
 }
 
@@ -0,0 +1,306 @@
+use std::{fmt::Write, future::Future};
+
+use anyhow::{bail, Result};
+use indexmap::IndexMap;
+use indoc::formatdoc;
+use next_transform_server_actions::generate_action_id;
+use swc_core::{common::comments::Comments, ecma::ast::Program};
+use turbo_tasks::{
+    graph::{AdjacencyMap, GraphTraversal, Visit, VisitControlFlow},
+    TryJoinIterExt, Value, Vc,
+};
+use turbo_tasks_fs::{File, FileSystemPath};
+use turbopack_binding::turbopack::{
+    core::{
+        asset::AssetContent,
+        chunk::ModuleId as TurbopackModuleId,
+        context::AssetContext,
+        file_source::FileSource,
+        module::Module,
+        reference_type::{EcmaScriptModulesReferenceSubType, ReferenceType},
+        virtual_source::VirtualSource,
+    },
+    ecmascript::{
+        chunk::{EcmascriptChunkItemExt, EcmascriptChunkPlaceable, EcmascriptChunkingContext},
+        parse::ParseResult,
+        utils::StringifyJs,
+        EcmascriptModuleAsset,
+    },
+};
+
+use crate::{
+    next_manifests::{ActionLayer, ActionManifestWorkerEntry, ServerReferenceManifest},
+    util::NextRuntime,
+};
+
+/// The server actions exported by a module, mapping action IDs to export
+/// names.
+#[turbo_tasks::value(transparent)]
+pub struct ModuleActions(IndexMap<String, String>);
+
+/// All modules exporting server actions, keyed by their path.
+#[turbo_tasks::value(transparent)]
+pub struct AllActions(IndexMap<Vc<FileSystemPath>, Vc<ModuleActions>>);
+
+/// Finds the server actions exported by a module, as listed by the Server
+/// Actions transform.
+#[turbo_tasks::function]
+pub async fn get_module_actions(module: Vc<Box<dyn Module>>) -> Result<Vc<ModuleActions>> {
+    let Some(ecmascript_asset) =
+        Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(module).await?
+    else {
+        return Ok(Vc::cell(IndexMap::new()));
+    };
+
+    let ParseResult::Ok {
+        program, comments, ..
+    } = &*ecmascript_asset.parse().await?
+    else {
+        return Ok(Vc::cell(IndexMap::new()));
+    };
+
+    let start = match program {
+        Program::Module(module) => module.span.lo,
+        Program::Script(script) => script.span.lo,
+    };
+    let Some(names) = comments
+        .get_leading(start)
+        .into_iter()
+        .flatten()
+        .find_map(|comment| parse_action_entry_comment(&comment.text))
+    else {
+        return Ok(Vc::cell(IndexMap::new()));
+    };
+
+    // The transform derives the IDs from the same path.
+    let path = &module.ident().path().await?.path;
+    Ok(Vc::cell(
+        names
+            .into_iter()
+            .map(|name| (generate_action_id(path.clone(), name.clone()), name))
+            .collect(),
+    ))
+}
+
+/// Parses the export names out of the comment the Server Actions transform
+/// prepends to modules exporting actions, like
+/// `getActionsFromBuildInfo` in next.js's flight-client-entry-plugin.
+fn parse_action_entry_comment(text: &str) -> Option<Vec<String>> {
+    let names = text
+        .trim()
+        .strip_prefix("__next_internal_action_entry_do_not_use__")?
+        .trim();
+    Some(
+        names
+            .split(',')
+            .filter(|name| !name.is_empty())
+            .map(ToString::to_string)
+            .collect(),
+    )
+}
+
+/// Collects the server actions of all modules reachable from `entry`. This
+/// includes actions imported by client components.
+#[turbo_tasks::function]
+pub async fn get_server_actions(entry: Vc<Box<dyn Module>>) -> Result<Vc<AllActions>> {
+    let modules = AdjacencyMap::new()
+        .skip_duplicates()
+        .visit([entry], VisitActions)
+        .await
+        .completed()?
+        .into_inner()
+        .into_reverse_topological();
+
+    let modules_actions = modules
+        .map(|module| async move {
+            let actions = get_module_actions(module);
+            if actions.await?.is_empty() {
+                return Ok(None);
+            }
+            Ok(Some((module.ident().path().resolve().await?, actions)))
+        })
+        .try_join()
+        .await?;
+
+    let mut all_actions = IndexMap::new();
+    for (path, actions) in modules_actions.into_iter().flatten() {
+        // Client and server versions of a module export the same actions.
+        all_actions.entry(path).or_insert(actions);
+    }
+
+    Ok(Vc::cell(all_actions))
+}
+
+struct VisitActions;
+
+impl Visit<Vc<Box<dyn Module>>> for VisitActions {
+    type Edge = Vc<Box<dyn Module>>;
+    type EdgesIntoIter = Vec<Self::Edge>;
+    type EdgesFuture = impl Future<Output = Result<Self::EdgesIntoIter>>;
+
+    fn visit(&mut self, edge: Self::Edge) -> VisitControlFlow<Vc<Box<dyn Module>>> {
+        VisitControlFlow::Continue(edge)
+    }
+
+    fn edges(&mut self, node: &Vc<Box<dyn Module>>) -> Self::EdgesFuture {
+        let module = *node;
+        async move {
+            let referenced_modules = module
+                .references()
+                .await?
+                .iter()
+                .copied()
+                .map(|reference| async move {
+                    let modules = reference.resolve_reference().primary_modules().await?;
+                    modules
+                        .iter()
+                        .copied()
+                        .map(|module| module.resolve())
+                        .try_join()
+                        .await
+                })
+                .try_join()
+                .await?;
+
+            Ok(referenced_modules.into_iter().flatten().collect())
+        }
+    }
+}
+
+/// Creates the module exposing the server actions of a page to the action
+/// handler by ID, like next.js's `next-flight-action-entry-loader`.
+///
+/// The action modules are processed in `asset_context`, which is expected to
+/// be the RSC context of the page, so that actions imported by client
+/// components run their server implementation.
+#[turbo_tasks::function]
+pub async fn create_server_actions_loader(
+    project_root: Vc<FileSystemPath>,
+    page_name: String,
+    actions: Vc<AllActions>,
+    asset_context: Vc<Box<dyn AssetContext>>,
+) -> Result<Vc<Box<dyn EcmascriptChunkPlaceable>>> {
+    let mut inner_assets = IndexMap::new();
+    let mut actions_code = String::new();
+    let mut exports_code = String::new();
+
+    for (i, (&path, module_actions)) in actions.await?.iter().enumerate() {
+        let request = format!("ACTION_{i}");
+        for (id, name) in module_actions.await?.iter() {
+            writeln!(
+                actions_code,
+                "  {id}: () => require({request})[{name}],",
+                id = StringifyJs(id),
+                request = StringifyJs(&request),
+                name = StringifyJs(name),
+            )?;
+            writeln!(
+                exports_code,
+                "  {id}: endpoint.bind(null, {id}),",
+                id = StringifyJs(id),
+            )?;
+        }
+
+        let module = asset_context.process(
+            Vc::upcast(FileSource::new(path)),
+            Value::new(ReferenceType::EcmaScriptModules(
+                EcmaScriptModulesReferenceSubType::Undefined,
+            )),
+        );
+        inner_assets.insert(request, module);
+    }
+
+    let code = formatdoc! {
+        r#"
+            const actions = {{
+            {actions_code}}}
+
+            async function endpoint(id, ...args) {{
+              const action = await actions[id]()
+              return action.apply(null, args)
+            }}
+
+            // Using CJS to avoid this to be tree-shaken away due to unused exports.
+            module.exports = {{
+            {exports_code}}}
+        "#
+    };
+
+    let source = VirtualSource::new(
+        project_root.join(format!(".next-internal/server/app{page_name}/actions.js")),
+        AssetContent::file(File::from(code).into()),
+    );
+
+    let loader = asset_context.process(
+        Vc::upcast(source),
+        Value::new(ReferenceType::Internal(Vc::cell(inner_assets))),
+    );
+
+    let Some(loader) =
+        Vc::try_resolve_downcast::<Box<dyn EcmascriptChunkPlaceable>>(loader).await?
+    else {
+        bail!("expected an ECMAScript chunk placeable module");
+    };
+
+    Ok(loader)
+}
+
+/// Adds the server actions of a page to `manifest`, pointing the action
+/// handler to the module ID of the page's actions loader.
+pub async fn add_server_actions_to_manifest(
+    manifest: &mut ServerReferenceManifest,
+    page_name: &str,
+    runtime: NextRuntime,
+    actions: Vc<AllActions>,
+    loader: Vc<Box<dyn EcmascriptChunkPlaceable>>,
+    chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+) -> Result<()> {
+    let loader_id = loader.as_chunk_item(chunking_context).id().await?;
+    let action_manifest = match runtime {
+        NextRuntime::NodeJs => &mut manifest.server_actions,
+        NextRuntime::Edge => &mut manifest.edge_server_actions,
+    };
+    // The action handler looks up workers by the page's bundle path.
+    let worker_name = format!("app{page_name}");
+
+    for module_actions in actions.await?.values() {
+        for id in module_actions.await?.keys() {
+            let entry = action_manifest.actions.entry(id.clone()).or_default();
+            entry
+                .workers
+                .insert(worker_name.clone(), (&*loader_id).into());
+            entry.layer.insert(worker_name.clone(), ActionLayer::Rsc);
+        }
+    }
+
+    Ok(())
+}
+
+impl From<&TurbopackModuleId> for ActionManifestWorkerEntry {
+    fn from(module_id: &TurbopackModuleId) -> Self {
+        match module_id {
+            TurbopackModuleId::String(string) => ActionManifestWorkerEntry::String(string.clone()),
+            TurbopackModuleId::Number(number) => ActionManifestWorkerEntry::Number(*number as _),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_action_entry_comment;
+
+    #[test]
+    fn parses_action_entry_comments() {
+        assert_eq!(
+            parse_action_entry_comment(
+                " __next_internal_action_entry_do_not_use__ foo,$$ACTION_0 "
+            ),
+            Some(vec!["foo".to_string(), "$$ACTION_0".to_string()])
+        );
+        assert_eq!(
+            parse_action_entry_comment(" __next_internal_action_entry_do_not_use__  "),
+            Some(vec![])
+        );
+        assert_eq!(parse_action_entry_comment(" @ts-ignore "), None);
+    }
+}
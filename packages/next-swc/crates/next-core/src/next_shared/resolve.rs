@@ -19,7 +19,10 @@ use turbopack_binding::{
     },
 };
 
-use crate::next_telemetry::ModuleFeatureTelemetry;
+use crate::{
+    next_shared::transforms::optimize_barrel::{get_optimized_barrel, BARREL_OPTIMIZE_QUERY},
+    next_telemetry::ModuleFeatureTelemetry,
+};
 
 lazy_static! {
     static ref UNSUPPORTED_PACKAGES: HashSet<&'static str> = [].into();
@@ -259,3 +262,53 @@ impl ResolvePlugin for NextSharedRuntimeResolvePlugin {
         )))
     }
 }
+
+/// Resolves requests for some exports of a barrel file, as created by the
+/// `optimizePackageImports` transform, to a module re-exporting only those
+/// from the modules declaring them.
+#[turbo_tasks::value]
+pub(crate) struct NextBarrelOptimizeResolvePlugin {
+    root: Vc<FileSystemPath>,
+}
+
+#[turbo_tasks::value_impl]
+impl NextBarrelOptimizeResolvePlugin {
+    #[turbo_tasks::function]
+    pub fn new(root: Vc<FileSystemPath>) -> Vc<Self> {
+        NextBarrelOptimizeResolvePlugin { root }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ResolvePlugin for NextBarrelOptimizeResolvePlugin {
+    #[turbo_tasks::function]
+    fn after_resolve_condition(&self) -> Vc<ResolvePluginCondition> {
+        ResolvePluginCondition::new(self.root.root(), Glob::new("**".to_string()))
+    }
+
+    #[turbo_tasks::function]
+    async fn after_resolve(
+        &self,
+        fs_path: Vc<FileSystemPath>,
+        _context: Vc<FileSystemPath>,
+        request: Vc<Request>,
+    ) -> Result<Vc<ResolveResultOption>> {
+        let query = match &*request.await? {
+            Request::Module { query, .. } | Request::Relative { query, .. } => *query,
+            _ => return Ok(ResolveResultOption::none()),
+        };
+        let query = qstring::QString::from(query.await?.as_str());
+        let Some(names) = query.get(BARREL_OPTIMIZE_QUERY) else {
+            return Ok(ResolveResultOption::none());
+        };
+
+        Ok(Vc::cell(Some(
+            ResolveResult::source(get_optimized_barrel(
+                fs_path,
+                names.to_string(),
+                query.has("wildcard"),
+            ))
+            .into(),
+        )))
+    }
+}
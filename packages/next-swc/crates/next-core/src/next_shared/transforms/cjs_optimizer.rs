@@ -0,0 +1,78 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use next_transform_cjs_optimizer::{cjs_optimizer, Config, PackageConfig};
+use swc_core::{
+    common::SyntaxContext,
+    ecma::{ast::Program, visit::VisitMutWith},
+};
+use turbo_tasks::Vc;
+use turbopack_binding::turbopack::{
+    ecmascript::{CustomTransformer, EcmascriptInputTransform, TransformContext},
+    turbopack::module_options::{ModuleRule, ModuleRuleEffect},
+};
+
+use super::module_rule_match_js_no_url;
+
+/// Returns a rule which rewrites property accesses on `require("next/server")`
+/// into requires of the modules defining them, so that the rest of
+/// `next/server` isn't bundled.
+pub fn get_next_cjs_optimizer_rule() -> ModuleRule {
+    // Keep in sync with `cjsRequireOptimizer` in
+    // next.js/packages/next/src/build/swc/options.ts
+    let config = Config {
+        packages: [(
+            "next/server".to_string(),
+            PackageConfig {
+                transforms: [
+                    ("NextRequest", "next/dist/server/web/spec-extension/request"),
+                    (
+                        "NextResponse",
+                        "next/dist/server/web/spec-extension/response",
+                    ),
+                    (
+                        "ImageResponse",
+                        "next/dist/server/web/spec-extension/image-response",
+                    ),
+                    (
+                        "userAgentFromString",
+                        "next/dist/server/web/spec-extension/user-agent",
+                    ),
+                    (
+                        "userAgent",
+                        "next/dist/server/web/spec-extension/user-agent",
+                    ),
+                ]
+                .into_iter()
+                .map(|(name, request)| (name.into(), request.into()))
+                .collect(),
+            },
+        )]
+        .into_iter()
+        .collect(),
+    };
+
+    let transformer =
+        EcmascriptInputTransform::Plugin(Vc::cell(Box::new(NextCjsOptimizer { config }) as _));
+    ModuleRule::new(
+        module_rule_match_js_no_url(),
+        vec![ModuleRuleEffect::AddEcmascriptTransforms(Vc::cell(vec![
+            transformer,
+        ]))],
+    )
+}
+
+#[derive(Debug)]
+struct NextCjsOptimizer {
+    config: Config,
+}
+
+#[async_trait]
+impl CustomTransformer for NextCjsOptimizer {
+    async fn transform(&self, program: &mut Program, ctx: &TransformContext<'_>) -> Result<()> {
+        program.visit_mut_with(&mut cjs_optimizer(
+            self.config.clone(),
+            SyntaxContext::empty().apply_mark(ctx.unresolved_mark),
+        ));
+        Ok(())
+    }
+}
@@ -1,18 +1,30 @@
+pub(crate) mod cjs_optimizer;
 pub(crate) mod emotion;
 pub(crate) mod modularize_imports;
 pub(crate) mod next_dynamic;
 pub(crate) mod next_font;
 pub(crate) mod next_strip_page_exports;
+pub(crate) mod optimize_barrel;
+pub(crate) mod optimize_server_react;
+pub(crate) mod react_remove_properties;
 pub(crate) mod relay;
+pub(crate) mod remove_console;
+pub(crate) mod server_actions;
 pub(crate) mod styled_components;
 pub(crate) mod styled_jsx;
 pub(crate) mod swc_ecma_transform_plugins;
 
+pub use cjs_optimizer::get_next_cjs_optimizer_rule;
 pub use modularize_imports::{get_next_modularize_imports_rule, ModularizeImportPackageConfig};
 pub use next_dynamic::get_next_dynamic_transform_rule;
 pub use next_font::get_next_font_transform_rule;
 pub use next_strip_page_exports::get_next_pages_transforms_rule;
+pub use optimize_barrel::get_next_optimize_package_imports_rule;
+pub use optimize_server_react::get_next_optimize_server_react_rule;
+pub use react_remove_properties::get_react_remove_properties_transform_plugin;
 pub use relay::get_relay_transform_plugin;
+pub use remove_console::get_remove_console_transform_rule;
+pub use server_actions::{get_server_actions_transform_rule, ActionsTransform};
 use turbo_tasks::{Value, Vc};
 use turbopack_binding::turbopack::{
     core::reference_type::{ReferenceType, UrlReferenceSubType},
@@ -0,0 +1,320 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use next_transform_named_import::{self as named_import_transform, named_import_transform};
+use next_transform_optimize_barrel::{self as optimize_barrel, optimize_barrel};
+use swc_core::{
+    common::{sync::Lrc, util::take::Take, FileName, Globals, SourceMap, DUMMY_SP, GLOBALS},
+    ecma::{
+        ast::{
+            EsVersion, Expr, ExprStmt, ImportDecl, Lit, Module, ModuleDecl, ModuleItem, Program,
+            Stmt, Str, VarDeclarator,
+        },
+        parser::{parse_file_as_module, EsConfig, Syntax, TsConfig},
+        visit::{FoldWith, VisitMut, VisitMutWith},
+    },
+};
+use turbo_tasks::Vc;
+use turbo_tasks_fs::{FileContent, FileSystemPath};
+use turbopack_binding::{
+    turbo::tasks_hash::hash_xxh3_hash64,
+    turbopack::{
+        core::{
+            asset::AssetContent, file_source::FileSource, source::Source,
+            virtual_source::VirtualSource,
+        },
+        ecmascript::{
+            utils::StringifyJs, CustomTransformer, EcmascriptInputTransform, TransformContext,
+        },
+        turbopack::module_options::{ModuleRule, ModuleRuleEffect},
+    },
+};
+
+use super::module_rule_match_js_no_url;
+
+/// The query parameter marking a request for a subset of the exports of a
+/// barrel file.
+pub(crate) const BARREL_OPTIMIZE_QUERY: &str = "__next_barrel_optimize__";
+
+/// Prefix of the webpack-style requests produced by the SWC barrel transforms,
+/// e.g. `__barrel_optimize__?names=a,b!=!./foo`.
+const WEBPACK_BARREL_REQUEST_PREFIX: &str = "__barrel_optimize__?names=";
+
+/// Returns a rule which redirects named imports from `packages` to requests
+/// for just the imported exports. The requests are then resolved by
+/// [crate::next_shared::resolve::NextBarrelOptimizeResolvePlugin].
+pub fn get_next_optimize_package_imports_rule(packages: &[String]) -> ModuleRule {
+    let transformer =
+        EcmascriptInputTransform::Plugin(Vc::cell(Box::new(NextOptimizePackageImports {
+            packages: packages.to_vec(),
+        }) as _));
+    ModuleRule::new(
+        module_rule_match_js_no_url(),
+        vec![ModuleRuleEffect::AddEcmascriptTransforms(Vc::cell(vec![
+            transformer,
+        ]))],
+    )
+}
+
+#[derive(Debug)]
+struct NextOptimizePackageImports {
+    packages: Vec<String>,
+}
+
+#[async_trait]
+impl CustomTransformer for NextOptimizePackageImports {
+    async fn transform(&self, program: &mut Program, _ctx: &TransformContext<'_>) -> Result<()> {
+        let p = std::mem::replace(program, Program::Module(Module::dummy()));
+        *program = p.fold_with(&mut named_import_transform(
+            named_import_transform::Config {
+                packages: self.packages.clone(),
+            },
+        ));
+        program.visit_mut_with(&mut BarrelRequestRewriter);
+        Ok(())
+    }
+}
+
+/// Turns the webpack-style requests of the named import transform into
+/// requests with a [BARREL_OPTIMIZE_QUERY] query.
+struct BarrelRequestRewriter;
+
+impl VisitMut for BarrelRequestRewriter {
+    fn visit_mut_import_decl(&mut self, decl: &mut ImportDecl) {
+        if let Some((names, request)) = parse_webpack_barrel_request(&decl.src.value) {
+            decl.src = Box::new(Str {
+                span: DUMMY_SP,
+                value: barrel_optimize_request(request, names, false).into(),
+                raw: None,
+            });
+        }
+    }
+}
+
+/// Splits `__barrel_optimize__?names=a,b!=!./foo` into `a,b` and `./foo`.
+fn parse_webpack_barrel_request(src: &str) -> Option<(&str, &str)> {
+    src.strip_prefix(WEBPACK_BARREL_REQUEST_PREFIX)?
+        .split_once("!=!")
+}
+
+fn barrel_optimize_request(request: &str, names: &str, wildcard: bool) -> String {
+    let wildcard = if wildcard { "&wildcard" } else { "" };
+    format!("{request}?{BARREL_OPTIMIZE_QUERY}={names}{wildcard}")
+}
+
+/// What the barrel transform found out about a module.
+#[turbo_tasks::value(shared)]
+pub struct BarrelExports {
+    /// Directives like `"use client"`, which need to be kept.
+    directives: Vec<String>,
+    /// `(exported name, request, original name)` of every export. The request
+    /// is empty for exports declared in the module itself.
+    export_map: Vec<(String, String, String)>,
+    /// The requests of `export * from "..."` statements.
+    wildcard_exports: Vec<String>,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionBarrelExports(Option<BarrelExports>);
+
+/// Analyzes a module with the barrel transform. Returns `None` if it is not a
+/// barrel file, i.e. it does anything besides re-exporting other modules.
+///
+/// With `wildcard`, modules declaring exports themselves are accepted too, as
+/// they may be the target of an `export *`.
+#[turbo_tasks::function]
+async fn get_barrel_exports(
+    path: Vc<FileSystemPath>,
+    wildcard: bool,
+) -> Result<Vc<OptionBarrelExports>> {
+    let FileContent::Content(file) = &*path.read().await? else {
+        return Ok(Vc::cell(None));
+    };
+    let Ok(source) = file.content().to_str() else {
+        return Ok(Vc::cell(None));
+    };
+    let path = path.await?;
+
+    let syntax = match path.extension_ref() {
+        Some(ext @ ("ts" | "tsx" | "mts" | "cts")) => Syntax::Typescript(TsConfig {
+            tsx: ext == "tsx",
+            ..Default::default()
+        }),
+        _ => Syntax::Es(EsConfig {
+            jsx: true,
+            ..Default::default()
+        }),
+    };
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(
+        FileName::Real(path.path.clone().into()),
+        source.into_owned(),
+    );
+    let Ok(module) = parse_file_as_module(&fm, syntax, EsVersion::latest(), None, &mut vec![])
+    else {
+        // Parse errors are reported when the module itself is processed.
+        return Ok(Vc::cell(None));
+    };
+
+    // The transform creates private identifiers, which need a `Mark`.
+    let module = GLOBALS.set(&Globals::new(), || {
+        module.fold_with(&mut optimize_barrel(optimize_barrel::Config { wildcard }))
+    });
+
+    let mut exports = BarrelExports {
+        directives: vec![],
+        export_map: vec![],
+        wildcard_exports: vec![],
+    };
+    let mut is_barrel = false;
+    for item in module.body {
+        match item {
+            ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. })) => {
+                if let Expr::Lit(Lit::Str(directive)) = *expr {
+                    exports.directives.push(directive.value.to_string());
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => {
+                let Some(var) = export_decl.decl.var() else {
+                    continue;
+                };
+                if let [VarDeclarator {
+                    init: Some(init), ..
+                }] = &var.decls[..]
+                {
+                    if let Expr::Lit(Lit::Str(export_map)) = &**init {
+                        exports.export_map = serde_json::from_str(&export_map.value)?;
+                        is_barrel = true;
+                    }
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export_all)) => {
+                if let Some((_, request)) = parse_webpack_barrel_request(&export_all.src.value) {
+                    exports.wildcard_exports.push(request.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(Vc::cell(is_barrel.then_some(exports)))
+}
+
+/// Returns a module re-exporting only `names` from the barrel file at `path`,
+/// like next.js's `next-barrel-loader`. Non-barrel files are returned as they
+/// are.
+#[turbo_tasks::function]
+pub async fn get_optimized_barrel(
+    path: Vc<FileSystemPath>,
+    names: String,
+    wildcard: bool,
+) -> Result<Vc<Box<dyn Source>>> {
+    let Some(exports) = &*get_barrel_exports(path, wildcard).await? else {
+        return Ok(Vc::upcast(FileSource::new(path)));
+    };
+
+    let file_name = path.await?.file_name().to_string();
+    let names: Vec<&str> = names.split(',').filter(|name| !name.is_empty()).collect();
+    let code = barrel_reexports(exports, &format!("./{file_name}"), &names, wildcard)?;
+
+    let hash = hash_xxh3_hash64((&file_name, &names, wildcard)) as u32;
+    Ok(Vc::upcast(VirtualSource::new(
+        path.parent()
+            .join(format!("{file_name}.__barrel_optimize__.{hash:08x}.js")),
+        AssetContent::file(FileContent::Content(code.into()).into()),
+    )))
+}
+
+/// Generates the re-exports of `names` from a barrel file, which is imported
+/// as `self_request` for exports it declares itself.
+fn barrel_reexports(
+    exports: &BarrelExports,
+    self_request: &str,
+    names: &[&str],
+    wildcard: bool,
+) -> Result<String> {
+    let mut code = String::new();
+    for directive in &exports.directives {
+        writeln!(code, "{};", StringifyJs(directive))?;
+    }
+
+    let mut missed_names = vec![];
+    for &name in names {
+        let Some((_, request, orig)) = exports
+            .export_map
+            .iter()
+            .find(|(exported, ..)| exported == name)
+        else {
+            missed_names.push(name);
+            continue;
+        };
+
+        let (request, orig) = if wildcard && request.is_empty() {
+            (self_request, name)
+        } else {
+            (request.as_str(), orig.as_str())
+        };
+        let request = StringifyJs(request);
+        if orig == "*" {
+            writeln!(code, "export * as {name} from {request};")?;
+        } else if orig == name {
+            writeln!(code, "export {{ {name} }} from {request};")?;
+        } else {
+            writeln!(code, "export {{ {orig} as {name} }} from {request};")?;
+        }
+    }
+
+    // Names not exported directly might come from any `export *`.
+    if !missed_names.is_empty() {
+        let missed_names = missed_names.join(",");
+        for request in &exports.wildcard_exports {
+            writeln!(
+                code,
+                "export * from {};",
+                StringifyJs(&barrel_optimize_request(request, &missed_names, true))
+            )?;
+        }
+    }
+
+    Ok(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{barrel_reexports, parse_webpack_barrel_request, BarrelExports};
+
+    #[test]
+    fn parses_webpack_barrel_requests() {
+        assert_eq!(
+            parse_webpack_barrel_request("__barrel_optimize__?names=a,b!=!@scope/pkg"),
+            Some(("a,b", "@scope/pkg"))
+        );
+        assert_eq!(parse_webpack_barrel_request("@scope/pkg"), None);
+    }
+
+    #[test]
+    fn reexports_requested_names() {
+        let exports = BarrelExports {
+            directives: vec!["use client".to_string()],
+            export_map: vec![
+                ("a".to_string(), "./a".to_string(), "a".to_string()),
+                ("b".to_string(), "./b".to_string(), "default".to_string()),
+                ("ns".to_string(), "./ns".to_string(), "*".to_string()),
+                ("local".to_string(), "".to_string(), "".to_string()),
+            ],
+            wildcard_exports: vec!["./rest".to_string()],
+        };
+
+        assert_eq!(
+            barrel_reexports(&exports, "./index.js", &["a", "b", "ns"], false).unwrap(),
+            "\"use client\";\nexport { a } from \"./a\";\nexport { default as b } from \
+             \"./b\";\nexport * as ns from \"./ns\";\n"
+        );
+        assert_eq!(
+            barrel_reexports(&exports, "./index.js", &["local", "c", "d"], true).unwrap(),
+            "\"use client\";\nexport { local } from \"./index.js\";\nexport * from \
+             \"./rest?__next_barrel_optimize__=c,d&wildcard\";\n"
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use next_transform_optimize_server_react::{optimize_server_react, Config};
+use swc_core::{
+    common::util::take::Take,
+    ecma::{
+        ast::{Module, Program},
+        visit::FoldWith,
+    },
+};
+use turbo_tasks::Vc;
+use turbopack_binding::turbopack::{
+    ecmascript::{CustomTransformer, EcmascriptInputTransform, TransformContext},
+    turbopack::module_options::{ModuleRule, ModuleRuleEffect},
+};
+
+use super::module_rule_match_js_no_url;
+
+/// Returns a rule which removes effects, which never run during server
+/// rendering, from server-side React code.
+pub fn get_next_optimize_server_react_rule() -> ModuleRule {
+    let transformer =
+        EcmascriptInputTransform::Plugin(Vc::cell(Box::new(NextOptimizeServerReact) as _));
+    ModuleRule::new(
+        module_rule_match_js_no_url(),
+        vec![ModuleRuleEffect::AddEcmascriptTransforms(Vc::cell(vec![
+            transformer,
+        ]))],
+    )
+}
+
+#[derive(Debug)]
+struct NextOptimizeServerReact;
+
+#[async_trait]
+impl CustomTransformer for NextOptimizeServerReact {
+    async fn transform(&self, program: &mut Program, _ctx: &TransformContext<'_>) -> Result<()> {
+        let p = std::mem::replace(program, Program::Module(Module::dummy()));
+        *program = p.fold_with(&mut optimize_server_react(Config {
+            optimize_use_state: true,
+        }));
+        Ok(())
+    }
+}
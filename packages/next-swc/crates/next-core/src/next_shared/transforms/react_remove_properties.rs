@@ -0,0 +1,65 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use next_transform_react_remove_properties::{remove_properties, Config, Options};
+use regex::Regex;
+use swc_core::{
+    common::util::take::Take,
+    ecma::{
+        ast::{Module, Program},
+        visit::FoldWith,
+    },
+};
+use turbo_tasks::Vc;
+use turbopack_binding::turbopack::ecmascript::{
+    CustomTransformer, OptionTransformPlugin, TransformContext,
+};
+
+use crate::next_config::{NextConfig, ReactRemoveProperties};
+
+/// Returns a transform plugin for the `compiler.reactRemoveProperties`
+/// transform, if it is enabled.
+///
+/// Unlike most Next.js transforms, this is not a module rule: rules run after
+/// JSX has been compiled, while this transform removes JSX attributes.
+#[turbo_tasks::function]
+pub async fn get_react_remove_properties_transform_plugin(
+    next_config: Vc<NextConfig>,
+) -> Result<Vc<OptionTransformPlugin>> {
+    let config = match next_config
+        .await?
+        .compiler
+        .as_ref()
+        .and_then(|compiler| compiler.react_remove_properties.as_ref())
+    {
+        Some(ReactRemoveProperties::Boolean(true)) => Config::All(true),
+        Some(ReactRemoveProperties::Config { properties }) => {
+            let properties = properties.clone().unwrap_or_default();
+            // The transform panics on invalid patterns, so report them here.
+            for pattern in &properties {
+                Regex::new(pattern).with_context(|| {
+                    format!("invalid regex `{pattern}` in compiler.reactRemoveProperties")
+                })?;
+            }
+            Config::WithOptions(Options { properties })
+        }
+        _ => return Ok(Vc::cell(None)),
+    };
+
+    Ok(Vc::cell(Some(Vc::cell(
+        Box::new(NextReactRemoveProperties { config }) as _,
+    ))))
+}
+
+#[derive(Debug)]
+struct NextReactRemoveProperties {
+    config: Config,
+}
+
+#[async_trait]
+impl CustomTransformer for NextReactRemoveProperties {
+    async fn transform(&self, program: &mut Program, _ctx: &TransformContext<'_>) -> Result<()> {
+        let p = std::mem::replace(program, Program::Module(Module::dummy()));
+        *program = p.fold_with(&mut remove_properties(self.config.clone()));
+        Ok(())
+    }
+}
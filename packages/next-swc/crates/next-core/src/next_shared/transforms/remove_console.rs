@@ -0,0 +1,64 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use next_transform_remove_console::{remove_console, Config, Options};
+use swc_core::{
+    common::util::take::Take,
+    ecma::{
+        ast::{Module, Program},
+        visit::FoldWith,
+    },
+};
+use turbo_tasks::Vc;
+use turbopack_binding::turbopack::{
+    ecmascript::{CustomTransformer, EcmascriptInputTransform, TransformContext},
+    turbopack::module_options::{ModuleRule, ModuleRuleEffect},
+};
+
+use super::module_rule_match_js_no_url;
+use crate::next_config::{NextConfig, RemoveConsoleConfig};
+
+/// Returns a rule which applies the `compiler.removeConsole` transform, if it
+/// is enabled.
+pub async fn get_remove_console_transform_rule(
+    next_config: Vc<NextConfig>,
+) -> Result<Option<ModuleRule>> {
+    let config = match next_config
+        .await?
+        .compiler
+        .as_ref()
+        .and_then(|compiler| compiler.remove_console.as_ref())
+    {
+        Some(RemoveConsoleConfig::Boolean(true)) => Config::All(true),
+        Some(RemoveConsoleConfig::Config { exclude }) => Config::WithOptions(Options {
+            exclude: exclude
+                .iter()
+                .flatten()
+                .map(|method| method.as_str().into())
+                .collect(),
+        }),
+        _ => return Ok(None),
+    };
+
+    let transformer =
+        EcmascriptInputTransform::Plugin(Vc::cell(Box::new(NextRemoveConsole { config }) as _));
+    Ok(Some(ModuleRule::new(
+        module_rule_match_js_no_url(),
+        vec![ModuleRuleEffect::AddEcmascriptTransforms(Vc::cell(vec![
+            transformer,
+        ]))],
+    )))
+}
+
+#[derive(Debug)]
+struct NextRemoveConsole {
+    config: Config,
+}
+
+#[async_trait]
+impl CustomTransformer for NextRemoveConsole {
+    async fn transform(&self, program: &mut Program, _ctx: &TransformContext<'_>) -> Result<()> {
+        let p = std::mem::replace(program, Program::Module(Module::dummy()));
+        *program = p.fold_with(&mut remove_console(self.config.clone()));
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use next_transform_server_actions::{server_actions, Config};
+use swc_core::{
+    common::FileName,
+    ecma::{ast::Program, visit::VisitMutWith},
+};
+use turbo_tasks::Vc;
+use turbopack_binding::turbopack::{
+    ecmascript::{CustomTransformer, EcmascriptInputTransform, TransformContext},
+    turbopack::module_options::{ModuleRule, ModuleRuleEffect},
+};
+
+use super::module_rule_match_js_no_url;
+
+#[derive(Debug, Clone, Copy)]
+pub enum ActionsTransform {
+    /// Turns `"use server"` functions into registered server references.
+    Server,
+    /// Replaces imported server actions with references that call the server.
+    Client,
+}
+
+/// Returns a rule which applies the Next.js Server Actions transform.
+pub fn get_server_actions_transform_rule(transform: ActionsTransform, enabled: bool) -> ModuleRule {
+    let transformer =
+        EcmascriptInputTransform::Plugin(Vc::cell(
+            Box::new(NextServerActions { transform, enabled }) as _,
+        ));
+    ModuleRule::new(
+        module_rule_match_js_no_url(),
+        vec![ModuleRuleEffect::AddEcmascriptTransforms(Vc::cell(vec![
+            transformer,
+        ]))],
+    )
+}
+
+#[derive(Debug)]
+struct NextServerActions {
+    transform: ActionsTransform,
+    enabled: bool,
+}
+
+#[async_trait]
+impl CustomTransformer for NextServerActions {
+    async fn transform(&self, program: &mut Program, ctx: &TransformContext<'_>) -> Result<()> {
+        let mut actions = server_actions(
+            &FileName::Real(ctx.file_path_str.into()),
+            Config {
+                is_server: matches!(self.transform, ActionsTransform::Server),
+                enabled: self.enabled,
+            },
+            ctx.comments.clone(),
+        );
+        program.visit_mut_with(&mut actions);
+        Ok(())
+    }
+}
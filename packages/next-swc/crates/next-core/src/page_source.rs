@@ -53,7 +53,7 @@ use crate::{
     mode::NextMode,
     next_client::{
         context::{
-            get_client_assets_path, get_client_chunking_context, get_client_module_options_context,
+            get_client_assets_path, get_client_module_options_context,
             get_client_resolve_options_context, get_client_runtime_entries, ClientContextType,
         },
         transition::NextClientTransition,
@@ -65,7 +65,8 @@ use crate::{
         route_transition::NextEdgeRouteTransition,
     },
     next_route_matcher::{
-        NextExactMatcher, NextFallbackMatcher, NextParamsMatcher, NextPrefixSuffixParamsMatcher,
+        NextExactMatcher, NextFallbackMatcher, NextLocaleParamsMatcher,
+        NextLocalePrefixSuffixParamsMatcher, NextParamsMatcher, NextPrefixSuffixParamsMatcher,
     },
     next_server::context::{
         get_server_compile_time_info, get_server_module_options_context,
@@ -287,12 +288,14 @@ pub async fn create_page_source(
             server_context,
             server_data_context,
             client_context,
+            client_chunking_context,
             pages_dir,
             server_runtime_entries,
             fallback_page,
             client_root,
             node_root,
             render_data,
+            next_config.locales(),
         ),
         Vc::upcast::<Box<dyn ContentSource>>(AssetGraphContentSource::new_eager(
             client_root,
@@ -334,6 +337,7 @@ async fn create_page_source_for_file(
     server_context: Vc<Box<dyn AssetContext>>,
     server_data_context: Vc<Box<dyn AssetContext>>,
     client_context: Vc<Box<dyn AssetContext>>,
+    client_chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
     _pages_dir: Vc<FileSystemPath>,
     page_asset: Vc<Box<dyn Source>>,
     runtime_entries: Vc<Sources>,
@@ -344,9 +348,8 @@ async fn create_page_source_for_file(
     node_path: Vc<FileSystemPath>,
     node_root: Vc<FileSystemPath>,
     render_data: Vc<JsonValue>,
+    locales: Vc<Vec<String>>,
 ) -> Result<Vc<Box<dyn ContentSource>>> {
-    let mode = NextMode::DevServer;
-
     let server_chunking_context = Vc::upcast(
         DevChunkingContext::builder(
             project_path,
@@ -373,20 +376,39 @@ async fn create_page_source_for_file(
         .build(),
     );
 
-    let client_chunking_context = get_client_chunking_context(
-        project_path,
-        client_root,
-        client_context.compile_time_info().environment(),
-        mode,
-    );
-
     let pathname = pathname_for_path(client_root, client_path, PathType::PagesPage);
     let route_matcher = NextParamsMatcher::new(pathname);
 
-    let (base_segments, route_type) = pathname_to_segments(&pathname.await?, "")?;
+    let pathname_ref = pathname.await?;
+    let (base_segments, route_type) = pathname_to_segments(&pathname_ref, "")?;
+    // With i18n, the Next.js router rewrites requests to locale prefixed paths,
+    // e.g. `/fr/about`, so pages need to be served under every locale too.
+    let has_locales = !locales.await?.is_empty();
+    let locale_route = if has_locales {
+        Some((
+            pathname_to_segments(&format!("/[locale]{}", pathname_ref), "")?,
+            NextLocaleParamsMatcher::new(pathname, locales),
+        ))
+    } else {
+        None
+    };
 
     Ok(if is_api_path {
-        create_node_api_source(
+        let ssr_entry = Vc::upcast(
+            SsrEntry {
+                runtime_entries,
+                context: server_context,
+                entry_asset: page_asset,
+                ty: SsrType::AutoApi,
+                chunking_context: server_chunking_context,
+                node_path,
+                node_root,
+                project_path,
+            }
+            .cell(),
+        );
+
+        let mut sources = vec![create_node_api_source(
             project_path,
             env,
             base_segments,
@@ -394,22 +416,27 @@ async fn create_page_source_for_file(
             client_root,
             Vc::upcast(route_matcher),
             pathname,
-            Vc::upcast(
-                SsrEntry {
-                    runtime_entries,
-                    context: server_context,
-                    entry_asset: page_asset,
-                    ty: SsrType::AutoApi,
-                    chunking_context: server_chunking_context,
-                    node_path,
-                    node_root,
-                    project_path,
-                }
-                .cell(),
-            ),
+            ssr_entry,
             render_data,
             should_debug("page_source"),
-        )
+        )];
+        if let Some(((locale_base_segments, locale_route_type), locale_route_matcher)) =
+            locale_route
+        {
+            sources.push(create_node_api_source(
+                project_path,
+                env,
+                locale_base_segments,
+                locale_route_type,
+                client_root,
+                Vc::upcast(locale_route_matcher),
+                pathname,
+                ssr_entry,
+                render_data,
+                should_debug("page_source"),
+            ));
+        }
+        Vc::upcast(CombinedContentSource::new(sources))
     } else {
         let data_pathname = pathname_for_path(client_root, client_path, PathType::Data);
         let data_route_matcher = NextPrefixSuffixParamsMatcher::new(
@@ -450,7 +477,7 @@ async fn create_page_source_for_file(
             .cell(),
         );
 
-        Vc::upcast(CombinedContentSource::new(vec![
+        let mut sources = vec![
             create_node_rendered_source(
                 project_path,
                 env,
@@ -485,7 +512,53 @@ async fn create_page_source_for_file(
                 pathname,
                 FileSystemPathOption::none(),
             ),
-        ]))
+        ];
+
+        if let Some(((locale_base_segments, locale_route_type), locale_route_matcher)) =
+            locale_route
+        {
+            // Localized data routes don't use the `index` special case, e.g.
+            // `_next/data/development/fr.json` is the data route for `/`.
+            let locale_data_route_matcher = NextLocalePrefixSuffixParamsMatcher::new(
+                pathname,
+                locales,
+                "_next/data/development/".to_string(),
+                ".json".to_string(),
+            );
+            let (locale_data_base_segments, locale_data_route_type) = pathname_to_segments(
+                &format!("_next/data/development/[locale]{}", pathname_ref),
+                ".json",
+            )?;
+
+            sources.push(create_node_rendered_source(
+                project_path,
+                env,
+                locale_base_segments,
+                locale_route_type,
+                client_root,
+                Vc::upcast(locale_route_matcher),
+                pathname,
+                ssr_entry,
+                fallback_page,
+                render_data,
+                should_debug("page_source"),
+            ));
+            sources.push(create_node_rendered_source(
+                project_path,
+                env,
+                locale_data_base_segments,
+                locale_data_route_type,
+                client_root,
+                Vc::upcast(locale_data_route_matcher),
+                pathname,
+                ssr_data_entry,
+                fallback_page,
+                render_data,
+                should_debug("page_source"),
+            ));
+        }
+
+        Vc::upcast(CombinedContentSource::new(sources))
     })
 }
 
@@ -600,12 +673,14 @@ async fn create_page_source_for_root_directory(
     server_context: Vc<Box<dyn AssetContext>>,
     server_data_context: Vc<Box<dyn AssetContext>>,
     client_context: Vc<Box<dyn AssetContext>>,
+    client_chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
     pages_dir: Vc<FileSystemPath>,
     runtime_entries: Vc<Sources>,
     fallback_page: Vc<DevHtmlAsset>,
     client_root: Vc<FileSystemPath>,
     node_root: Vc<FileSystemPath>,
     render_data: Vc<JsonValue>,
+    locales: Vc<Vec<String>>,
 ) -> Result<Vc<Box<dyn ContentSource>>> {
     let PagesStructure {
         app: _,
@@ -624,6 +699,7 @@ async fn create_page_source_for_root_directory(
             server_context,
             server_data_context,
             client_context,
+            client_chunking_context,
             pages_dir,
             runtime_entries,
             fallback_page,
@@ -631,6 +707,7 @@ async fn create_page_source_for_root_directory(
             false,
             node_root,
             render_data,
+            locales,
         ));
     }
 
@@ -642,6 +719,7 @@ async fn create_page_source_for_root_directory(
             server_context,
             server_data_context,
             client_context,
+            client_chunking_context,
             pages_dir,
             runtime_entries,
             fallback_page,
@@ -649,6 +727,7 @@ async fn create_page_source_for_root_directory(
             true,
             node_root,
             render_data,
+            locales,
         ));
     }
 
@@ -666,6 +745,7 @@ async fn create_page_source_for_directory(
     server_context: Vc<Box<dyn AssetContext>>,
     server_data_context: Vc<Box<dyn AssetContext>>,
     client_context: Vc<Box<dyn AssetContext>>,
+    client_chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
     pages_dir: Vc<FileSystemPath>,
     runtime_entries: Vc<Sources>,
     fallback_page: Vc<DevHtmlAsset>,
@@ -673,6 +753,7 @@ async fn create_page_source_for_directory(
     is_api_path: bool,
     node_root: Vc<FileSystemPath>,
     render_data: Vc<JsonValue>,
+    locales: Vc<Vec<String>>,
 ) -> Result<Vc<Box<dyn ContentSource>>> {
     let PagesDirectoryStructure {
         ref items,
@@ -693,6 +774,7 @@ async fn create_page_source_for_directory(
             server_context,
             server_data_context,
             client_context,
+            client_chunking_context,
             pages_dir,
             Vc::upcast(FileSource::new(project_path)),
             runtime_entries,
@@ -703,6 +785,7 @@ async fn create_page_source_for_directory(
             node_root,
             node_root,
             render_data,
+            locales,
         )
         .issue_file_path(
             project_path,
@@ -724,6 +807,7 @@ async fn create_page_source_for_directory(
             server_context,
             server_data_context,
             client_context,
+            client_chunking_context,
             pages_dir,
             runtime_entries,
             fallback_page,
@@ -731,6 +815,7 @@ async fn create_page_source_for_directory(
             is_api_path,
             node_root,
             render_data,
+            locales,
         ))
     }
 
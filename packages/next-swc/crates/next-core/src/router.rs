@@ -1,8 +1,8 @@
 use anyhow::{anyhow, bail, Context, Result};
-use futures::StreamExt;
+use futures::TryStreamExt;
 use indexmap::indexmap;
-use serde::Deserialize;
-use serde_json::json;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
 use turbo_tasks::{util::SharedError, Completion, Completions, Value, Vc};
 use turbo_tasks_fs::json::parse_json_with_source_context;
 use turbopack_binding::{
@@ -18,7 +18,6 @@ use turbopack_binding::{
             context::AssetContext,
             environment::{ServerAddr, ServerInfo},
             file_source::FileSource,
-            ident::AssetIdent,
             issue::IssueDescriptionExt,
             module::Module,
             reference_type::{EcmaScriptModulesReferenceSubType, InnerAssets, ReferenceType},
@@ -26,9 +25,10 @@ use turbopack_binding::{
             virtual_source::VirtualSource,
         },
         dev::DevChunkingContext,
+        dev_server::source::Body,
         node::{
             debug::should_debug,
-            evaluate::{evaluate, get_evaluate_pool},
+            evaluate::get_evaluate_pool,
             execution_context::ExecutionContext,
             source_map::{trace_stack_with_source_mapping_assets, StructuredError},
         },
@@ -68,7 +68,6 @@ pub struct RouterRequest {
     pub pathname: String,
     pub raw_query: String,
     pub raw_headers: Vec<(String, String)>,
-    pub body: Vec<Bytes>,
 }
 
 #[turbo_tasks::value(shared)]
@@ -91,6 +90,26 @@ pub struct MiddlewareHeadersResponse {
 #[derive(Debug, Clone, Default)]
 pub struct MiddlewareBodyResponse(Bytes);
 
+/// Messages sent to the router's evaluation. The request body is streamed to
+/// the router after it has been invoked.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum RouterOutgoingMessage<'a> {
+    Evaluate { args: Vec<&'a JsonValue> },
+    BodyChunk { data: &'a [u8] },
+    BodyEnd,
+}
+
+/// Messages received from the router's evaluation, each value containing a
+/// [RouterIncomingMessage].
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum RouterEvaluationMessage {
+    Value { data: String },
+    End { data: Option<String> },
+    Error(StructuredError),
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(tag = "type", rename_all = "kebab-case")]
 enum RouterIncomingMessage {
@@ -275,6 +294,7 @@ fn edge_transition_map(
 pub async fn route(
     execution_context: Vc<ExecutionContext>,
     request: Vc<RouterRequest>,
+    body: Vc<Body>,
     next_config: Vc<NextConfig>,
     server_addr: Vc<ServerAddr>,
     routes_changed: Vc<Completion>,
@@ -287,6 +307,7 @@ pub async fn route(
     route_internal(
         execution_context,
         request,
+        body,
         next_config,
         server_addr,
         routes_changed,
@@ -311,6 +332,7 @@ macro_rules! shared_anyhow {
 async fn route_internal(
     execution_context: Vc<ExecutionContext>,
     request: Vc<RouterRequest>,
+    body: Vc<Body>,
     next_config: Vc<NextConfig>,
     server_addr: Vc<ServerAddr>,
     routes_changed: Vc<Completion>,
@@ -343,119 +365,138 @@ async fn route_internal(
     let Some(dir) = to_sys_path(project_path).await? else {
         bail!("Next.js requires a disk path to check for valid routes");
     };
+    let dir = JsonValue::from(dir.to_string_lossy());
     let chunking_context = chunking_context.with_layer("router".to_string());
     let server_addr = server_addr.await?;
+    let server_info = serde_json::to_value(ServerInfo::try_from(&*server_addr)?)?;
     let invalidation = Completions::all(vec![next_config_changed, routes_changed]);
     let debug = should_debug("router");
-    let result = evaluate(
+    let pool = get_evaluate_pool(
         router_asset,
         project_path,
         env,
-        AssetIdent::from_path(project_path),
         context,
-        chunking_context.with_layer("router".to_string()),
+        chunking_context,
         None,
-        vec![
-            Vc::cell(request),
-            Vc::cell(dir.to_string_lossy().into()),
-            Vc::cell(serde_json::to_value(ServerInfo::try_from(&*server_addr)?)?),
-        ],
         invalidation,
         debug,
     )
     .await?;
 
-    let mut read = result.read();
-
-    let first = match read.next().await {
-        Some(Ok(first)) => first,
-        Some(Err(e)) => {
-            return Ok(RouterResult::Error(SharedError::new(
-                anyhow!(e)
-                    .context("router evaluation failed: received error from javascript stream"),
-            ))
-            .cell())
-        }
-        None => {
+    // The request body is streamed to the router, which passes it on to
+    // middleware while it's still being received.
+    let mut operation = pool.operation().await?;
+    operation
+        .send(RouterOutgoingMessage::Evaluate {
+            args: vec![&request, &dir, &server_info],
+        })
+        .await?;
+    let mut body = body.await?.read();
+    while let Some(data) = body.try_next().await? {
+        operation
+            .send(RouterOutgoingMessage::BodyChunk { data: &data })
+            .await?;
+    }
+    operation.send(RouterOutgoingMessage::BodyEnd).await?;
+
+    // Results are returned from the router, so they end the evaluation, while
+    // middleware responses are sent as values.
+    let (first, ended) = match operation.recv::<RouterEvaluationMessage>().await? {
+        RouterEvaluationMessage::Value { data } => (data, false),
+        RouterEvaluationMessage::End { data: Some(data) } => (data, true),
+        RouterEvaluationMessage::End { data: None } => {
             return Ok(RouterResult::Error(shared_anyhow!(
                 "router evaluation failed: no message received from javascript stream"
             ))
             .cell())
         }
+        RouterEvaluationMessage::Error(error) => {
+            operation.disallow_reuse();
+            return Ok(RouterResult::Error(SharedError::new(
+                anyhow!(
+                    trace_stack_with_source_mapping_assets(
+                        error,
+                        pool.assets_for_source_mapping,
+                        chunking_context.output_root(),
+                        project_path
+                    )
+                    .await?
+                )
+                .context("router evaluation failed: received error from javascript stream"),
+            ))
+            .cell());
+        }
     };
-    let first = first.to_str()?;
-    let first: RouterIncomingMessage = parse_json_with_source_context(first)
+    let first: RouterIncomingMessage = parse_json_with_source_context(&first)
         .with_context(|| format!("parsing incoming message ({})", first))?;
 
-    let (res, read) = match first {
-        RouterIncomingMessage::Rewrite { data } => (RouterResult::Rewrite(data), Some(read)),
+    let res = match first {
+        RouterIncomingMessage::Rewrite { data } => RouterResult::Rewrite(data),
 
         RouterIncomingMessage::MiddlewareHeaders { data } => {
             // The double encoding here is annoying. It'd be a lot nicer if we could embed
             // a buffer directly into the IPC message without having to wrap it in an
             // object.
-            let body = read.map(|data| {
-                let chunk: RouterIncomingMessage = data?
-                    .to_str()
-                    .context("error decoding string")
-                    .and_then(parse_json_with_source_context)?;
-                match chunk {
-                    RouterIncomingMessage::MiddlewareBody { data } => Ok(Bytes::from(data)),
-                    m => Err(shared_anyhow!("unexpected message type: {:#?}", m)),
-                }
+            let body = futures::stream::unfold(Some(operation), |operation| async move {
+                let mut operation = operation?;
+                let chunk = match operation.recv::<RouterEvaluationMessage>().await {
+                    Ok(RouterEvaluationMessage::Value { data }) => data,
+                    Ok(RouterEvaluationMessage::End { .. }) => return None,
+                    Ok(RouterEvaluationMessage::Error(error)) => {
+                        operation.disallow_reuse();
+                        return Some((
+                            Err(shared_anyhow!(
+                                "error while streaming middleware body: {:?}",
+                                error
+                            )),
+                            None,
+                        ));
+                    }
+                    Err(err) => return Some((Err(SharedError::new(err)), None)),
+                };
+                let chunk = parse_json_with_source_context::<RouterIncomingMessage>(&chunk)
+                    .context("error decoding middleware body")
+                    .map_err(SharedError::new)
+                    .and_then(|chunk| match chunk {
+                        RouterIncomingMessage::MiddlewareBody { data } => Ok(Bytes::from(data)),
+                        m => Err(shared_anyhow!("unexpected message type: {:#?}", m)),
+                    });
+                Some((chunk, Some(operation)))
             });
             let middleware = MiddlewareResponse {
                 status_code: data.status_code,
                 headers: data.headers,
-                body: Stream::from(body),
+                body: Stream::from(Box::pin(body)),
             };
 
-            (RouterResult::Middleware(middleware), None)
+            return Ok(RouterResult::Middleware(middleware).cell());
         }
 
-        RouterIncomingMessage::None => (RouterResult::None, Some(read)),
-
-        RouterIncomingMessage::Error { error } => {
-            // Must be the same pool as above
-            let pool = get_evaluate_pool(
-                router_asset,
-                project_path,
-                env,
-                context,
-                chunking_context,
-                None,
-                invalidation,
-                debug,
-            )
-            .await?;
-            (
-                RouterResult::Error(shared_anyhow!(
-                    trace_stack_with_source_mapping_assets(
-                        error,
-                        pool.assets_for_source_mapping,
-                        chunking_context.output_root(),
-                        project_path
-                    )
-                    .await?
-                )),
-                Some(read),
+        RouterIncomingMessage::None => RouterResult::None,
+
+        RouterIncomingMessage::Error { error } => RouterResult::Error(shared_anyhow!(
+            trace_stack_with_source_mapping_assets(
+                error,
+                pool.assets_for_source_mapping,
+                chunking_context.output_root(),
+                project_path
             )
-        }
+            .await?
+        )),
 
-        RouterIncomingMessage::MiddlewareBody { .. } => (
-            RouterResult::Error(shared_anyhow!(
-                "unexpected incoming middleware body without middleware headers"
-            )),
-            Some(read),
-        ),
+        RouterIncomingMessage::MiddlewareBody { .. } => RouterResult::Error(shared_anyhow!(
+            "unexpected incoming middleware body without middleware headers"
+        )),
     };
 
-    // Middleware will naturally drain the full stream, but the rest only take a
-    // single item. In order to free the NodeJsOperation, we must pull another
-    // value out of the stream.
-    if let Some(mut read) = read {
-        if let Some(v) = read.next().await {
-            bail!("unexpected message type: {:#?}", v);
+    // Middleware will naturally drain the full evaluation, but a value sent
+    // otherwise must be followed by the end of the evaluation, in order to free
+    // the NodeJsOperation.
+    if !ended {
+        let message = operation.recv::<RouterEvaluationMessage>().await?;
+        if !matches!(message, RouterEvaluationMessage::End { data: None }) {
+            operation.disallow_reuse();
+            bail!("unexpected message type: {:#?}", message);
         }
     }
 
@@ -1,11 +1,11 @@
 use anyhow::{anyhow, bail, Context, Result};
-use futures::{Stream, TryStreamExt};
 use indexmap::IndexSet;
 use turbo_tasks::{Completion, Completions, Value, Vc};
 use turbopack_binding::turbopack::{
     core::{
         environment::ServerAddr,
         introspect::{Introspectable, IntrospectableChildren},
+        resolve::{find_context_file, FindContextFileResult},
     },
     dev_server::source::{
         route_tree::{RouteTree, RouteType},
@@ -17,9 +17,16 @@ use turbopack_binding::turbopack::{
 
 use crate::{
     app_structure::OptionAppDir,
-    next_config::NextConfig,
+    custom_routes::{
+        header_routes, matched_headers, matched_redirect, merge_headers, redirect_routes,
+        RouteRequest,
+    },
+    i18n::locale_redirect,
+    middleware::middleware_files,
+    next_config::{Headers, NextConfig, Redirects},
     pages_structure::PagesStructure,
     router::{route, RouterRequest, RouterResult},
+    util::remove_trailing_slash,
 };
 
 #[turbo_tasks::value(shared)]
@@ -28,6 +35,8 @@ pub struct NextRouterContentSource {
     inner: Vc<Box<dyn ContentSource>>,
     execution_context: Vc<ExecutionContext>,
     next_config: Vc<NextConfig>,
+    headers: Vc<Headers>,
+    redirects: Vc<Redirects>,
     server_addr: Vc<ServerAddr>,
     app_dir: Vc<OptionAppDir>,
     pages_structure: Vc<PagesStructure>,
@@ -40,6 +49,8 @@ impl NextRouterContentSource {
         inner: Vc<Box<dyn ContentSource>>,
         execution_context: Vc<ExecutionContext>,
         next_config: Vc<NextConfig>,
+        headers: Vc<Headers>,
+        redirects: Vc<Redirects>,
         server_addr: Vc<ServerAddr>,
         app_dir: Vc<OptionAppDir>,
         pages_structure: Vc<PagesStructure>,
@@ -48,6 +59,8 @@ impl NextRouterContentSource {
             inner,
             execution_context,
             next_config,
+            headers,
+            redirects,
             server_addr,
             app_dir,
             pages_structure,
@@ -124,26 +137,78 @@ impl GetContentSourceContent for NextRouterContentSource {
             bail!("missing data for router");
         };
 
-        // TODO: change router so we can stream the request body to it
-        let mut body_stream = body.await?.read();
+        let pathname = format!("/{path}");
+        let route_request = RouteRequest {
+            pathname: &pathname,
+            raw_query,
+            raw_headers,
+        };
+        let project_path = this.execution_context.await?.project_path;
+        // The Next.js router applies the `headers` from next.config.js to the
+        // responses it routes itself, so they only need to be matched here for
+        // the redirects answered before it runs.
+        let custom_headers = matched_headers(
+            &header_routes(this.headers, project_path).await?,
+            &route_request,
+        );
+
+        if let Some(redirect) = matched_redirect(
+            &redirect_routes(this.redirects, project_path).await?,
+            &route_request,
+        ) {
+            let mut headers = custom_headers;
+            merge_headers(&mut headers, redirect.headers());
+            return Ok(ContentSourceContent::HttpProxy(
+                ProxyResult {
+                    status: redirect.status_code,
+                    headers,
+                    body: "".into(),
+                }
+                .cell(),
+            )
+            .cell());
+        }
 
-        let mut body = Vec::with_capacity(body_stream.size_hint().0);
-        while let Some(data) = body_stream.try_next().await? {
-            body.push(data);
+        // Locale detection is handled by the Next.js server rather than its router,
+        // so it has to be done here.
+        if let Some(i18n) = &*this.next_config.i18n().await? {
+            let base_path = this.next_config.base_path().await?;
+            if let Some(location) = locale_redirect(i18n, &base_path, &route_request) {
+                let mut headers = custom_headers;
+                merge_headers(&mut headers, vec![("Location".to_string(), location)]);
+                return Ok(ContentSourceContent::HttpProxy(
+                    ProxyResult {
+                        status: 307,
+                        headers,
+                        body: "".into(),
+                    }
+                    .cell(),
+                )
+                .cell());
+            }
         }
 
+        // The router only passes the request body on to middleware, which
+        // receives it as it's streamed in. Without middleware, the body is left
+        // untouched for the rewritten source.
+        let body = if *has_middleware(self).await? {
+            *body
+        } else {
+            Body::from("").cell()
+        };
+
         let request = RouterRequest {
-            pathname: format!("/{path}"),
+            pathname,
             method: method.clone(),
             raw_headers: raw_headers.clone(),
             raw_query: raw_query.clone(),
-            body,
         }
         .cell();
 
         let res = route(
             this.execution_context,
             request,
+            body,
             this.next_config,
             this.server_addr,
             routes_changed(this.app_dir, this.pages_structure, this.next_config),
@@ -161,13 +226,17 @@ impl GetContentSourceContent for NextRouterContentSource {
                 )))
             }
             RouterResult::None => {
-                let rewrite =
-                    RewriteBuilder::new_source_with_path_and_query(this.inner, format!("/{path}"));
+                let rewrite = RewriteBuilder::new_source_with_path_and_query(
+                    this.inner,
+                    without_trailing_slash(&format!("/{path}")),
+                );
                 ContentSourceContent::Rewrite(rewrite.build()).cell()
             }
             RouterResult::Rewrite(data) => {
-                let mut rewrite =
-                    RewriteBuilder::new_source_with_path_and_query(this.inner, data.url.clone());
+                let mut rewrite = RewriteBuilder::new_source_with_path_and_query(
+                    this.inner,
+                    without_trailing_slash(&data.url),
+                );
                 if !data.headers.is_empty() {
                     rewrite = rewrite.response_headers(HeaderList::new(data.headers.clone()));
                 }
@@ -186,6 +255,29 @@ impl GetContentSourceContent for NextRouterContentSource {
     }
 }
 
+#[turbo_tasks::function]
+async fn has_middleware(source: Vc<NextRouterContentSource>) -> Result<Vc<bool>> {
+    let this = source.await?;
+    let project_path = this.execution_context.await?.project_path;
+    let middleware = find_context_file(
+        project_path,
+        middleware_files(this.next_config.page_extensions()),
+    );
+    Ok(Vc::cell(matches!(
+        *middleware.await?,
+        FindContextFileResult::Found(..)
+    )))
+}
+
+/// Pages are served without a trailing slash, whatever the `trailingSlash`
+/// setting, so it's removed before passing the request on.
+fn without_trailing_slash(path_and_query: &str) -> String {
+    match path_and_query.split_once('?') {
+        Some((pathname, query)) => format!("{}?{query}", remove_trailing_slash(pathname)),
+        None => remove_trailing_slash(path_and_query).to_string(),
+    }
+}
+
 fn formated_query(query: &str) -> String {
     if query.is_empty() {
         "".to_string()
@@ -23,7 +23,7 @@ use turbopack_binding::{
 };
 
 use crate::{
-    next_config::{NextConfig, OutputType},
+    next_config::{I18NConfig, NextConfig, OutputType},
     next_import_map::get_next_package,
 };
 
@@ -80,6 +80,25 @@ pub fn get_asset_path_from_pathname(pathname: &str, ext: &str) -> String {
     format!("{}{}", get_asset_prefix_from_pathname(pathname), ext)
 }
 
+/// Returns the path of the HTML file a page is statically emitted to, e.g.
+/// `/about.html`, or `/about/index.html` with `trailingSlash`, so that static
+/// file servers serve it under the URL next.js redirects to.
+pub fn get_html_path_from_pathname(pathname: &str, trailing_slash: bool) -> String {
+    if trailing_slash && pathname != "/" {
+        format!("{}/index.html", pathname.trim_end_matches('/'))
+    } else {
+        get_asset_path_from_pathname(pathname, ".html")
+    }
+}
+
+/// Removes the trailing slash of a path, keeping the root path as it is.
+pub fn remove_trailing_slash(path: &str) -> &str {
+    match path.strip_suffix('/') {
+        Some(stripped) if !stripped.is_empty() => stripped,
+        _ => path,
+    }
+}
+
 pub async fn foreign_code_context_condition(
     next_config: Vc<NextConfig>,
 ) -> Result<ContextCondition> {
@@ -385,6 +404,9 @@ pub async fn render_data(
     #[serde(rename_all = "camelCase")]
     struct Data {
         next_config_output: Option<OutputType>,
+        base_path: String,
+        asset_prefix: String,
+        i18n: Option<I18NConfig>,
         server_info: Option<ServerInfo>,
         allowed_revalidate_header_keys: Option<Vec<String>>,
         fetch_cache_key_prefix: Option<String>,
@@ -399,6 +421,9 @@ pub async fn render_data(
 
     let value = serde_json::to_value(Data {
         next_config_output: config.output.clone(),
+        base_path: config.base_path.clone(),
+        asset_prefix: next_config.computed_asset_prefix().await?.clone_value(),
+        i18n: config.i18n.clone(),
         server_info: server_info.ok(),
         allowed_revalidate_header_keys: experimental.allowed_revalidate_header_keys.clone(),
         fetch_cache_key_prefix: experimental.fetch_cache_key_prefix.clone(),
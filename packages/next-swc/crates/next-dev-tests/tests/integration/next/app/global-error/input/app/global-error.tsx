@@ -0,0 +1,11 @@
+'use client'
+
+export default function GlobalError() {
+  return (
+    <html>
+      <body>
+        <div data-test-global-error>Custom global error</div>
+      </body>
+    </html>
+  )
+}
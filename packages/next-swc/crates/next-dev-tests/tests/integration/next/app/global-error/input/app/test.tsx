@@ -0,0 +1,29 @@
+'use client'
+
+import { useRef } from 'react'
+import { useTestHarness, Harness } from '@turbo/pack-test-harness'
+
+export default function Test() {
+  const iframeRef = useRef<HTMLIFrameElement | null>(null)
+
+  useTestHarness((harness) => runTests(harness, iframeRef.current!))
+
+  return <iframe style={{ width: 800, height: 600 }} ref={iframeRef} />
+}
+
+function runTests(harness: Harness, iframe: HTMLIFrameElement) {
+  const TIMEOUT = 20000
+
+  it(
+    'renders the custom global error page',
+    async () => {
+      await harness.load(iframe, '/throw')
+      await harness.waitForSelector(iframe, '[data-test-global-error]')
+
+      expect(
+        iframe.contentDocument!.querySelector('[data-test-global-error]')
+      ).not.toBeNull()
+    },
+    TIMEOUT
+  )
+}
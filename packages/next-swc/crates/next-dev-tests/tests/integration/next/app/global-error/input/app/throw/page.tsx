@@ -0,0 +1,3 @@
+export default function ThrowPage() {
+  throw new Error('Thrown from a page without an error boundary')
+}
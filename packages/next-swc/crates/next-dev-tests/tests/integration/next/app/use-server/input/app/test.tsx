@@ -1,20 +1,19 @@
 'use client'
 
 import { useTestHarness } from '@turbo/pack-test-harness'
+import action from './action'
 
-export default function Test({ action }) {
+export default function Test({ action: actionProp, result }) {
   useTestHarness(() => {
     it('should run', () => {})
-    it('should throw an error when importing server action in client component', async () => {
-      await expect(import('./action')).rejects.toMatchObject({
-        message:
-          /Server actions \("use server"\) are not yet supported in Turbopack/,
-      })
+    it('should call server actions in server components', () => {
+      expect(result).toBe(42)
     })
-    it('should throw an error when importing server action in server component', () => {
-      expect(action).toMatch(
-        /Server actions \("use server"\) are not yet supported in Turbopack/
-      )
+    it('should pass server actions to client components', () => {
+      expect(actionProp).toEqual(expect.any(Function))
+    })
+    it('should import server actions in client components', () => {
+      expect(action).toEqual(expect.any(Function))
     })
   })
 }
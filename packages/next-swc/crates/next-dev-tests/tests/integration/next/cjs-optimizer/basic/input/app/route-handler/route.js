@@ -0,0 +1,9 @@
+const server = require('next/server')
+
+function getNextResponse() {
+  return server.NextResponse
+}
+
+export function GET() {
+  return getNextResponse().json({ source: getNextResponse.toString() })
+}
@@ -0,0 +1,29 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+const server = require('next/server')
+
+function getNextResponse() {
+  return server.NextResponse
+}
+
+export default function Home({ source }) {
+  useTestHarness(() => runTests(source))
+
+  return 'index'
+}
+
+export function getServerSideProps() {
+  return { props: { source: getNextResponse.toString() } }
+}
+
+function runTests(source) {
+  it('should optimize next/server requires in pages', () => {
+    expect(source).not.toContain('server.NextResponse')
+  })
+
+  it('should optimize next/server requires in app routes', async () => {
+    const res = await fetch('/route-handler')
+    const json = await res.json()
+    expect(json.source).not.toContain('server.NextResponse')
+  })
+}
@@ -0,0 +1,19 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Home() {
+  useTestHarness(runTests)
+
+  return <div id="element" data-custom="removed" data-test="kept" />
+}
+
+function runTests() {
+  it('should remove the configured properties', () => {
+    const element = document.getElementById('element')
+    expect(element.hasAttribute('data-custom')).toBe(false)
+  })
+
+  it('should keep other properties', () => {
+    const element = document.getElementById('element')
+    expect(element.getAttribute('data-test')).toBe('kept')
+  })
+}
@@ -0,0 +1,22 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+function log() {
+  console.log('removed')
+  console.error('kept')
+}
+
+export default function Home() {
+  useTestHarness(runTests)
+
+  return 'index'
+}
+
+function runTests() {
+  it('should remove console calls', () => {
+    expect(log.toString()).not.toContain('console.log')
+  })
+
+  it('should keep excluded console methods', () => {
+    expect(log.toString()).toContain('console.error')
+  })
+}
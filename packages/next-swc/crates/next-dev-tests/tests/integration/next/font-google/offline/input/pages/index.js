@@ -0,0 +1,59 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+import { Inter } from 'next/font/google'
+
+const interNoArgs = Inter()
+
+export default function Home() {
+  useTestHarness(runTests)
+
+  return <div className={interNoArgs.className}>Test</div>
+}
+
+function runTests() {
+  it('returns structured data about the font styles when Google Fonts is unavailable', () => {
+    expect(interNoArgs).toEqual({
+      className: 'className__inter_34ab8b4d__7bdff866',
+      style: {
+        fontFamily: "'__Inter_34ab8b', '__Inter_Fallback_34ab8b'",
+        fontStyle: 'normal',
+      },
+    })
+  })
+
+  it('falls back to the fallback font when the stylesheet is not cached', async () => {
+    const fontFaces = await getFontFaceFamilies()
+    expect(fontFaces).toContain('__Inter_Fallback_34ab8b')
+    expect(fontFaces).not.toContain('__Inter_34ab8b')
+  })
+}
+
+async function getFontFaceFamilies() {
+  const families = []
+
+  for (const stylesheet of document.querySelectorAll('link[rel=stylesheet]')) {
+    if (stylesheet.sheet == null) {
+      // Wait for the stylesheet to load completely if it hasn't already
+      await new Promise((resolve) => {
+        stylesheet.addEventListener('load', resolve)
+      })
+    }
+
+    collectFontFaceFamilies(stylesheet.sheet.cssRules, families)
+  }
+
+  return families
+}
+
+function collectFontFaceFamilies(rules, families) {
+  for (const rule of rules) {
+    if (rule instanceof CSSFontFaceRule) {
+      families.push(
+        rule.style.getPropertyValue('font-family').replace(/["']/g, '')
+      )
+    }
+
+    if (rule instanceof CSSLayerBlockRule) {
+      collectFontFaceFamilies(rule.cssRules, families)
+    }
+  }
+}
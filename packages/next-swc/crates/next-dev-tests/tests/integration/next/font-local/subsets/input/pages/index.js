@@ -0,0 +1,79 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+import localFont from 'next/font/local'
+
+// Reuses the font bundled with @vercel/og. The test runs from a copy in
+// tests/temp, which has the same depth as tests/integration.
+// The font only has latin glyphs, so there's no cyrillic subset
+const notoSans = localFont({
+  src: '../../../../../../../../../../next/src/compiled/@vercel/og/noto-sans-v27-latin-regular.ttf',
+  subsets: ['latin', 'cyrillic'],
+})
+
+export default function Home() {
+  useTestHarness(runTests)
+
+  return <div className={notoSans.className}>Test</div>
+}
+
+function runTests() {
+  it('declares a font face for each subset with glyphs', async () => {
+    const fontFaceRules = await getFontFaceRules()
+    expect(fontFaceRules).toHaveLength(1)
+
+    const { style } = fontFaceRules[0]
+    expect(style.getPropertyValue('unicode-range')).toContain('U+131')
+  })
+
+  it('serves the subsets as WOFF2 files', async () => {
+    const [fontFaceRule] = await getFontFaceRules()
+    const src = fontFaceRule.style.getPropertyValue('src')
+    const url = src.match(/url\("?([^")]+)"?\)/)[1]
+
+    const res = await fetch(url)
+    expect(res.status).toBe(200)
+    const signature = new Uint8Array(await res.arrayBuffer()).slice(0, 4)
+    expect(String.fromCharCode(...signature)).toBe('wOF2')
+  })
+
+  it('loads the subsets in the browser', async () => {
+    const fontFaces = Array.from(document.fonts).filter(
+      (fontFace) => !fontFace.family.includes('Fallback')
+    )
+    expect(fontFaces).toHaveLength(1)
+
+    // Rejects when the font can't be decoded
+    await fontFaces[0].load()
+    expect(fontFaces[0].status).toBe('loaded')
+  })
+}
+
+async function getFontFaceRules() {
+  const rules = []
+
+  for (const stylesheet of document.querySelectorAll('link[rel=stylesheet]')) {
+    if (stylesheet.sheet == null) {
+      // Wait for the stylesheet to load completely if it hasn't already
+      await new Promise((resolve) => {
+        stylesheet.addEventListener('load', resolve)
+      })
+    }
+
+    collectFontFaceRules(stylesheet.sheet.cssRules, rules)
+  }
+
+  return rules.filter(
+    (rule) => !rule.style.getPropertyValue('font-family').includes('Fallback')
+  )
+}
+
+function collectFontFaceRules(cssRules, rules) {
+  for (const rule of cssRules) {
+    if (rule instanceof CSSFontFaceRule) {
+      rules.push(rule)
+    }
+
+    if (rule instanceof CSSLayerBlockRule) {
+      collectFontFaceRules(rule.cssRules, rules)
+    }
+  }
+}
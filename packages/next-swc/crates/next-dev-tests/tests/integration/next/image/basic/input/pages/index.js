@@ -105,6 +105,27 @@ function runTests() {
     expect(res.status).toBe(200)
   })
 
+  it('should serve the negotiated image format', async () => {
+    const res = await fetch('/_next/image?url=%2Ftriangle-black.png&w=64&q=75', {
+      headers: { Accept: 'image/webp,*/*' },
+    })
+    expect(res.status).toBe(200)
+    expect(res.headers.get('vary')).toBe('Accept')
+    // This build has no WebP encoder, so the image keeps its source format.
+    expect(res.headers.get('content-type')).toBe('image/png')
+    expect(res.headers.get('content-disposition')).toBe(
+      'inline; filename="triangle-black.png"'
+    )
+  })
+
+  it('should reject SVGs with a misleading extension', async () => {
+    const res = await fetch('/_next/image?url=%2Fdisguised-svg.png&w=64&q=75')
+    expect(res.status).toBe(400)
+    expect(await res.text()).toBe(
+      '"url" parameter is valid but image type is not allowed'
+    )
+  })
+
   it('should link to imported broken image', async () => {
     const img = document.querySelector('#broken')
     expect(img.src).toContain(encodeURIComponent('_next/static/media'))
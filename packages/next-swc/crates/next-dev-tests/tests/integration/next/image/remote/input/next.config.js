@@ -0,0 +1,17 @@
+/**@type {import('next').NextConfig} */
+const config = {
+  reactStrictMode: true,
+  env: {
+    MOCK_SERVER: process.env.TURBOPACK_TEST_ONLY_MOCK_SERVER,
+  },
+  images: {
+    minimumCacheTTL: 60,
+    remotePatterns: [
+      {
+        hostname: '127.0.0.1',
+      },
+    ],
+  },
+}
+
+module.exports = config
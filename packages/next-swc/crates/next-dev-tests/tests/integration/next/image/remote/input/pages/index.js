@@ -0,0 +1,47 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Home() {
+  useTestHarness(runTests)
+
+  return <div>Test</div>
+}
+
+function imageUrl(url) {
+  return `/_next/image?url=${encodeURIComponent(url)}&w=64&q=75`
+}
+
+function runTests() {
+  it('should reject images from hosts that are not allowed', async () => {
+    const res = await fetch(imageUrl('https://example.com/test.jpg'))
+    expect(res.status).toBe(400)
+    expect(await res.text()).toBe('"url" parameter is not allowed')
+  })
+
+  it('should optimize allowed remote images', async () => {
+    const res = await fetch(imageUrl(`${process.env.MOCK_SERVER}/cached.png`))
+    expect(res.status).toBe(200)
+    expect(res.headers.get('content-type')).toBe('image/png')
+    const image = await createImageBitmap(await res.blob())
+    expect(image.width).toBe(64)
+  })
+
+  it('should cache remote images for minimumCacheTTL', async () => {
+    const res = await fetch(imageUrl(`${process.env.MOCK_SERVER}/cached.png`))
+    expect(res.headers.get('cache-control')).toBe(
+      'public, max-age=60, must-revalidate'
+    )
+  })
+
+  it('should forward the status of invalid upstream responses', async () => {
+    const res = await fetch(imageUrl(`${process.env.MOCK_SERVER}/missing.png`))
+    expect(res.status).toBe(404)
+  })
+
+  it('should reject SVGs unless dangerouslyAllowSVG is set', async () => {
+    const res = await fetch(imageUrl(`${process.env.MOCK_SERVER}/icon.svg`))
+    expect(res.status).toBe(400)
+    expect(await res.text()).toBe(
+      '"url" parameter is valid but image type is not allowed'
+    )
+  })
+}
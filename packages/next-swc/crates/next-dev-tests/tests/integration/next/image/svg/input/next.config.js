@@ -0,0 +1,10 @@
+/**@type {import('next').NextConfig} */
+const config = {
+  reactStrictMode: true,
+  images: {
+    dangerouslyAllowSVG: true,
+    contentSecurityPolicy: "script-src 'none'; frame-src 'none'; sandbox;",
+  },
+}
+
+module.exports = config
@@ -0,0 +1,22 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Home() {
+  useTestHarness(runTests)
+
+  return <div>Test</div>
+}
+
+function runTests() {
+  it('should serve allowed SVGs with a restrictive CSP', async () => {
+    const res = await fetch('/_next/image?url=%2Ftest.svg&w=64&q=75')
+    expect(res.status).toBe(200)
+    expect(res.headers.get('content-type')).toBe('image/svg+xml')
+    expect(res.headers.get('content-security-policy')).toBe(
+      "script-src 'none'; frame-src 'none'; sandbox;"
+    )
+    expect(res.headers.get('content-disposition')).toBe(
+      'inline; filename="test.svg"'
+    )
+    expect(await res.text()).toContain('<svg')
+  })
+}
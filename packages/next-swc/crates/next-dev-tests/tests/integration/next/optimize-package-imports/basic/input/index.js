@@ -0,0 +1,14 @@
+import { a, renamed, c } from 'barrel'
+
+it('imports named exports from a barrel file', () => {
+  expect(a).toBe('a')
+  expect(renamed).toBe('b')
+})
+
+it('imports named exports through wildcard re-exports', () => {
+  expect(c).toBe('c')
+})
+
+it('does not evaluate modules for exports that are not imported', () => {
+  expect(globalThis.unusedEvaluated).toBeUndefined()
+})
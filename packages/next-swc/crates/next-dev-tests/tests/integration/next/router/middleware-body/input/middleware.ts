@@ -0,0 +1,16 @@
+import { NextResponse } from 'next/server'
+import type { NextRequest } from 'next/server'
+
+export async function middleware(req: NextRequest) {
+  if (req.nextUrl.pathname === '/middleware-echo') {
+    return new NextResponse(await req.arrayBuffer())
+  }
+
+  const res = NextResponse.next()
+  res.headers.set('x-middleware', 'passed')
+  return res
+}
+
+export const config = {
+  matcher: ['/middleware-echo', '/api/echo'],
+}
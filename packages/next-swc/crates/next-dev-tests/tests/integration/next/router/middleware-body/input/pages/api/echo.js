@@ -0,0 +1,13 @@
+export const config = {
+  api: {
+    bodyParser: false,
+  },
+}
+
+export default async function handler(req, res) {
+  const chunks = []
+  for await (const chunk of req) {
+    chunks.push(chunk)
+  }
+  res.status(200).send(Buffer.concat(chunks))
+}
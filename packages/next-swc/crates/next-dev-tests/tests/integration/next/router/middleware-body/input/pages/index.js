@@ -0,0 +1,41 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Foo() {
+  useTestHarness(runTests)
+
+  return 'index'
+}
+
+// Larger than the chunks the body is streamed in.
+const BODY_SIZE = 8 * 1024 * 1024
+
+function createBody() {
+  const body = new Uint8Array(BODY_SIZE)
+  for (let i = 0; i < body.length; i++) {
+    body[i] = i % 251
+  }
+  return body
+}
+
+async function expectEcho(res, body) {
+  expect(res.status).toBe(200)
+  const echoed = new Uint8Array(await res.arrayBuffer())
+  expect(echoed.length).toBe(body.length)
+  const mismatch = echoed.findIndex((byte, i) => byte !== body[i])
+  expect(mismatch).toBe(-1)
+}
+
+function runTests() {
+  it('streams a large request body to middleware', async () => {
+    const body = createBody()
+    const res = await fetch('/middleware-echo', { method: 'POST', body })
+    await expectEcho(res, body)
+  }, 60000)
+
+  it('passes a large request body through middleware', async () => {
+    const body = createBody()
+    const res = await fetch('/api/echo', { method: 'POST', body })
+    expect(res.headers.get('x-middleware')).toBe('passed')
+    await expectEcho(res, body)
+  }, 60000)
+}
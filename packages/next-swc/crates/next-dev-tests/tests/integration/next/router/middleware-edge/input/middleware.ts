@@ -0,0 +1,13 @@
+import { NextResponse } from 'next/server'
+import type { NextRequest } from 'next/server'
+
+export function middleware(request: NextRequest) {
+  return NextResponse.json({
+    runtime: process.env.NEXT_RUNTIME,
+    pathname: request.nextUrl.pathname,
+  })
+}
+
+export const config = {
+  matcher: '/runtime',
+}
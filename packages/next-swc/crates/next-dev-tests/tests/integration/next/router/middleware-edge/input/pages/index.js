@@ -0,0 +1,21 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Foo() {
+  useTestHarness(runTests)
+
+  return 'index'
+}
+
+function runTests() {
+  it('should run middleware in the edge runtime', async () => {
+    const res = await fetch('/runtime')
+    expect(res.status).toBe(200)
+    const json = await res.json()
+    expect(json).toEqual({ runtime: 'edge', pathname: '/runtime' })
+  })
+
+  it('should not run middleware for unmatched paths', async () => {
+    const res = await fetch('/')
+    expect(res.headers.get('content-type')).toMatch(/text\/html/)
+  })
+}
@@ -0,0 +1,23 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Foo() {
+  useTestHarness(runTests)
+
+  return 'index'
+}
+
+function runTests() {
+  it('should add a trailing slash to pages', async () => {
+    const res = await fetch('/about')
+    expect(res.redirected).toBe(true)
+    expect(new URL(res.url).pathname).toBe('/about/')
+    expect(await res.text()).toContain('about')
+  })
+
+  it('should remove the trailing slash of files', async () => {
+    const res = await fetch('/file.txt/')
+    expect(res.redirected).toBe(true)
+    expect(new URL(res.url).pathname).toBe('/file.txt')
+    expect(await res.text()).toBe('file\n')
+  })
+}
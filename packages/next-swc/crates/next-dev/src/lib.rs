@@ -27,7 +27,7 @@ use next_core::{
     dev_manifest::DevManifestContentSource,
     mode::NextMode,
     next_client::{get_client_chunking_context, get_client_compile_time_info},
-    next_config::{load_next_config, load_rewrites},
+    next_config::{load_headers, load_next_config, load_redirects, load_rewrites},
     next_image::NextImageContentSource,
     pages_structure::find_pages_structure,
     router_source::NextRouterContentSource,
@@ -338,6 +338,8 @@ async fn source(
     let next_config_execution_context = execution_context.with_layer("next_config".to_string());
     let next_config = load_next_config(next_config_execution_context);
     let rewrites = load_rewrites(next_config_execution_context);
+    let headers = load_headers(next_config_execution_context);
+    let redirects = load_redirects(next_config_execution_context);
 
     let output_root = output_fs.root().join(".next/server".to_string());
 
@@ -364,10 +366,13 @@ async fn source(
         browserslist_query.clone(),
         next_config,
     );
-    let client_compile_time_info = get_client_compile_time_info(mode, browserslist_query);
+    let client_compile_time_info =
+        get_client_compile_time_info(mode, browserslist_query, next_config);
     let client_chunking_context = get_client_chunking_context(
         project_path,
         dev_server_root,
+        next_config.computed_asset_prefix(),
+        next_config.production_browser_source_maps(),
         client_compile_time_info.environment(),
         mode,
     );
@@ -425,15 +430,27 @@ async fn source(
     );
     let main_source = Vc::upcast(main_source);
     let source_map_trace = Vc::upcast(NextSourceMapTraceContentSource::new(main_source));
-    let img_source = Vc::upcast(NextImageContentSource::new(main_source));
+    let img_source = Vc::upcast(NextImageContentSource::new(
+        main_source,
+        next_config.base_path(),
+        next_config.image_config(),
+    ));
     let router_source = Vc::upcast(NextRouterContentSource::new(
         main_source,
         execution_context,
         next_config,
+        headers,
+        redirects,
         server_addr,
         app_dir,
         pages_structure,
     ));
+    let image_path = next_config
+        .image_config()
+        .await?
+        .path
+        .trim_start_matches('/')
+        .to_string();
     let source = Vc::upcast(
         PrefixedRouterContentSource {
             prefix: Default::default(),
@@ -444,8 +461,7 @@ async fn source(
                     "__nextjs_original-stack-frame".to_string(),
                     source_map_trace,
                 ),
-                // TODO: Load path from next.config.js
-                ("_next/image".to_string(), img_source),
+                (image_path, img_source),
             ],
             fallback: router_source,
         }
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use serde::Deserialize;
-use turbopack_binding::swc::core::{
+use swc_core::{
     common::DUMMY_SP,
     ecma::{ast::*, utils::private_ident, visit::Fold},
 };
@@ -1,5 +1,5 @@
 use serde::Deserialize;
-use turbopack_binding::swc::core::{
+use swc_core::{
     common::{collections::AHashSet, DUMMY_SP},
     ecma::{
         ast::*,
@@ -10,6 +10,8 @@ use turbopack_binding::swc::core::{
 
 use crate::top_level_binding_collector::collect_top_level_decls;
 
+mod top_level_binding_collector;
+
 #[derive(Clone, Debug, Deserialize)]
 #[serde(untagged)]
 pub enum Config {
@@ -3,7 +3,7 @@ use std::convert::{TryFrom, TryInto};
 use hex::encode as hex_encode;
 use serde::Deserialize;
 use sha1::{Digest, Sha1};
-use turbopack_binding::swc::core::{
+use swc_core::{
     common::{
         comments::{Comment, CommentKind, Comments},
         errors::HANDLER,
@@ -1162,7 +1162,7 @@ fn collect_pat_idents(pat: &Pat, closure_idents: &mut Vec<Id>) {
     }
 }
 
-fn generate_action_id(file_name: String, export_name: String) -> String {
+pub fn generate_action_id(file_name: String, export_name: String) -> String {
     // Attach a checksum to the action using sha1:
     // $$id = sha1('file_name' + ':' + 'export_name');
     let mut hasher = Sha1::new();
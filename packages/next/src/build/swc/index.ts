@@ -157,6 +157,7 @@ export interface Binding {
       options: ProjectOptions,
       turboEngineOptions?: TurboEngineOptions
     ) => Promise<Project>
+    rootTaskCount?: () => number
   }
   minify: any
   minifySync: any
@@ -609,6 +610,16 @@ function bindingToApi(binding: any, _wasm: boolean) {
         }
       | undefined
     let canceled = false
+    // The native root task. It must be disposed exactly once, either when the
+    // generator finishes or when the consumer returns early while the
+    // generator is suspended at a `yield` and won't resume.
+    let task: { __napiType: 'RootTask' } | undefined
+    const dispose = () => {
+      if (task) {
+        binding.rootTaskDispose(task)
+        task = undefined
+      }
+    }
 
     // The native function will call this every time it emits a new result. We
     // either need to notify a waiting consumer, or buffer the new result until
@@ -627,7 +638,7 @@ function bindingToApi(binding: any, _wasm: boolean) {
     }
 
     const iterator = (async function* () {
-      const task = await withErrorCause(() => nativeFunction(emitResult))
+      task = await withErrorCause(() => nativeFunction(emitResult))
       try {
         while (!canceled) {
           if (buffer.length > 0) {
@@ -645,12 +656,13 @@ function bindingToApi(binding: any, _wasm: boolean) {
         if (e === cancel) return
         throw e
       } finally {
-        binding.rootTaskDispose(task)
+        dispose()
       }
     })()
     iterator.return = async () => {
       canceled = true
       if (waiting) waiting.reject(cancel)
+      else dispose()
       return { value: undefined, done: true } as IteratorReturnResult<never>
     }
     return iterator
@@ -1293,6 +1305,8 @@ function loadNative(importPath?: string) {
           },
         },
         createProject: bindingToApi(customBindings ?? bindings, false),
+        // Only exported by debug builds of the native bindings
+        rootTaskCount: (customBindings ?? bindings).rootTaskCount,
       },
       mdx: {
         compile: (src: string, options: any) =>
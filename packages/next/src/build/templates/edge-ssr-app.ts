@@ -0,0 +1,60 @@
+import '../../server/web/globals'
+import type { AdapterOptions } from '../../server/web/adapter'
+import type { NextConfigComplete } from '../../server/config-shared'
+import { adapter } from '../../server/web/adapter'
+import { getRender } from '../webpack/loaders/next-edge-ssr-loader/render'
+import { IncrementalCache } from '../../server/lib/incremental-cache'
+import { renderToHTMLOrFlight as renderToHTML } from '../../server/app-render/app-render'
+
+// Import the userland code.
+// @ts-expect-error - replaced by webpack/turbopack loader
+import * as pageMod from 'VAR_USERLAND'
+
+// These are injected by the loader afterwards.
+declare const nextConfig: NextConfigComplete
+declare const dev: boolean
+
+// INJECT:nextConfig
+// INJECT:dev
+
+const maybeJSONParse = (str?: string) => (str ? JSON.parse(str) : undefined)
+
+// The manifests are registered on the global scope by the other files of the
+// edge function.
+const manifests = self as any
+
+const render = getRender({
+  pagesType: 'app',
+  dev,
+  page: 'VAR_DEFINITION_PAGE',
+  appMod: null,
+  pageMod,
+  errorMod: null,
+  error500Mod: null,
+  appServerMod: null,
+  Document: null as any,
+  buildManifest: manifests.__BUILD_MANIFEST,
+  prerenderManifest: maybeJSONParse(manifests.__PRERENDER_MANIFEST),
+  reactLoadableManifest: maybeJSONParse(manifests.__REACT_LOADABLE_MANIFEST),
+  renderToHTML,
+  clientReferenceManifest: manifests.__RSC_MANIFEST?.['VAR_DEFINITION_PAGE'],
+  serverActionsManifest: maybeJSONParse(manifests.__RSC_SERVER_MANIFEST),
+  subresourceIntegrityManifest: maybeJSONParse(
+    manifests.__SUBRESOURCE_INTEGRITY_MANIFEST
+  ),
+  config: nextConfig,
+  buildId: 'VAR_BUILD_ID',
+  nextFontManifest: maybeJSONParse(manifests.__NEXT_FONT_MANIFEST),
+})
+
+export const ComponentMod = pageMod
+
+export default function (
+  opts: Omit<AdapterOptions, 'IncrementalCache' | 'handler'>
+) {
+  return adapter({
+    ...opts,
+    IncrementalCache,
+    handler: render,
+  })
+}
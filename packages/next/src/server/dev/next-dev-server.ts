@@ -752,6 +752,7 @@ export default class DevServer extends Server {
     query,
     params,
     isAppPath,
+    sriEnabled,
     appPaths = null,
     shouldEnsure,
   }: {
@@ -790,6 +791,7 @@ export default class DevServer extends Server {
         query,
         params,
         isAppPath,
+        sriEnabled,
         shouldEnsure,
       })
     } catch (err) {
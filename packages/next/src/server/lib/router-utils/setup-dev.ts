@@ -63,6 +63,9 @@ import {
   NEXT_FONT_MANIFEST,
   PAGES_MANIFEST,
   PHASE_DEVELOPMENT_SERVER,
+  REACT_LOADABLE_MANIFEST,
+  SERVER_REFERENCE_MANIFEST,
+  SUBRESOURCE_INTEGRITY_MANIFEST,
 } from '../../../shared/lib/constants'
 
 import {
@@ -92,6 +95,8 @@ import { PageNotFoundError } from '../../../shared/lib/utils'
 import { srcEmptySsgManifest } from '../../../build/webpack/plugins/build-manifest-plugin'
 import { PropagateToWorkersField } from './types'
 import { MiddlewareManifest } from '../../../build/webpack/plugins/middleware-plugin'
+import type { ActionManifest } from '../../../build/webpack/plugins/flight-client-entry-plugin'
+import type { ReactLoadableManifest } from '../../load-components'
 import { devPageFiles } from '../../../build/webpack/plugins/next-types-plugin/shared'
 import type { RenderWorkers } from '../router-server'
 import { pathToRegexp } from 'next/dist/compiled/path-to-regexp'
@@ -412,6 +417,12 @@ async function startWatcher(opts: SetupOpts) {
     const pagesManifests = new Map<string, PagesManifest>()
     const appPathsManifests = new Map<string, PagesManifest>()
     const middlewareManifests = new Map<string, MiddlewareManifest>()
+    const actionManifests = new Map<string, ActionManifest>()
+    const reactLoadableManifests = new Map<string, ReactLoadableManifest>()
+    const subresourceIntegrityManifests = new Map<
+      string,
+      Record<string, string>
+    >()
     const clientToHmrSubscription = new Map<
       ws,
       Map<string, AsyncIterator<any>>
@@ -462,6 +473,42 @@ async function startWatcher(opts: SetupOpts) {
       )
     }
 
+    async function loadActionManifest(pageName: string): Promise<void> {
+      actionManifests.set(
+        pageName,
+        await loadPartialManifest(
+          `${SERVER_REFERENCE_MANIFEST}.json`,
+          pageName,
+          'app'
+        )
+      )
+    }
+
+    async function loadReactLoadableManifest(pageName: string): Promise<void> {
+      reactLoadableManifests.set(
+        pageName,
+        await loadPartialManifest(REACT_LOADABLE_MANIFEST, pageName, 'app')
+      )
+    }
+
+    // Only written by turbopack when `experimental.sri` is enabled.
+    const sriEnabled = !!nextConfig.experimental.sri?.algorithm
+
+    async function loadSubresourceIntegrityManifest(
+      pageName: string,
+      type: 'app' | 'pages' = 'pages'
+    ): Promise<void> {
+      if (!sriEnabled) return
+      subresourceIntegrityManifests.set(
+        pageName,
+        await loadPartialManifest(
+          `${SUBRESOURCE_INTEGRITY_MANIFEST}.json`,
+          pageName,
+          type
+        )
+      )
+    }
+
     const buildingReported = new Set<string>()
 
     async function changeSubscription(
@@ -663,6 +710,53 @@ async function startWatcher(opts: SetupOpts) {
       return manifest
     }
 
+    function mergeActionManifests(
+      manifests: Iterable<ActionManifest>
+    ): ActionManifest {
+      const manifest: ActionManifest = {
+        node: {},
+        edge: {},
+      }
+
+      function mergeActionIds(
+        actionEntries: ActionManifest['node' | 'edge'],
+        other: ActionManifest['node' | 'edge']
+      ): void {
+        for (const key in other) {
+          const action = (actionEntries[key] ??= { workers: {}, layer: {} })
+          Object.assign(action.workers, other[key].workers)
+          Object.assign(action.layer, other[key].layer)
+        }
+      }
+
+      for (const m of manifests) {
+        mergeActionIds(manifest.node, m.node)
+        mergeActionIds(manifest.edge, m.edge)
+      }
+
+      return manifest
+    }
+
+    function mergeReactLoadableManifests(
+      manifests: Iterable<ReactLoadableManifest>
+    ): ReactLoadableManifest {
+      const manifest: ReactLoadableManifest = {}
+      for (const m of manifests) {
+        Object.assign(manifest, m)
+      }
+      return manifest
+    }
+
+    function mergeSubresourceIntegrityManifests(
+      manifests: Iterable<Record<string, string>>
+    ) {
+      const manifest: Record<string, string> = {}
+      for (const m of manifests) {
+        Object.assign(manifest, m)
+      }
+      return manifest
+    }
+
     async function writeBuildManifest(): Promise<void> {
       const buildManifest = mergeBuildManifests(buildManifests.values())
       const buildManifestPath = path.join(distDir, BUILD_MANIFEST)
@@ -752,6 +846,55 @@ async function startWatcher(opts: SetupOpts) {
       )
     }
 
+    async function writeActionManifest(): Promise<void> {
+      const actionManifest = mergeActionManifests(actionManifests.values())
+      const actionManifestJsonPath = path.join(
+        distDir,
+        'server',
+        `${SERVER_REFERENCE_MANIFEST}.json`
+      )
+      const actionManifestJsPath = path.join(
+        distDir,
+        'server',
+        `${SERVER_REFERENCE_MANIFEST}.js`
+      )
+      const json = JSON.stringify(actionManifest, null, 2)
+      await clearCache(actionManifestJsonPath)
+      await clearCache(actionManifestJsPath)
+      await writeFile(actionManifestJsonPath, json, 'utf-8')
+      await writeFile(
+        actionManifestJsPath,
+        `self.__RSC_SERVER_MANIFEST=${JSON.stringify(json)}`,
+        'utf-8'
+      )
+    }
+
+    async function writeSubresourceIntegrityManifest(): Promise<void> {
+      if (!sriEnabled) return
+      const sriManifest = mergeSubresourceIntegrityManifests(
+        subresourceIntegrityManifests.values()
+      )
+      const sriManifestJsonPath = path.join(
+        distDir,
+        'server',
+        `${SUBRESOURCE_INTEGRITY_MANIFEST}.json`
+      )
+      const sriManifestJsPath = path.join(
+        distDir,
+        'server',
+        `${SUBRESOURCE_INTEGRITY_MANIFEST}.js`
+      )
+      const json = JSON.stringify(sriManifest, null, 2)
+      await clearCache(sriManifestJsonPath)
+      await clearCache(sriManifestJsPath)
+      await writeFile(sriManifestJsonPath, json, 'utf-8')
+      await writeFile(
+        sriManifestJsPath,
+        `self.__SUBRESOURCE_INTEGRITY_MANIFEST=${JSON.stringify(json)}`,
+        'utf-8'
+      )
+    }
+
     async function writeFontManifest(): Promise<void> {
       // TODO: turbopack should write the correct
       // version of this
@@ -776,15 +919,15 @@ async function startWatcher(opts: SetupOpts) {
       )
     }
 
-    async function writeOtherManifests(): Promise<void> {
-      const loadableManifestPath = path.join(
-        distDir,
-        'react-loadable-manifest.json'
+    async function writeReactLoadableManifest(): Promise<void> {
+      const loadableManifest = mergeReactLoadableManifests(
+        reactLoadableManifests.values()
       )
+      const loadableManifestPath = path.join(distDir, REACT_LOADABLE_MANIFEST)
       await clearCache(loadableManifestPath)
       await writeFile(
         loadableManifestPath,
-        JSON.stringify({}, null, 2),
+        JSON.stringify(loadableManifest, null, 2),
         'utf-8'
       )
     }
@@ -851,7 +994,9 @@ async function startWatcher(opts: SetupOpts) {
     await writePagesManifest()
     await writeAppPathsManifest()
     await writeMiddlewareManifest()
-    await writeOtherManifests()
+    await writeActionManifest()
+    await writeSubresourceIntegrityManifest()
+    await writeReactLoadableManifest()
     await writeFontManifest()
 
     const turbopackHotReloader: NextJsHotReloaderInterface = {
@@ -1013,7 +1158,7 @@ async function startWatcher(opts: SetupOpts) {
           await writeBuildManifest()
           await writePagesManifest()
           await writeMiddlewareManifest()
-          await writeOtherManifests()
+          await writeReactLoadableManifest()
 
           return
         }
@@ -1114,6 +1259,7 @@ async function startWatcher(opts: SetupOpts) {
 
             await loadBuildManifest(page)
             await loadPagesManifest(page)
+            await loadSubresourceIntegrityManifest(page)
             if (type === 'edge') {
               await loadMiddlewareManifest(page, 'pages')
             } else {
@@ -1123,7 +1269,8 @@ async function startWatcher(opts: SetupOpts) {
             await writeBuildManifest()
             await writePagesManifest()
             await writeMiddlewareManifest()
-            await writeOtherManifests()
+            await writeSubresourceIntegrityManifest()
+            await writeReactLoadableManifest()
 
             processIssues(page, writtenEndpoint, true)
 
@@ -1149,7 +1296,7 @@ async function startWatcher(opts: SetupOpts) {
 
             await writePagesManifest()
             await writeMiddlewareManifest()
-            await writeOtherManifests()
+            await writeReactLoadableManifest()
 
             processIssues(page, writtenEndpoint, true)
 
@@ -1175,12 +1322,17 @@ async function startWatcher(opts: SetupOpts) {
             await loadAppBuildManifest(page)
             await loadBuildManifest(page, 'app')
             await loadAppPathManifest(page, 'app')
+            await loadActionManifest(page)
+            await loadReactLoadableManifest(page)
+            await loadSubresourceIntegrityManifest(page, 'app')
 
             await writeAppBuildManifest()
             await writeBuildManifest()
             await writeAppPathsManifest()
             await writeMiddlewareManifest()
-            await writeOtherManifests()
+            await writeActionManifest()
+            await writeSubresourceIntegrityManifest()
+            await writeReactLoadableManifest()
 
             processIssues(page, writtenEndpoint, true)
 
@@ -1204,7 +1356,7 @@ async function startWatcher(opts: SetupOpts) {
             await writeAppPathsManifest()
             await writeMiddlewareManifest()
             await writeMiddlewareManifest()
-            await writeOtherManifests()
+            await writeReactLoadableManifest()
 
             processIssues(page, writtenEndpoint, true)
 
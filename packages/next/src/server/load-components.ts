@@ -17,6 +17,7 @@ import {
   REACT_LOADABLE_MANIFEST,
   CLIENT_REFERENCE_MANIFEST,
   SERVER_REFERENCE_MANIFEST,
+  SUBRESOURCE_INTEGRITY_MANIFEST,
 } from '../shared/lib/constants'
 import { join } from 'path'
 import { requirePage } from './require'
@@ -91,10 +92,12 @@ async function loadComponentsImpl({
   distDir,
   pathname,
   isAppPath,
+  sriEnabled,
 }: {
   distDir: string
   pathname: string
   isAppPath: boolean
+  sriEnabled?: boolean
 }): Promise<LoadComponentsReturnType> {
   let DocumentMod = {}
   let AppMod = {}
@@ -120,6 +123,7 @@ async function loadComponentsImpl({
     reactLoadableManifest,
     clientReferenceManifest,
     serverActionsManifest,
+    subresourceIntegrityManifest,
   ] = await Promise.all([
     loadManifestWithRetries<BuildManifest>(join(distDir, BUILD_MANIFEST)),
     loadManifestWithRetries<ReactLoadableManifest>(
@@ -145,6 +149,11 @@ async function loadComponentsImpl({
           join(distDir, 'server', SERVER_REFERENCE_MANIFEST + '.json')
         ).catch(() => null)
       : null,
+    isAppPath && sriEnabled
+      ? loadManifestWithRetries<Record<string, string>>(
+          join(distDir, 'server', SUBRESOURCE_INTEGRITY_MANIFEST + '.json')
+        ).catch(() => undefined)
+      : undefined,
   ])
 
   const Component = interopDefault(ComponentMod)
@@ -159,6 +168,7 @@ async function loadComponentsImpl({
     Document,
     Component,
     buildManifest,
+    subresourceIntegrityManifest,
     reactLoadableManifest,
     pageConfig: ComponentMod.config || {},
     ComponentMod,
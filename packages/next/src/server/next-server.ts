@@ -603,6 +603,7 @@ export default class NextNodeServer extends BaseServer {
     query,
     params,
     isAppPath,
+    sriEnabled,
   }: {
     pathname: string
     query: NextParsedUrlQuery
@@ -628,6 +629,7 @@ export default class NextNodeServer extends BaseServer {
           query,
           params,
           isAppPath,
+          sriEnabled,
         })
     )
   }
@@ -637,11 +639,13 @@ export default class NextNodeServer extends BaseServer {
     query,
     params,
     isAppPath,
+    sriEnabled,
   }: {
     pathname: string
     query: NextParsedUrlQuery
     params: Params
     isAppPath: boolean
+    sriEnabled?: boolean
   }): Promise<FindComponentsResult | null> {
     const paths: string[] = [pathname]
     if (query.amp) {
@@ -666,6 +670,7 @@ export default class NextNodeServer extends BaseServer {
           distDir: this.distDir,
           pathname: pagePath,
           isAppPath,
+          sriEnabled,
         })
 
         if (
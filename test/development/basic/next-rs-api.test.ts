@@ -202,6 +202,31 @@ describe('next.rs api', () => {
     entrypointsSubscribtion.return()
   })
 
+  it('should dispose root tasks when subscriptions are returned', async () => {
+    const { rootTaskCount } = (await loadBindings()).turbo
+    // Release builds of the native bindings don't export the counter
+    if (!rootTaskCount) {
+      return
+    }
+    const before = rootTaskCount()
+
+    for (let i = 0; i < 10; i++) {
+      const entrypointsSubscribtion = project.entrypointsSubscribe()
+      const entrypoints = await entrypointsSubscribtion.next()
+      expect(entrypoints.done).toBe(false)
+      expect(rootTaskCount()).toBe(before + 1)
+      await entrypointsSubscribtion.return()
+      expect(rootTaskCount()).toBe(before)
+
+      const hmrIdentifiersSubscription = project.hmrIdentifiersSubscribe()
+      const identifiers = await hmrIdentifiersSubscription.next()
+      expect(identifiers.done).toBe(false)
+      expect(rootTaskCount()).toBe(before + 1)
+      await hmrIdentifiersSubscription.return()
+      expect(rootTaskCount()).toBe(before)
+    }
+  })
+
   const routes = [
     {
       name: 'root page',
@@ -0,0 +1,28 @@
+import { createNextDescribe } from 'e2e-utils'
+
+createNextDescribe(
+  'app-dir edge SSR built with turbopack',
+  {
+    files: __dirname,
+    skipDeployment: true,
+    buildCommand: 'yarn next build --experimental-turbo',
+  },
+  ({ next, isNextStart }) => {
+    if (!isNextStart) {
+      it('should only run for next start', () => {})
+      return
+    }
+
+    it('should render edge pages with the build id', async () => {
+      const buildId = (await next.readFile('.next/BUILD_ID')).trim()
+      const html = await next.render('/')
+      expect(html).toContain('<p>Edge!</p>')
+      expect(html).toContain(buildId)
+    })
+
+    it('should render edge pages with the complete next.config.js', async () => {
+      const html = await next.render('/')
+      expect(html).toContain('?dpl=test-deployment-id')
+    })
+  }
+)
@@ -0,0 +1,7 @@
+module.exports = {
+  experimental: {
+    // Turbopack doesn't know about this option, it has to be passed through
+    // from the config Next.js resolved.
+    deploymentId: 'test-deployment-id',
+  },
+}
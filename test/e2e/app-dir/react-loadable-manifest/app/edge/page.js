@@ -0,0 +1,9 @@
+import dynamic from 'next/dynamic'
+
+export const runtime = 'edge'
+
+const Text = dynamic(() => import('./text'))
+
+export default function Page() {
+  return <Text>hello from edge</Text>
+}
@@ -0,0 +1,5 @@
+'use client'
+
+export default function Text({ children }) {
+  return <p id="text">{children}</p>
+}
@@ -0,0 +1,7 @@
+import dynamic from 'next/dynamic'
+
+const Text = dynamic(() => import('./text'))
+
+export default function Page() {
+  return <Text>hello from node</Text>
+}
@@ -0,0 +1,56 @@
+import { createNextDescribe } from 'e2e-utils'
+
+createNextDescribe(
+  'react-loadable-manifest',
+  {
+    files: __dirname,
+    // Only turbopack writes per-page loadable manifests in development.
+    turbo: true,
+  },
+  ({ next, isNextDev, isTurbopack }) => {
+    it('includes the next/dynamic imports of each rendered page', async () => {
+      for (const [pathname, text] of [
+        ['/', 'hello from node'],
+        ['/edge', 'hello from edge'],
+      ]) {
+        const $ = await next.render$(pathname)
+        expect($('#text').text()).toBe(text)
+      }
+
+      const manifest = JSON.parse(
+        await next.readFile('.next/react-loadable-manifest.json')
+      )
+      const entries = Object.values<{ id: string; files: string[] }>(manifest)
+      expect(entries.length).toBeGreaterThanOrEqual(2)
+
+      for (const { id, files } of entries) {
+        expect(id).toBeDefined()
+        expect(files.length).toBeGreaterThan(0)
+        for (const file of files) {
+          const res = await next.fetch(`/_next/${file}`)
+          expect(res.status).toBe(200)
+        }
+      }
+    })
+
+    // Turbopack emits a loadable manifest next to each edge page in dev.
+    if (isTurbopack && isNextDev) {
+      it('loads the manifest of edge pages from the global scope', async () => {
+        await next.render$('/edge')
+
+        const middlewareManifest = JSON.parse(
+          await next.readFile('.next/server/middleware-manifest.json')
+        )
+        const { files } = middlewareManifest.functions['/edge/page']
+        expect(files).toContain(
+          'server/app/edge/page/react-loadable-manifest.js'
+        )
+        expect(
+          await next.readFile(
+            '.next/server/app/edge/page/react-loadable-manifest.js'
+          )
+        ).toStartWith('self.__REACT_LOADABLE_MANIFEST=')
+      })
+    }
+  }
+)
@@ -0,0 +1,45 @@
+import { createNextDescribe } from 'e2e-utils'
+import crypto from 'crypto'
+
+createNextDescribe(
+  'subresource-integrity',
+  {
+    files: __dirname,
+    // Only turbopack writes the integrity manifest in development.
+    turbo: true,
+  },
+  ({ next }) => {
+    it.each(['/', '/edge'])(
+      'includes an integrity attribute on scripts of %s',
+      async (pathname) => {
+        const $ = await next.render$(pathname)
+
+        // Find all the script tags with src attributes.
+        const elements = $('script[src]')
+        expect(elements.length).toBeGreaterThan(0)
+
+        const files: [string, string][] = []
+        elements.each((i, el) => {
+          const integrity = el.attribs['integrity']
+          expect(integrity).toStartWith('sha256-')
+          files.push([el.attribs['src'], integrity])
+        })
+
+        // The integrity attribute has to be the hash of the script.
+        for (const [src, integrity] of files) {
+          const res = await next.fetch(src)
+          expect(res.status).toBe(200)
+          const content = await res.text()
+
+          const hash = crypto
+            .createHash('sha256')
+            .update(content)
+            .digest()
+            .toString('base64')
+
+          expect(integrity).toEndWith(hash)
+        }
+      }
+    )
+  }
+)
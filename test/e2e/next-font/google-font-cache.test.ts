@@ -0,0 +1,91 @@
+import { createHash } from 'crypto'
+import { createNext, FileRef } from 'e2e-utils'
+import { NextInstance } from 'test/lib/next-modes/base'
+import { join } from 'path'
+import webdriver from 'next-webdriver'
+
+const mockedGoogleFontResponses = require.resolve(
+  './google-font-mocked-responses.js'
+)
+
+// Mocked as unavailable in google-font-mocked-responses.js
+const STYLESHEET_URL =
+  'https://fonts.googleapis.com/css2?family=Inter:wght@400&display=swap'
+const FONT_URL = 'https://fonts.gstatic.com/s/inter/v12/cached.woff2'
+
+const CACHED_STYLESHEET = `
+/* latin */
+@font-face {
+  font-family: 'Inter';
+  font-style: normal;
+  font-weight: 400;
+  font-display: swap;
+  src: url(${FONT_URL}) format('woff2');
+}
+`
+
+// Turbopack caches Google Fonts responses in the cache/google-fonts directory
+// of distDir, named after the SHA-256 of their url.
+function cachePath(url: string, extension: string) {
+  const hash = createHash('sha256').update(url).digest('hex')
+  return `.next/cache/google-fonts/${hash}.${extension}`
+}
+
+describe('next/font/google cache', () => {
+  const isDev = (global as any).isNextDev
+  let next: NextInstance
+
+  if ((global as any).isNextDeploy) {
+    it('should skip next deploy for now', () => {})
+    return
+  }
+
+  afterEach(() => next?.destroy())
+
+  if (isDev) {
+    it('should use the cached stylesheet and font files in dev', async () => {
+      next = await createNext({
+        files: {
+          pages: new FileRef(join(__dirname, 'google-fetch-error/pages')),
+          [cachePath(STYLESHEET_URL, 'css')]: CACHED_STYLESHEET,
+          [cachePath(FONT_URL, 'woff2')]: 'cached font',
+        },
+        env: {
+          NEXT_FONT_GOOGLE_MOCKED_RESPONSES: mockedGoogleFontResponses,
+        },
+        turbo: true,
+      })
+      const browser = await webdriver(next.url, '/')
+      const cachedFont = Buffer.from('cached font').toString('base64')
+
+      const sources: string[] = await browser.eval(`
+        Array.from(document.styleSheets)
+          .flatMap((sheet) => Array.from(sheet.cssRules))
+          .filter((rule) => rule instanceof CSSFontFaceRule)
+          .filter((rule) => !rule.style.fontFamily.includes('Fallback'))
+          .map((rule) => rule.style.getPropertyValue('src'))
+      `)
+      expect(sources).toEqual([
+        `url("data:font/woff2;base64,${cachedFont}") format("woff2")`,
+      ])
+    })
+  } else {
+    it('should fail the turbopack build without a cached stylesheet', async () => {
+      next = await createNext({
+        files: {
+          pages: new FileRef(join(__dirname, 'google-fetch-error/pages')),
+        },
+        env: {
+          NEXT_FONT_GOOGLE_MOCKED_RESPONSES: mockedGoogleFontResponses,
+        },
+        buildCommand: 'yarn next build --experimental-turbo',
+        skipStart: true,
+      })
+
+      await expect(next.start()).rejects.toThrow('next build failed')
+      expect(next.cliOutput).toInclude(
+        'Failed to fetch font `Inter` from Google Fonts'
+      )
+    })
+  }
+})
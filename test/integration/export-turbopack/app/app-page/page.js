@@ -0,0 +1,3 @@
+export default function AppPage() {
+  return <p>Hello from the app dir</p>
+}
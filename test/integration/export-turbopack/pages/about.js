@@ -0,0 +1,7 @@
+export default function About({ title }) {
+  return <p>{title}</p>
+}
+
+export function getStaticProps() {
+  return { props: { title: 'About us' } }
+}
@@ -0,0 +1,14 @@
+export default function Post({ id }) {
+  return <p>Post {id}</p>
+}
+
+export function getStaticPaths() {
+  return {
+    paths: [{ params: { id: '1' } }, { params: { id: '2' } }],
+    fallback: false,
+  }
+}
+
+export function getStaticProps({ params }) {
+  return { props: { id: params.id } }
+}
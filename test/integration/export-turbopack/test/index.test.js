@@ -0,0 +1,63 @@
+/* eslint-env jest */
+
+import fs from 'fs-extra'
+import { join } from 'path'
+import { nextBuild } from 'next-test-utils'
+
+const appDir = join(__dirname, '../')
+const outDir = join(appDir, 'out')
+
+function readOutFile(file) {
+  return fs.readFile(join(outDir, file), 'utf8')
+}
+
+describe('Static export with Turbopack', () => {
+  beforeAll(async () => {
+    await fs.remove(outDir)
+    const { code } = await nextBuild(appDir, ['--experimental-turbo'], {
+      stdout: true,
+      stderr: true,
+    })
+    expect(code).toBe(0)
+  })
+
+  it('should export pages to HTML files', async () => {
+    expect(await readOutFile('index.html')).toContain(
+      'Hello from the index page'
+    )
+    expect(await readOutFile('about.html')).toContain('About us')
+  })
+
+  it('should export the paths of getStaticPaths', async () => {
+    expect(await readOutFile('posts/1.html')).toContain('Post <!-- -->1')
+    expect(await readOutFile('posts/2.html')).toContain('Post <!-- -->2')
+  })
+
+  it('should export the data of getStaticProps', async () => {
+    const buildId = (
+      await fs.readFile(join(appDir, '.next/BUILD_ID'), 'utf8')
+    ).trim()
+    const data = JSON.parse(
+      await readOutFile(`_next/data/${buildId}/about.json`)
+    )
+    expect(data.pageProps).toEqual({ title: 'About us' })
+  })
+
+  it('should export app pages to HTML and RSC payload files', async () => {
+    expect(await readOutFile('app-page.html')).toContain(
+      'Hello from the app dir'
+    )
+    expect(await readOutFile('app-page.txt')).toContain(
+      'Hello from the app dir'
+    )
+  })
+
+  it('should export a 404 page', async () => {
+    expect(await fs.pathExists(join(outDir, '404.html'))).toBe(true)
+  })
+
+  it('should copy the public and static files', async () => {
+    expect(await readOutFile('robots.txt')).toBe('User-agent: *\n')
+    expect(await fs.pathExists(join(outDir, '_next/static'))).toBe(true)
+  })
+})
@@ -1,7 +1,11 @@
 /* eslint-env jest */
 import fs from 'fs-extra'
 import { join } from 'path'
-import { nextBuild, getPageFileFromBuildManifest } from 'next-test-utils'
+import {
+  File,
+  nextBuild,
+  getPageFileFromBuildManifest,
+} from 'next-test-utils'
 import { recursiveReadDir } from 'next/dist/lib/recursive-readdir'
 
 const appDir = join(__dirname, '../')
@@ -46,3 +50,59 @@ describe('Production browser sourcemaps', () => {
     runTests()
   })
 })
+
+describe('Production browser sourcemaps with Turbopack', () => {
+  const nextConfig = new File(join(appDir, 'next.config.js'))
+
+  // Only the chunks of the Turbopack build should be checked
+  beforeEach(() => fs.remove(join(appDir, '.next')))
+  afterAll(() => nextConfig.restore())
+
+  async function getChunkFiles() {
+    const files = await recursiveReadDir(join(appDir, '.next'))
+    return files.filter((file) => file.includes('/static/chunks/'))
+  }
+
+  function readFile(file) {
+    return fs.readFile(join(appDir, '.next', file), 'utf8')
+  }
+
+  it('links source maps with the original sources to all chunks', async () => {
+    await nextBuild(appDir, ['--experimental-turbo'], {})
+
+    const files = await getChunkFiles()
+    const jsFiles = files.filter((file) => file.endsWith('.js'))
+    expect(jsFiles).not.toHaveLength(0)
+
+    for (const file of jsFiles) {
+      expect(files).toContain(`${file}.map`)
+      expect(await readFile(file)).toContain('//# sourceMappingURL=')
+    }
+
+    const maps = await Promise.all(
+      jsFiles.map((file) => readFile(`${file}.map`))
+    )
+    expect(
+      maps.some(
+        (map) =>
+          map.includes('"sourcesContent"') &&
+          map.includes('export default function StaticPage')
+      )
+    ).toBe(true)
+  })
+
+  it('emits no source maps when productionBrowserSourceMaps is false', async () => {
+    nextConfig.replace(
+      'productionBrowserSourceMaps: true',
+      'productionBrowserSourceMaps: false'
+    )
+    await nextBuild(appDir, ['--experimental-turbo'], {})
+
+    const files = await getChunkFiles()
+    expect(files.filter((file) => file.endsWith('.map'))).toEqual([])
+
+    for (const file of files.filter((file) => file.endsWith('.js'))) {
+      expect(await readFile(file)).not.toContain('//# sourceMappingURL=')
+    }
+  })
+})
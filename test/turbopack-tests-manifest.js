@@ -60,6 +60,7 @@ const enabledTests = [
   'test/e2e/app-dir/next-config/index.test.ts',
   'test/e2e/app-dir/not-found/css-precedence/index.test.ts',
   'test/e2e/app-dir/not-found/group-route-root-not-found/index.test.ts',
+  'test/e2e/app-dir/react-loadable-manifest/react-loadable-manifest.test.ts',
   'test/e2e/app-dir/rewrites-redirects/rewrites-redirects.test.ts',
   'test/e2e/app-dir/root-layout-redirect/root-layout-redirect.test.ts',
   'test/e2e/app-dir/route-page-manifest-bug/route-page-manifest-bug.test.ts',
@@ -67,6 +68,7 @@ const enabledTests = [
   'test/e2e/app-dir/search-params-react-key/layout-params.test.ts',
   'test/e2e/app-dir/searchparams-static-bailout/searchparams-static-bailout.test.ts',
   'test/e2e/app-dir/similar-pages-paths/similar-pages-paths.test.ts',
+  'test/e2e/app-dir/subresource-integrity/subresource-integrity.test.ts',
   'test/e2e/app-dir/test-template/{{ toFileName name }}/{{ toFileName name }}.test.ts',
   'test/e2e/app-dir/third-parties/basic.test.ts',
   'test/e2e/app-dir/use-params/use-params.test.ts',
@@ -87,6 +89,7 @@ const enabledTests = [
   'test/e2e/i18n-disallow-multiple-locales/i18n-disallow-multiple-locales.test.ts',
   'test/e2e/link-with-api-rewrite/index.test.ts',
   'test/e2e/middleware-fetches-with-body/index.test.ts',
+  'test/e2e/next-font/google-font-cache.test.ts',
   'test/e2e/next-head/index.test.ts',
   'test/e2e/next-image-forward-ref/index.test.ts',
   'test/e2e/no-eslint-warn-with-no-eslint-config/index.test.ts',